@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -8,9 +10,38 @@ pub enum Error {
     Json(serde_json::Error),
     NotFoundSchema(String),
     NotSupported(String),
+    Schema(openapi_spec_schema::Error),
     Yaml(serde_yaml::Error),
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Arg(m) => write!(f, "{m}"),
+            Error::InvalidComponentPath(p) => write!(f, "Invalid component path `{p}`."),
+            Error::InvalidUri(u) => write!(f, "Invalid URI `{u}`."),
+            Error::Io(_) => write!(f, "I/O error."),
+            Error::Json(_) => write!(f, "Invalid JSON."),
+            Error::NotFoundSchema(s) => write!(f, "Not found schema `{s}`."),
+            Error::NotSupported(m) => write!(f, "Not supported: {m}"),
+            Error::Schema(e) => e.fmt(f),
+            Error::Yaml(_) => write!(f, "Invalid YAML."),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Json(e) => Some(e),
+            Error::Schema(e) => e.source(),
+            Error::Yaml(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(value: serde_json::Error) -> Self {
         Error::Json(value)
@@ -23,6 +54,12 @@ impl From<serde_yaml::Error> for Error {
     }
 }
 
+impl From<openapi_spec_schema::Error> for Error {
+    fn from(value: openapi_spec_schema::Error) -> Self {
+        Error::Schema(value)
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(value: std::io::Error) -> Self {
         Error::Io(value)
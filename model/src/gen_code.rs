@@ -1,6 +1,7 @@
 use super::error::Error;
 use super::{Config, SchemaItem, anonymous_ty};
 use openapi_spec_schema::model::Any;
+use openapi_spec_schema::resolve::Resolver;
 use openapi_spec_schema::{Schema, SchemaType, SchemaTypes};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
@@ -15,9 +16,13 @@ pub fn gen_code(output: &Path, schemas: &[SchemaItem]) -> Result<(), Error> {
     let mut structs = vec![];
     let mut progress = 0usize;
     for item in config.schemas {
-        if item.schema.all_of.is_some() {
-            panic!("Not supported `allOf`.");
-        }
+        let merged;
+        let item = if item.schema.all_of.is_some() {
+            merged = merge_all_of(&config, item)?;
+            &merged
+        } else {
+            item
+        };
 
         if item.schema.r#ref.is_some() {
             structs.push(get_type_alias(&config, item)?);
@@ -268,6 +273,26 @@ fn get_type_alias(config: &Config, item: &SchemaItem) -> Result<StructInfo, Erro
     })
 }
 
+/// `allOf` を一つのスキーマにまとめる。
+fn merge_all_of(config: &Config, item: &SchemaItem) -> Result<SchemaItem, Error> {
+    let resolver = ItemResolver { config, item };
+    let schema = item
+        .schema
+        .merge_all_of(&resolver)
+        .map_err(|e| Error::NotSupported(e.to_string()))?;
+    if schema.all_of.is_some() {
+        return Err(Error::NotSupported(format!(
+            "Not supported `allOf`: {}.",
+            item.schema_name
+        )));
+    }
+
+    Ok(SchemaItem {
+        schema,
+        ..item.clone()
+    })
+}
+
 /// unit variant のコードを生成する。
 fn gen_unit_variant(config: &Config, item: &SchemaItem) -> Result<StructInfo, Error> {
     let struct_name = config.types_name(&item.schema_name, item.duplicated);
@@ -325,25 +350,26 @@ fn gen_newtype_variant(config: &Config, item: &SchemaItem) -> Result<StructInfo,
     if let Some(any_of) = item.schema.any_of.as_ref() {
         for variant_schema in any_of {
             // TODO: anonymous
-            let r = variant_schema.r#ref.as_deref().unwrap();
+            let r = variant_schema.schema().r#ref.as_deref().unwrap();
             let variant_schema_def =
                 config.get_def_by_url(&item.domain_name, &item.schema_file_name, r)?;
             let (_, version) = config.modules_name(&variant_schema_def.schema_name);
-            variants.push((version_num(version), variant_schema_def));
+            variants.push((version_num(version), variant_schema_def, Some(r)));
         }
     }
 
     if let Some(one_of) = item.schema.one_of.as_ref() {
         for (i, variant_schema) in one_of.iter().enumerate() {
+            let variant_schema = variant_schema.schema();
             if let Some(r) = variant_schema.r#ref.as_deref() {
                 let variant_schema_def =
                     config.get_def_by_url(&item.domain_name, &item.schema_file_name, r)?;
                 let (_, version) = config.modules_name(&variant_schema_def.schema_name);
-                variants.push((version_num(version), variant_schema_def));
+                variants.push((version_num(version), variant_schema_def, Some(r)));
             } else if variant_schema.r#enum.is_some() {
                 let schema_name = format!("{}-{}", item.schema_name, i);
                 let variant_schema_def = config.get_def_by_name(&item.domain_name, &schema_name)?;
-                variants.push((i as u32, variant_schema_def));
+                variants.push((i as u32, variant_schema_def, None));
             } else {
                 dbg!(&item.schema);
                 panic!(
@@ -357,6 +383,9 @@ fn gen_newtype_variant(config: &Config, item: &SchemaItem) -> Result<StructInfo,
     variants.sort_unstable_by_key(|v| v.0);
     variants.reverse();
 
+    let tag = tagged_variants(config, item).map(|(property, _)| property);
+    let tagged = tag.is_some();
+
     let variant_idents = variants.iter().map(|v| {
         let variant_name = if v.0 == 0 {
             // バージョンがない場合はフラグメント
@@ -367,17 +396,35 @@ fn gen_newtype_variant(config: &Config, item: &SchemaItem) -> Result<StructInfo,
         };
         let variant_ident = format_ident!("{}", variant_name);
 
-        let ref_ty_ident = schema_ty(
-            config,
-            &v.1.domain_name,
-            &v.1.schema_file_name,
-            &v.1.schema_name,
-            "",
-            &v.1.schema,
-        )
-        .unwrap();
+        // discriminator value of the variant.
+        let rename =
+            v.2.filter(|_| tagged)
+                .and_then(|r| item.schema.discriminator_value(r))
+                .map(|value| quote! { #[serde(rename = #value)] });
+
+        let ref_ty_ident = match &tag {
+            // the tag property is read and written by the enum.
+            Some(property) => {
+                let mut type_names =
+                    config.ref_types_name(&v.1.domain_name, &v.1.schema_name, v.1.duplicated);
+                let name = type_names.pop().unwrap();
+                type_names.push(tag_payload_name(config, &name, property));
+                let type_idents = type_names.iter().map(|n| format_ident!("{n}"));
+                quote! { #(#type_idents)::* }
+            }
+            None => schema_ty(
+                config,
+                &v.1.domain_name,
+                &v.1.schema_file_name,
+                &v.1.schema_name,
+                "",
+                &v.1.schema,
+            )
+            .unwrap(),
+        };
 
         quote! {
+            #rename
             #variant_ident(#ref_ty_ident)
         }
     });
@@ -401,6 +448,12 @@ fn gen_newtype_variant(config: &Config, item: &SchemaItem) -> Result<StructInfo,
         }
     });
 
+    // internally tagged by the discriminator property.
+    let serde_tag = match &tag {
+        Some(property) => quote! { #[serde(tag = #property)] },
+        None => quote! { #[serde(untagged)] },
+    };
+
     let (module_name, version) = config.modules_name(&item.schema_name);
 
     Ok(StructInfo {
@@ -410,7 +463,7 @@ fn gen_newtype_variant(config: &Config, item: &SchemaItem) -> Result<StructInfo,
         name: struct_name,
         token: quote! {
             #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
-            #[serde(untagged)]
+            #serde_tag
             pub enum #struct_ident {
                 #(#variant_idents),*
             }
@@ -422,14 +475,57 @@ fn gen_newtype_variant(config: &Config, item: &SchemaItem) -> Result<StructInfo,
 }
 
 /// struct のコードを生成する。
+///
+/// 列挙型の variant になる構造体は、タグのプロパティを除いた variant 用の構造体も生成する。
 fn gen_struct(config: &Config, item: &SchemaItem) -> Result<Vec<StructInfo>, Error> {
     let struct_name = config.types_name(&item.schema_name, item.duplicated);
-    let struct_ident = format_ident!("{struct_name}");
+    let (module_name, version) = config.modules_name(&item.schema_name);
 
     let mut structs = vec![];
+    let mut names = vec![(struct_name.clone(), None)];
+    for tag in tag_properties(config, item) {
+        names.push((tag_payload_name(config, &struct_name, &tag), Some(tag)));
+    }
+
+    for (name, tag) in names {
+        let struct_ident = format_ident!("{name}");
+        let property_info = gen_properties(config, item, tag.as_deref())?;
+        let token_properties = property_info.iter().map(|p| &p.token);
+
+        structs.push(StructInfo {
+            domain: item.domain_name.clone(),
+            module: module_name.clone(),
+            version,
+            name,
+            token: quote! {
+                #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+                pub struct #struct_ident {
+                    #(#token_properties),*
+                }
+            },
+            use_serde: true,
+        });
+    }
+
+    Ok(structs)
+}
+
+/// struct のフィールドのコードを生成する。
+///
+/// `tag` のプロパティは列挙型のタグとして読み書きするため除く。
+fn gen_properties(
+    config: &Config,
+    item: &SchemaItem,
+    tag: Option<&str>,
+) -> Result<Vec<PropertyInfo>, Error> {
     let mut property_info = vec![];
     if let Some(properties) = item.schema.properties.as_ref() {
         for (prop_name, prop_schema) in properties {
+            if Some(prop_name.as_str()) == tag {
+                continue;
+            }
+
+            let prop_schema = prop_schema.schema();
             let mut derive = quote! { #[serde(rename = #prop_name)] };
 
             let field_name = config.fields_name(prop_name);
@@ -479,25 +575,60 @@ fn gen_struct(config: &Config, item: &SchemaItem) -> Result<Vec<StructInfo>, Err
     }
 
     property_info.sort_unstable_by_key(|p| p.name.clone());
-    let token_properties = property_info.iter().map(|p| &p.token);
+    Ok(property_info)
+}
 
-    let (module_name, version) = config.modules_name(&item.schema_name);
+/// タグのプロパティを除いた variant 用の構造体の名前を取得する。
+fn tag_payload_name(config: &Config, struct_name: &str, tag: &str) -> String {
+    format!("{struct_name}Without{}", config.enum_variants_name(tag))
+}
 
-    structs.push(StructInfo {
-        domain: item.domain_name.clone(),
-        module: module_name,
-        version,
-        name: struct_name,
-        token: quote! {
-            #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
-            pub struct #struct_ident {
-                #(#token_properties),*
-            }
-        },
-        use_serde: true,
-    });
+/// discriminator で判別する列挙型のタグのプロパティ名と各 variant を取得する。
+///
+/// 構造体でない variant があり internally tagged にできない場合は `None` を返却する。
+fn tagged_variants<'a>(
+    config: &Config<'a>,
+    item: &SchemaItem,
+) -> Option<(String, Vec<&'a SchemaItem>)> {
+    let discriminator = item.schema.discriminator()?;
 
-    Ok(structs)
+    let mut variants = vec![];
+    for variant_schema in item
+        .schema
+        .one_of
+        .iter()
+        .chain(&item.schema.any_of)
+        .flatten()
+    {
+        let r = variant_schema.schema().r#ref.as_deref()?;
+        let variant_schema_def = config
+            .get_def_by_url(&item.domain_name, &item.schema_file_name, r)
+            .ok()?;
+        if !struct_ty(&variant_schema_def.schema) {
+            return None;
+        }
+        variants.push(variant_schema_def);
+    }
+
+    Some((discriminator.property_name.clone(), variants))
+}
+
+/// 構造体が variant になる列挙型のタグのプロパティ名を取得する。
+fn tag_properties(config: &Config, item: &SchemaItem) -> Vec<String> {
+    let mut properties = vec![];
+    for parent in config.schemas {
+        if parent.schema.discriminator().is_none() {
+            continue;
+        }
+
+        if let Some((property, variants)) = tagged_variants(config, parent) {
+            if variants.iter().any(|v| v.r#ref() == item.r#ref()) && !properties.contains(&property)
+            {
+                properties.push(property);
+            }
+        }
+    }
+    properties
 }
 
 /// スキーマの Rust 型を取得する。
@@ -549,7 +680,7 @@ fn schema_array_ty(
     prop_name: &str,
     schema: &Schema,
 ) -> Result<TokenStream, Error> {
-    let elem_schema = schema.items.as_ref().unwrap();
+    let elem_schema = schema.items.as_ref().unwrap().schema();
     let elem_ty = schema_ty(
         config,
         domain,
@@ -634,6 +765,15 @@ fn primitive(schema: &Schema) -> bool {
     }
 }
 
+/// スキーマが構造体かどうか判定する。
+fn struct_ty(schema: &Schema) -> bool {
+    schema.r#ref.is_none()
+        && schema.any_of.is_none()
+        && schema.one_of.is_none()
+        && schema.r#enum.is_none()
+        && !primitive(schema)
+}
+
 /// スキーマが null 許容かどうか判定する。
 fn optional(prop_schema: &Schema) -> bool {
     prop_schema.nullable.unwrap_or(false)
@@ -690,6 +830,27 @@ impl StructInfo {
 
 // ---------------------------------------------------------------------------
 
+/// スキーマの参照を解決する。
+struct ItemResolver<'a> {
+    config: &'a Config<'a>,
+    item: &'a SchemaItem,
+}
+
+impl Resolver for ItemResolver<'_> {
+    fn resolve_schema(&self, _base: Option<&str>, reference: &str) -> Option<&Schema> {
+        self.config
+            .get_def_by_url(
+                &self.item.domain_name,
+                &self.item.schema_file_name,
+                reference,
+            )
+            .ok()
+            .map(|s| &s.schema)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
 struct PropertyInfo {
     name: String,
     token: TokenStream,
@@ -741,3 +902,138 @@ fn versions(items: &[&StructInfo]) -> Vec<String> {
         .map(|&k| versions.iter().find(|v| v.0 == k).unwrap().1.clone())
         .collect::<Vec<String>>()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pets::{Ball, Cat, CatWithoutPetType, Dog, DogWithoutPetType, Pet, Toy, ToyN1};
+
+    fn item(name: &str, schema: &str) -> SchemaItem {
+        SchemaItem {
+            domain_name: "pets".to_string(),
+            schema_file_name: "openapi.yaml".to_string(),
+            schema_name: name.to_string(),
+            schema: serde_json::from_str(schema).unwrap(),
+            anony: false,
+            duplicated: false,
+        }
+    }
+
+    fn schemas() -> Vec<SchemaItem> {
+        vec![
+            item(
+                "Pet",
+                r##"{
+                    "oneOf": [
+                        {"$ref": "#/components/schemas/Dog"},
+                        {"$ref": "#/components/schemas/Cat"}
+                    ],
+                    "discriminator": {
+                        "propertyName": "petType",
+                        "mapping": {"dog": "#/components/schemas/Dog"}
+                    }
+                }"##,
+            ),
+            item(
+                "Dog",
+                r#"{
+                    "type": "object",
+                    "required": ["petType", "bark"],
+                    "properties": {"petType": {"type": "string"}, "bark": {"type": "string"}}
+                }"#,
+            ),
+            item(
+                "Cat",
+                r#"{
+                    "type": "object",
+                    "required": ["petType"],
+                    "properties": {"petType": {"type": "string"}, "lives": {"type": "integer"}}
+                }"#,
+            ),
+            item(
+                "Toy",
+                r##"{
+                    "oneOf": [{"$ref": "#/components/schemas/Ball"}, {"enum": ["none"]}],
+                    "discriminator": {"propertyName": "kind"}
+                }"##,
+            ),
+            item("Toy-1", r#"{"type": "string", "enum": ["none"]}"#),
+            item(
+                "Ball",
+                r#"{
+                    "type": "object",
+                    "required": ["kind"],
+                    "properties": {"kind": {"type": "string"}}
+                }"#,
+            ),
+        ]
+    }
+
+    // formatted the same regardless of the layout of `code`.
+    fn format(code: &str) -> String {
+        prettyplease::unparse(&syn::parse_file(code).unwrap())
+    }
+
+    fn round_trip<T>(payload: &str) -> T
+    where
+        T: for<'de> serde::Deserialize<'de> + serde::Serialize,
+    {
+        let value = serde_json::from_str::<serde_json::Value>(payload).unwrap();
+        let v = serde_json::from_value::<T>(value.clone()).unwrap();
+        assert_eq!(value, serde_json::to_value(&v).unwrap());
+        v
+    }
+
+    #[test]
+    fn gen_code_discriminator() {
+        let output = tempfile::tempdir().unwrap();
+        gen_code(output.path(), &schemas()).unwrap();
+
+        // `crate::pets` is compiled from the expected code.
+        let code = fs::read_to_string(output.path().join("pets").join("mod.rs")).unwrap();
+        let expected = include_str!("../testdata/pets/mod.rs");
+        assert_eq!(format(expected), format(&code));
+    }
+
+    #[test]
+    fn gen_code_discriminator_round_trip() {
+        let dog = round_trip::<Pet>(r#"{"petType": "dog", "bark": "woof"}"#);
+        assert_eq!(
+            Pet::Dog(DogWithoutPetType {
+                bark: "woof".to_string()
+            }),
+            dog
+        );
+
+        let cat = round_trip::<Pet>(r#"{"petType": "Cat", "lives": 9}"#);
+        assert_eq!(Pet::Cat(CatWithoutPetType { lives: Some(9) }), cat);
+        round_trip::<Pet>(r#"{"petType": "Cat"}"#);
+
+        assert!(serde_json::from_str::<Pet>(r#"{"petType": "Dog", "bark": ""}"#).is_err());
+        assert!(serde_json::from_str::<Pet>(r#"{"bark": ""}"#).is_err());
+
+        // the struct keeps the tag property.
+        let dog = round_trip::<Dog>(r#"{"petType": "dog", "bark": "woof"}"#);
+        assert_eq!("dog", dog.pet_type);
+        let cat = round_trip::<Cat>(r#"{"petType": "Cat"}"#);
+        assert_eq!(
+            Cat {
+                lives: None,
+                pet_type: "Cat".to_string()
+            },
+            cat
+        );
+        assert!(serde_json::from_str::<Dog>(r#"{"bark": "woof"}"#).is_err());
+
+        // not tagged by the enum variant.
+        let ball = round_trip::<Toy>(r#"{"kind": "ball"}"#);
+        assert_eq!(
+            Toy::Ball(Ball {
+                kind: "ball".to_string()
+            }),
+            ball
+        );
+        let none = round_trip::<Toy>(r#""none""#);
+        assert_eq!(Toy::V000001(ToyN1::None), none);
+    }
+}
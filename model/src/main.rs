@@ -1,15 +1,18 @@
 mod error;
 mod gen_code;
+#[cfg(test)]
+#[path = "../testdata/pets/mod.rs"]
+mod pets;
 
 use self::error::Error;
 use self::gen_code::gen_code;
+use openapi_spec_schema::load;
 use openapi_spec_schema::{
     OpenApi, Operation, PartOpenApi, ReferenceOr, RequestBody, Response, Schema, SchemaType,
     SchemaTypes,
 };
 use std::env;
-use std::fs::{self, File};
-use std::io::prelude::*;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -51,53 +54,11 @@ fn main() -> Result<(), Error> {
 }
 
 fn read_openapi(file: &Path) -> Result<OpenApi, Error> {
-    let mut content = String::new();
-    File::open(file)?.read_to_string(&mut content)?;
-
-    match file
-        .extension()
-        .ok_or(Error::arg("Not found extension"))?
-        .to_str()
-        .unwrap()
-    {
-        "yml" => from_yml(&content),
-        "yaml" => from_yml(&content),
-        "json" => from_json(&content),
-        _ => panic!("Not supported format."),
-    }
-}
-
-fn from_json(content: &str) -> Result<OpenApi, Error> {
-    Ok(serde_json::from_str::<OpenApi>(content)?)
-}
-
-fn from_yml(content: &str) -> Result<OpenApi, Error> {
-    Ok(serde_yaml::from_str::<OpenApi>(content)?)
+    Ok(load::from_path(file)?)
 }
 
 fn read_part_openapi(file: &Path) -> Result<PartOpenApi, Error> {
-    let mut content = String::new();
-    File::open(file)?.read_to_string(&mut content)?;
-
-    match file
-        .extension()
-        .ok_or(Error::arg("Not found extension"))?
-        .to_str()
-        .unwrap()
-    {
-        "yml" => from_part_yml(&content),
-        "yaml" => from_part_yml(&content),
-        "json" => from_part_json(&content),
-        _ => panic!("Not supported format."),
-    }
-}
-
-fn from_part_json(content: &str) -> Result<PartOpenApi, Error> {
-    Ok(serde_json::from_str::<PartOpenApi>(content)?)
-}
-
-fn from_part_yml(content: &str) -> Result<PartOpenApi, Error> {
-    Ok(serde_yaml::from_str::<PartOpenApi>(content)?)
+    Ok(load::from_path(file)?)
 }
 
 /// 再帰的にディレクトリを探索して openapi.yaml ファイルからスキーマを収集する。
@@ -188,6 +149,14 @@ fn collect_schemas(
             if let Some(op) = item.trace {
                 collect_schema(root, entry_file, &path, "TRACE", &op, scaned_files, schemas)?;
             }
+
+            if let Some(op) = item.query {
+                collect_schema(root, entry_file, &path, "QUERY", &op, scaned_files, schemas)?;
+            }
+
+            for (method, op) in item.additional_operations.into_iter().flatten() {
+                collect_schema(root, entry_file, &path, &method, &op, scaned_files, schemas)?;
+            }
         }
     }
 
@@ -284,7 +253,7 @@ fn collect_request_schema(
             collect_anonymous_or_child(
                 root,
                 entry_file,
-                schema,
+                schema.schema(),
                 &schema_name,
                 true,
                 scaned_files,
@@ -324,7 +293,7 @@ fn collect_response_schema(
                 collect_anonymous_or_child(
                     root,
                     entry_file,
-                    schema,
+                    schema.schema(),
                     &schema_name,
                     true,
                     scaned_files,
@@ -450,7 +419,7 @@ fn collect_schema_child(
         collect_anonymous_or_child(
             root,
             entry_file,
-            i,
+            i.schema(),
             parent_name,
             another_file,
             scaned_files,
@@ -464,7 +433,7 @@ fn collect_schema_child(
             collect_anonymous_or_child(
                 root,
                 entry_file,
-                s,
+                s.schema(),
                 &schema_name,
                 another_file,
                 scaned_files,
@@ -479,7 +448,7 @@ fn collect_schema_child(
             collect_anonymous_or_child(
                 root,
                 entry_file,
-                s,
+                s.schema(),
                 &schema_name,
                 another_file,
                 scaned_files,
@@ -558,7 +527,7 @@ fn collect_reference(
                             domain_name: domain_name(root, &version),
                             schema_file_name: file_name.to_string(),
                             schema_name: schema_name.to_string(),
-                            schema: schema.clone(),
+                            schema: schema.as_ref().clone(),
                             anony: false,
                             duplicated: false,
                         };
@@ -610,7 +579,7 @@ fn collect_schema_property(
             collect_anonymous_or_child(
                 root,
                 entry_file,
-                property,
+                property.schema(),
                 &schema_name,
                 another_file,
                 scaned_files,
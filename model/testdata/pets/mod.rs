@@ -0,0 +1,61 @@
+// `gen_code` の出力 (`gen_code::tests::schemas`)。
+use serde::{Deserialize, Serialize};
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Ball {
+    #[serde(rename = "kind")]
+    pub kind: String,
+}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Cat {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "lives")]
+    pub lives: Option<i64>,
+    #[serde(rename = "petType")]
+    pub pet_type: String,
+}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CatWithoutPetType {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "lives")]
+    pub lives: Option<i64>,
+}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Dog {
+    #[serde(rename = "bark")]
+    pub bark: String,
+    #[serde(rename = "petType")]
+    pub pet_type: String,
+}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct DogWithoutPetType {
+    #[serde(rename = "bark")]
+    pub bark: String,
+}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "petType")]
+pub enum Pet {
+    #[serde(rename = "Cat")]
+    Cat(crate::pets::CatWithoutPetType),
+    #[serde(rename = "dog")]
+    Dog(crate::pets::DogWithoutPetType),
+}
+impl Default for Pet {
+    fn default() -> Self {
+        Self::Cat(Default::default())
+    }
+}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Toy {
+    V000001(crate::pets::ToyN1),
+    Ball(crate::pets::Ball),
+}
+impl Default for Toy {
+    fn default() -> Self {
+        Self::V000001(Default::default())
+    }
+}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum ToyN1 {
+    #[default]
+    #[serde(rename = "none")]
+    None,
+}
@@ -0,0 +1,156 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use openapi_spec_schema::{OpenApi, borrowed};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// counts bytes in use, to compare the memory of the models.
+struct Counter;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+// a document like a bundle of Redfish schemas.
+fn fixture(schemas: usize) -> serde_json::Value {
+    let mut components = serde_json::Map::new();
+    let mut paths = serde_json::Map::new();
+    for i in 0..schemas {
+        let name = format!("Resource{i}");
+        components.insert(
+            name.clone(),
+            serde_json::json!({
+                "type": "object",
+                "description": format!("The {name} schema describes a resource of the service."),
+                "x-longDescription": format!("This resource shall represent the {name} resource."),
+                "required": ["@odata.id", "Id", "Name"],
+                "properties": {
+                    "@odata.id": {"$ref": "#/components/schemas/odata-v4_id"},
+                    "Id": {"type": "string", "readOnly": true},
+                    "Name": {"type": "string", "description": "The name of the resource."},
+                    "Status": {"$ref": "#/components/schemas/Resource_Status"},
+                    "Links": {
+                        "type": "object",
+                        "properties": {
+                            "Parent": {"$ref": format!("#/components/schemas/Resource{}", i / 2)}
+                        }
+                    }
+                }
+            }),
+        );
+        paths.insert(
+            format!("/redfish/v1/Resources/{i}"),
+            serde_json::json!({
+                "get": {
+                    "operationId": format!("get{name}"),
+                    "responses": {
+                        "200": {
+                            "description": "The resource.",
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": format!("#/components/schemas/{name}")}
+                                }
+                            }
+                        }
+                    }
+                }
+            }),
+        );
+    }
+    serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": "Redfish", "version": "1.0.0"},
+        "paths": paths,
+        "components": {"schemas": components}
+    })
+}
+
+// bytes retained by the value which `parse` returns.
+fn retained<T>(parse: impl Fn() -> T) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let value = parse();
+    let after = ALLOCATED.load(Ordering::Relaxed);
+    drop(value);
+    after - before
+}
+
+fn parse(c: &mut Criterion) {
+    let fixture = fixture(2000);
+    let json = serde_json::to_string(&fixture).unwrap();
+    let yaml = serde_yaml::to_string(&fixture).unwrap();
+
+    for (name, bytes) in [
+        (
+            "json/owned",
+            retained(|| serde_json::from_str::<OpenApi>(&json).unwrap()),
+        ),
+        (
+            "json/borrowed",
+            retained(|| serde_json::from_str::<borrowed::OpenApi>(&json).unwrap()),
+        ),
+        (
+            "yaml/owned",
+            retained(|| serde_yaml::from_str::<OpenApi>(&yaml).unwrap()),
+        ),
+        (
+            "yaml/borrowed",
+            retained(|| serde_yaml::from_str::<borrowed::OpenApi>(&yaml).unwrap()),
+        ),
+    ] {
+        println!("{name}: {} KiB retained", bytes / 1024);
+    }
+
+    let mut group = c.benchmark_group("parse");
+    group.sample_size(10);
+    group.bench_function("json/owned", |b| {
+        b.iter(|| serde_json::from_str::<OpenApi>(black_box(&json)).unwrap())
+    });
+    group.bench_function("json/borrowed", |b| {
+        b.iter(|| serde_json::from_str::<borrowed::OpenApi>(black_box(&json)).unwrap())
+    });
+    group.bench_function("yaml/owned", |b| {
+        b.iter(|| serde_yaml::from_str::<OpenApi>(black_box(&yaml)).unwrap())
+    });
+    group.bench_function("yaml/borrowed", |b| {
+        b.iter(|| serde_yaml::from_str::<borrowed::OpenApi>(black_box(&yaml)).unwrap())
+    });
+    group.finish();
+}
+
+// the examples of the specification, which use most of the objects.
+fn examples(c: &mut Criterion) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/oas");
+    let mut group = c.benchmark_group("examples");
+    for version in ["v3.0", "v3.1"] {
+        let mut paths = std::fs::read_dir(dir.join(version))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let yaml = std::fs::read_to_string(&path).unwrap();
+            let name = path.file_stem().unwrap().to_string_lossy();
+            group.bench_function(format!("{version}/{name}"), |b| {
+                b.iter(|| serde_yaml::from_str::<OpenApi>(black_box(&yaml)).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, examples);
+criterion_main!(benches);
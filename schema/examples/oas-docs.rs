@@ -0,0 +1,47 @@
+use openapi_spec_schema::OpenApi;
+use openapi_spec_schema::docs::{DocFormat, render};
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args();
+    let path = PathBuf::from(args.nth(1).ok_or("Specify a file path.")?);
+    let format = match args.next().as_deref() {
+        None | Some("md") | Some("markdown") => DocFormat::Markdown,
+        Some("html") => DocFormat::Html,
+        Some(f) => return Err(format!("Not supported format `{f}`.").into()),
+    };
+
+    let model = read_openapi(&path)?;
+    print!("{}", render(&model, format));
+
+    Ok(())
+}
+
+fn read_openapi(file: &Path) -> Result<OpenApi, Box<dyn Error>> {
+    let mut content = String::new();
+    File::open(file)?.read_to_string(&mut content)?;
+
+    match file
+        .extension()
+        .ok_or("Not found extension")?
+        .to_str()
+        .unwrap()
+    {
+        "yml" => from_yml(&content),
+        "yaml" => from_yml(&content),
+        "json" => from_json(&content),
+        _ => panic!("Not supported format."),
+    }
+}
+
+fn from_json(content: &str) -> Result<OpenApi, Box<dyn Error>> {
+    Ok(serde_json::from_str::<OpenApi>(content)?)
+}
+
+fn from_yml(content: &str) -> Result<OpenApi, Box<dyn Error>> {
+    Ok(serde_yaml::from_str::<OpenApi>(content)?)
+}
@@ -0,0 +1,56 @@
+use openapi_spec_schema::canonical::{FormatOptions, Style, format_str};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let mut check = false;
+    let mut sort = true;
+    let mut paths = vec![];
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--no-sort" => sort = false,
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        return Err("Specify file paths.".into());
+    }
+
+    let mut unformatted = false;
+    for path in paths {
+        let style = match path
+            .extension()
+            .ok_or("Not found extension")?
+            .to_str()
+            .unwrap()
+        {
+            "yml" => Style::Yaml,
+            "yaml" => Style::Yaml,
+            "json" => Style::Json,
+            _ => panic!("Not supported format."),
+        };
+
+        let content = fs::read_to_string(&path)?;
+        let formatted = format_str(&content, &FormatOptions { style, sort })?;
+        if formatted == content {
+            continue;
+        }
+
+        if check {
+            println!("Unformatted: {}", path.display());
+            unformatted = true;
+        } else {
+            fs::write(&path, formatted)?;
+        }
+    }
+
+    if unformatted {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
@@ -0,0 +1,79 @@
+use openapi_spec_schema::OpenApi;
+use openapi_spec_schema::lint::{LintConfig, Linter, Severity};
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let mut args = env::args();
+    let path = PathBuf::from(args.nth(1).ok_or("Specify a file path.")?);
+    let config = match args.next() {
+        Some(config) => read_config(&PathBuf::from(config))?,
+        None => LintConfig::default(),
+    };
+
+    let model = read_openapi(&path)?;
+
+    let mut linter = Linter::new(config);
+    let diagnostics = linter.lint(&model);
+    for d in &diagnostics {
+        println!(
+            "{:<7} {:<24} {} : {}",
+            format!("{:?}", d.severity).to_lowercase(),
+            d.rule,
+            d.pointer,
+            d.message
+        );
+    }
+
+    if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn read_config(file: &Path) -> Result<LintConfig, Box<dyn Error>> {
+    let mut content = String::new();
+    File::open(file)?.read_to_string(&mut content)?;
+
+    match file
+        .extension()
+        .ok_or("Not found extension")?
+        .to_str()
+        .unwrap()
+    {
+        "yml" => Ok(serde_yaml::from_str::<LintConfig>(&content)?),
+        "yaml" => Ok(serde_yaml::from_str::<LintConfig>(&content)?),
+        "json" => Ok(serde_json::from_str::<LintConfig>(&content)?),
+        _ => panic!("Not supported format."),
+    }
+}
+
+fn read_openapi(file: &Path) -> Result<OpenApi, Box<dyn Error>> {
+    let mut content = String::new();
+    File::open(file)?.read_to_string(&mut content)?;
+
+    match file
+        .extension()
+        .ok_or("Not found extension")?
+        .to_str()
+        .unwrap()
+    {
+        "yml" => from_yml(&content),
+        "yaml" => from_yml(&content),
+        "json" => from_json(&content),
+        _ => panic!("Not supported format."),
+    }
+}
+
+fn from_json(content: &str) -> Result<OpenApi, Box<dyn Error>> {
+    Ok(serde_json::from_str::<OpenApi>(content)?)
+}
+
+fn from_yml(content: &str) -> Result<OpenApi, Box<dyn Error>> {
+    Ok(serde_yaml::from_str::<OpenApi>(content)?)
+}
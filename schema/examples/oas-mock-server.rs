@@ -0,0 +1,135 @@
+use openapi_spec_schema::OpenApi;
+use openapi_spec_schema::mock::{MockRequest, MockResponse, MockServer};
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::prelude::*;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args();
+    let path = PathBuf::from(args.nth(1).ok_or("Specify a file path.")?);
+    let addr = args.next().unwrap_or("127.0.0.1:4010".to_string());
+
+    let model = read_openapi(&path)?;
+    let server = MockServer::new(&model);
+
+    let listener = TcpListener::bind(&addr)?;
+    println!("Listening on http://{addr}");
+
+    // a failed connection does not stop the server.
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        };
+        let req = match read_request(&mut stream) {
+            Ok(req) => req,
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        };
+        let res = server.handle(&req);
+        println!("{} {} -> {}", req.method, req.target, res.status);
+        if let Err(e) = write_response(&mut stream, &res) {
+            eprintln!("{e}");
+        }
+    }
+
+    Ok(())
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<MockRequest, Box<dyn Error>> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or("Invalid request line.")?.to_string();
+    let target = parts.next().ok_or("Invalid request line.")?.to_string();
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((k, v)) = line.split_once(':') {
+            headers.push((k.trim().to_string(), v.trim().to_string()));
+        }
+    }
+
+    let length = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(MockRequest {
+        method,
+        target,
+        headers,
+        body,
+    })
+}
+
+fn write_response(stream: &mut TcpStream, res: &MockResponse) -> Result<(), Box<dyn Error>> {
+    write!(stream, "HTTP/1.1 {} {}\r\n", res.status, reason(res.status))?;
+    for (k, v) in &res.headers {
+        write!(stream, "{k}: {v}\r\n")?;
+    }
+    write!(stream, "Content-Length: {}\r\n", res.body.len())?;
+    write!(stream, "Connection: close\r\n\r\n")?;
+    stream.write_all(&res.body)?;
+    Ok(())
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        _ => "",
+    }
+}
+
+fn read_openapi(file: &Path) -> Result<OpenApi, Box<dyn Error>> {
+    let mut content = String::new();
+    File::open(file)?.read_to_string(&mut content)?;
+
+    match file
+        .extension()
+        .ok_or("Not found extension")?
+        .to_str()
+        .unwrap()
+    {
+        "yml" => from_yml(&content),
+        "yaml" => from_yml(&content),
+        "json" => from_json(&content),
+        _ => panic!("Not supported format."),
+    }
+}
+
+fn from_json(content: &str) -> Result<OpenApi, Box<dyn Error>> {
+    Ok(serde_json::from_str::<OpenApi>(content)?)
+}
+
+fn from_yml(content: &str) -> Result<OpenApi, Box<dyn Error>> {
+    Ok(serde_yaml::from_str::<OpenApi>(content)?)
+}
@@ -159,7 +159,10 @@ fn get_schema(
             .ok_or(format!("Not found schemas {path:?}"))?
             .get(name)
             .ok_or(format!("Not found schemas {path:?}:{name}"))?;
-        Ok((components.1.as_ref().unwrap().clone(), schema.clone()))
+        Ok((
+            components.1.as_ref().unwrap().clone(),
+            schema.as_ref().clone(),
+        ))
     } else {
         get_remote_schema(components.0, r)
     }
@@ -197,6 +200,7 @@ fn dump_media(
     media: &MediaType,
 ) -> Result<(), Box<dyn Error>> {
     if let Some(schema) = &media.schema {
+        let schema = schema.schema();
         match &schema.r#ref {
             Some(r) => {
                 let v = get_schema(model, r)?;
@@ -235,7 +239,7 @@ fn get_schema_any(
 
     if let Some(any_of) = schema.any_of.as_ref() {
         if let Some(schema) = any_of.iter().next() {
-            return get_schema_any(model, schema);
+            return get_schema_any(model, schema.schema());
         }
     }
 
@@ -257,7 +261,7 @@ fn get_schema_array(
     schema: &Schema,
 ) -> Result<Any, Box<dyn Error>> {
     let v = schema.items.as_ref().ok_or("Not found items")?;
-    get_schema_any(model, v)
+    get_schema_any(model, v.schema())
 }
 
 fn get_schema_hash(
@@ -268,12 +272,13 @@ fn get_schema_hash(
 
     if let Some(properties) = &schema.properties {
         for (name, property) in properties {
-            obj.insert(name.to_string(), get_schema_any(model, property)?);
+            obj.insert(name.to_string(), get_schema_any(model, property.schema())?);
         }
     }
 
     if let Some(all_of) = &schema.all_of {
         for item in all_of {
+            let item = item.schema();
             if let Any::Object(inner) = get_schema_any(model, item)? {
                 for (k, v) in inner {
                     obj.insert(k, v);
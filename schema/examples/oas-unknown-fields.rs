@@ -0,0 +1,49 @@
+use openapi_spec_schema::strict::{UnknownField, from_json_strict, from_yaml_strict};
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let mut deny = false;
+    let mut path = None;
+    for arg in args.by_ref() {
+        match arg.as_str() {
+            "--deny" => deny = true,
+            _ => path = Some(PathBuf::from(arg)),
+        }
+    }
+    let path = path.ok_or("Specify a file path.")?;
+
+    let fields = read_unknown_fields(&path)?;
+    let level = if deny { "error" } else { "warning" };
+    for f in &fields {
+        println!("{level}: {f}");
+    }
+
+    if deny && !fields.is_empty() {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn read_unknown_fields(file: &Path) -> Result<Vec<UnknownField>, Box<dyn Error>> {
+    let mut content = String::new();
+    File::open(file)?.read_to_string(&mut content)?;
+
+    match file
+        .extension()
+        .ok_or("Not found extension")?
+        .to_str()
+        .unwrap()
+    {
+        "yml" => Ok(from_yaml_strict(&content)?.1),
+        "yaml" => Ok(from_yaml_strict(&content)?.1),
+        "json" => Ok(from_json_strict(&content)?.1),
+        _ => panic!("Not supported format."),
+    }
+}
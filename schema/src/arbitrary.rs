@@ -0,0 +1,1429 @@
+use super::model::{Any, Extensions, HttpStatuses, KeyValues};
+use super::*;
+use proptest::collection::{hash_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+use std::fmt;
+use std::sync::Arc;
+
+// https://docs.rs/proptest/latest/proptest/arbitrary/trait.Arbitrary.html
+
+// depth of objects which contain themselves, like `MediaType` in `Encoding` headers.
+const DEPTH: u32 = 2;
+
+macro_rules! arbitrary {
+    ($ty:ty, $strategy:expr) => {
+        impl Arbitrary for $ty {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                $strategy.boxed()
+            }
+        }
+    };
+}
+
+fn text() -> BoxedStrategy<String> {
+    "(?s).{0,8}".boxed()
+}
+
+fn opt<T: fmt::Debug + 'static>(s: impl Strategy<Value = T> + 'static) -> BoxedStrategy<Option<T>> {
+    option::weighted(0.2, s).boxed()
+}
+
+fn map<T: fmt::Debug + 'static>(
+    s: impl Strategy<Value = T> + 'static,
+) -> BoxedStrategy<HashMap<String, T>> {
+    hash_map(text(), s, 0..3).boxed()
+}
+
+fn list<T: fmt::Debug + 'static>(s: impl Strategy<Value = T> + 'static) -> BoxedStrategy<Vec<T>> {
+    vec(s, 0..3).boxed()
+}
+
+// `null` is read as `None`.
+fn value() -> BoxedStrategy<Any> {
+    any::<Any>()
+        .prop_filter("null", |v| *v != Any::Null)
+        .boxed()
+}
+
+// ---------------------------------------------------------------------------
+
+arbitrary!(
+    Any,
+    prop_oneof![
+        Just(Any::Null),
+        any::<bool>().prop_map(Any::Boolean),
+        any::<i32>().prop_map(Any::Integer),
+        // non-finite numbers are written as `null`.
+        (proptest::num::f32::NORMAL | proptest::num::f32::ZERO).prop_map(Any::Number),
+        text().prop_map(Any::String),
+    ]
+    .prop_recursive(3, 16, 3, |inner| prop_oneof![
+        list(inner.clone()).prop_map(Any::Array),
+        map(inner).prop_map(Any::Object),
+    ])
+);
+
+arbitrary!(
+    Extensions,
+    map(any::<Any>()).prop_map(|values| Extensions { values })
+);
+
+impl<T: Arbitrary + 'static> Arbitrary for HttpStatuses<T> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        hash_map(100u16..600, any::<T>(), 0..3)
+            .prop_map(|values| HttpStatuses { values })
+            .boxed()
+    }
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for KeyValues<T> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        // keys like paths do not conflict with fixed fields nor extensions.
+        (hash_map("/(?s).{0,8}", any::<T>(), 0..3), map(any::<Any>()))
+            .prop_map(|(values, extensions)| KeyValues { values, extensions })
+            .boxed()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<T: Arbitrary + 'static> Arbitrary for BooleanOr<T> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        boolean_or(any::<T>())
+    }
+}
+
+fn boolean_or<T: fmt::Debug + 'static>(
+    s: impl Strategy<Value = T> + 'static,
+) -> BoxedStrategy<BooleanOr<T>> {
+    prop_oneof![
+        s.prop_map(BooleanOr::Value),
+        any::<bool>().prop_map(BooleanOr::Boolean),
+    ]
+    .boxed()
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for ReferenceOr<T> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        reference_or(any::<T>())
+    }
+}
+
+fn reference_or<T: fmt::Debug + 'static>(
+    s: impl Strategy<Value = T> + 'static,
+) -> BoxedStrategy<ReferenceOr<T>> {
+    prop_oneof![
+        any::<Reference>().prop_map(ReferenceOr::Ref),
+        s.prop_map(ReferenceOr::Value),
+    ]
+    .boxed()
+}
+
+arbitrary!(
+    OpenApi,
+    (
+        text(),
+        opt(text()),
+        any::<Info>(),
+        opt(text()),
+        opt(list(any::<Server>())),
+        opt(any::<Paths>()),
+        opt(map(reference_or(path_item(DEPTH)))),
+        opt(any::<Components>()),
+        opt(list(security_requirement())),
+        opt(list(any::<Tag>())),
+        opt(any::<ExternalDocumentation>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(
+                openapi,
+                self_uri,
+                info,
+                json_schema_dialect,
+                servers,
+                paths,
+                webhooks,
+                components,
+                security,
+                tags,
+                external_docs,
+                extensions,
+            )| OpenApi {
+                openapi,
+                self_uri,
+                info,
+                json_schema_dialect,
+                servers,
+                paths,
+                webhooks,
+                components,
+                security,
+                tags,
+                external_docs,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    Info,
+    (
+        text(),
+        opt(text()),
+        opt(text()),
+        opt(text()),
+        opt(any::<Contact>()),
+        opt(any::<License>()),
+        text(),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(
+                title,
+                summary,
+                description,
+                terms_of_service,
+                contact,
+                license,
+                version,
+                extensions,
+            )| Info {
+                title,
+                summary,
+                description,
+                terms_of_service,
+                contact,
+                license,
+                version,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    Contact,
+    (opt(text()), opt(text()), opt(text()), any::<Extensions>()).prop_map(
+        |(name, url, email, extensions)| Contact {
+            name,
+            url,
+            email,
+            extensions,
+        }
+    )
+);
+
+arbitrary!(
+    License,
+    prop_oneof![
+        any::<LicenseUrl>().prop_map(License::Url),
+        any::<LicenseId>().prop_map(License::Id),
+    ]
+);
+
+arbitrary!(
+    LicenseId,
+    (text(), opt(text()), any::<Extensions>()).prop_map(|(name, identifier, extensions)| {
+        LicenseId {
+            name,
+            identifier,
+            extensions,
+        }
+    })
+);
+
+arbitrary!(
+    LicenseUrl,
+    (text(), text(), any::<Extensions>()).prop_map(|(name, url, extensions)| LicenseUrl {
+        name,
+        url,
+        extensions,
+    })
+);
+
+arbitrary!(
+    Server,
+    (
+        text(),
+        opt(text()),
+        opt(text()),
+        opt(map(any::<ServerVariable>())),
+        any::<Extensions>(),
+    )
+        .prop_map(|(url, description, name, variables, extensions)| Server {
+            url,
+            description,
+            name,
+            variables,
+            extensions,
+        })
+);
+
+arbitrary!(
+    ServerVariable,
+    (opt(list(text())), text(), opt(text()), any::<Extensions>(),).prop_map(
+        |(r#enum, default, description, extensions)| ServerVariable {
+            r#enum,
+            default,
+            description,
+            extensions,
+        }
+    )
+);
+
+arbitrary!(
+    Components,
+    (
+        opt(map(any::<Arc<Schema>>())),
+        opt(map(any::<ReferenceOr<Response>>())),
+        opt(map(any::<ReferenceOr<Parameter>>())),
+        opt(map(any::<ReferenceOr<Example>>())),
+        opt(map(any::<ReferenceOr<RequestBody>>())),
+        opt(map(any::<ReferenceOr<Header>>())),
+        opt(map(any::<ReferenceOr<SecurityScheme>>())),
+        opt(map(any::<ReferenceOr<Link>>())),
+        opt(map(reference_or(callback(DEPTH)))),
+        opt(map(reference_or(path_item(DEPTH)))),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(
+                schemas,
+                responses,
+                parameters,
+                examples,
+                request_bodies,
+                headers,
+                security_schemes,
+                links,
+                callbacks,
+                path_items,
+                extensions,
+            )| Components {
+                schemas,
+                responses,
+                parameters,
+                examples,
+                request_bodies,
+                headers,
+                security_schemes,
+                links,
+                callbacks,
+                path_items,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(PathItem, path_item(DEPTH));
+
+// `$ref` with other fields is read as `Reference` by `ReferenceOr`.
+fn path_item(depth: u32) -> BoxedStrategy<PathItem> {
+    let operation = || opt(operation(depth));
+    (
+        (opt(text()), opt(text())),
+        (
+            operation(),
+            operation(),
+            operation(),
+            operation(),
+            operation(),
+            operation(),
+            operation(),
+            operation(),
+        ),
+        (operation(), opt(map(self::operation(depth)))),
+        opt(list(any::<Server>())),
+        opt(list(any::<ReferenceOr<Parameter>>())),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(
+                (summary, description),
+                (get, put, post, delete, options, head, patch, trace),
+                (query, additional_operations),
+                servers,
+                parameters,
+                extensions,
+            )| PathItem {
+                r#ref: None,
+                summary,
+                description,
+                get,
+                put,
+                post,
+                delete,
+                options,
+                head,
+                patch,
+                trace,
+                query,
+                additional_operations,
+                servers,
+                parameters,
+                extensions,
+            },
+        )
+        .boxed()
+}
+
+fn callback(depth: u32) -> BoxedStrategy<Callback> {
+    (
+        hash_map("/(?s).{0,8}", reference_or(path_item(depth)), 0..3),
+        map(any::<Any>()),
+    )
+        .prop_map(|(values, extensions)| KeyValues { values, extensions })
+        .boxed()
+}
+
+arbitrary!(Operation, operation(DEPTH));
+
+fn operation(depth: u32) -> BoxedStrategy<Operation> {
+    let callbacks = match depth {
+        0 => Just(None).boxed(),
+        _ => opt(map(reference_or(callback(depth - 1)))),
+    };
+    (
+        opt(list(text())),
+        opt(text()),
+        opt(text()),
+        opt(any::<ExternalDocumentation>()),
+        opt(text()),
+        opt(list(any::<ReferenceOr<Parameter>>())),
+        opt(any::<ReferenceOr<RequestBody>>()),
+        opt(any::<Responses>()),
+        callbacks,
+        opt(any::<bool>()),
+        opt(list(security_requirement())),
+        (opt(list(any::<Server>())), any::<Extensions>()),
+    )
+        .prop_map(
+            |(
+                tags,
+                summary,
+                description,
+                external_docs,
+                operation_id,
+                parameters,
+                request_body,
+                responses,
+                callbacks,
+                deprecated,
+                security,
+                (servers, extensions),
+            )| Operation {
+                tags,
+                summary,
+                description,
+                external_docs,
+                operation_id,
+                parameters,
+                request_body,
+                responses,
+                callbacks,
+                deprecated,
+                security,
+                servers,
+                extensions,
+            },
+        )
+        .boxed()
+}
+
+arbitrary!(
+    ExternalDocumentation,
+    (opt(text()), text(), any::<Extensions>()).prop_map(|(description, url, extensions)| {
+        ExternalDocumentation {
+            description,
+            url,
+            extensions,
+        }
+    })
+);
+
+arbitrary!(
+    Parameter,
+    (
+        text(),
+        any::<ParameterIn>(),
+        opt(text()),
+        opt(any::<bool>()),
+        opt(any::<bool>()),
+        opt(any::<bool>()),
+        parameter_pattern(DEPTH),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(
+                name,
+                r#in,
+                description,
+                required,
+                deprecated,
+                allow_empty_value,
+                pattern,
+                extensions,
+            )| Parameter {
+                name,
+                r#in,
+                description,
+                required,
+                deprecated,
+                allow_empty_value,
+                pattern,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(ParameterPattern, parameter_pattern(DEPTH));
+
+fn parameter_pattern(depth: u32) -> BoxedStrategy<ParameterPattern> {
+    let style = any::<ParameterStyle>().prop_map(ParameterPattern::Style);
+    match depth {
+        0 => style.boxed(),
+        _ => prop_oneof![
+            parameter_content(depth - 1).prop_map(ParameterPattern::Content),
+            style,
+        ]
+        .boxed(),
+    }
+}
+
+arbitrary!(
+    ParameterStyle,
+    (
+        opt(text()),
+        opt(any::<bool>()),
+        opt(any::<bool>()),
+        opt(any::<SchemaOrBool>()),
+        any::<Examples>(),
+    )
+        .prop_map(
+            |(style, explode, allow_reserved, schema, example)| ParameterStyle {
+                style,
+                explode,
+                allow_reserved,
+                schema,
+                example,
+            }
+        )
+);
+
+arbitrary!(
+    Examples,
+    prop_oneof![
+        map(any::<ReferenceOr<Example>>()).prop_map(|examples| Examples::Values { examples }),
+        opt(value()).prop_map(|example| Examples::Value { example }),
+    ]
+);
+
+arbitrary!(ParameterContent, parameter_content(DEPTH));
+
+fn parameter_content(depth: u32) -> BoxedStrategy<ParameterContent> {
+    map(media_type(depth))
+        .prop_map(|content| ParameterContent { content })
+        .boxed()
+}
+
+arbitrary!(
+    ParameterIn,
+    prop_oneof![
+        Just(ParameterIn::Query),
+        Just(ParameterIn::Header),
+        Just(ParameterIn::Path),
+        Just(ParameterIn::Cookie),
+        Just(ParameterIn::Querystring),
+    ]
+);
+
+arbitrary!(
+    RequestBody,
+    (
+        opt(text()),
+        map(media_type(DEPTH)),
+        opt(any::<bool>()),
+        any::<Extensions>(),
+    )
+        .prop_map(|(description, content, required, extensions)| RequestBody {
+            description,
+            content,
+            required,
+            extensions,
+        })
+);
+
+arbitrary!(MediaType, media_type(DEPTH));
+
+fn media_type(depth: u32) -> BoxedStrategy<MediaType> {
+    let encoding = match depth {
+        0 => Just(None).boxed(),
+        _ => opt(map(encoding(depth - 1))),
+    };
+    (
+        opt(any::<SchemaOrBool>()),
+        opt(any::<SchemaOrBool>()),
+        any::<Examples>(),
+        encoding,
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(schema, item_schema, example, encoding, extensions)| MediaType {
+                schema,
+                item_schema,
+                example,
+                encoding,
+                extensions,
+            },
+        )
+        .boxed()
+}
+
+arbitrary!(Encoding, encoding(DEPTH));
+
+fn encoding(depth: u32) -> BoxedStrategy<Encoding> {
+    (
+        opt(text()),
+        opt(map(reference_or(header(depth)))),
+        opt(text()),
+        opt(any::<bool>()),
+        opt(any::<bool>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(content_type, headers, style, explode, allow_reserved, extensions)| Encoding {
+                content_type,
+                headers,
+                style,
+                explode,
+                allow_reserved,
+                extensions,
+            },
+        )
+        .boxed()
+}
+
+arbitrary!(
+    Responses,
+    (
+        opt(any::<ReferenceOr<Response>>()),
+        any::<HttpStatuses<ReferenceOr<Response>>>(),
+        any::<Extensions>(),
+    )
+        .prop_map(|(default, statuses, extensions)| Responses {
+            default,
+            statuses,
+            extensions,
+        })
+);
+
+arbitrary!(
+    Response,
+    (
+        text(),
+        opt(map(any::<ReferenceOr<Header>>())),
+        opt(map(any::<MediaType>())),
+        opt(map(any::<ReferenceOr<Link>>())),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(description, headers, content, links, extensions)| Response {
+                description,
+                headers,
+                content,
+                links,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    Example,
+    (
+        opt(text()),
+        opt(text()),
+        any::<ExampleValue>(),
+        any::<Extensions>(),
+    )
+        .prop_map(|(summary, description, value, extensions)| Example {
+            summary,
+            description,
+            value,
+            extensions,
+        })
+);
+
+arbitrary!(
+    ExampleValue,
+    prop_oneof![
+        text().prop_map(|external_value| ExampleValue::Url { external_value }),
+        opt(value()).prop_map(|value| ExampleValue::Literal { value }),
+    ]
+);
+
+arbitrary!(
+    Link,
+    (
+        any::<LinkOperation>(),
+        opt(map(any::<Any>())),
+        opt(value()),
+        opt(text()),
+        opt(any::<Server>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(operation, parameters, request_body, description, server, extensions)| Link {
+                operation,
+                parameters,
+                request_body,
+                description,
+                server,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    LinkOperation,
+    prop_oneof![
+        text().prop_map(LinkOperation::Ref),
+        text().prop_map(LinkOperation::Id),
+    ]
+);
+
+arbitrary!(Header, header(DEPTH));
+
+fn header(depth: u32) -> BoxedStrategy<Header> {
+    (
+        opt(text()),
+        opt(any::<bool>()),
+        opt(any::<bool>()),
+        opt(any::<bool>()),
+        parameter_pattern(depth),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(description, required, deprecated, allow_empty_value, pattern, extensions)| Header {
+                description,
+                required,
+                deprecated,
+                allow_empty_value,
+                pattern,
+                extensions,
+            },
+        )
+        .boxed()
+}
+
+arbitrary!(
+    Tag,
+    (
+        text(),
+        opt(text()),
+        opt(text()),
+        opt(any::<ExternalDocumentation>()),
+        opt(text()),
+        opt(text()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(name, summary, description, external_docs, parent, kind, extensions)| Tag {
+                name,
+                summary,
+                description,
+                external_docs,
+                parent,
+                kind,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    Reference,
+    (text(), opt(text()), opt(text())).prop_map(|(r#ref, summary, description)| Reference {
+        r#ref,
+        summary,
+        description,
+    })
+);
+
+// ---------------------------------------------------------------------------
+
+arbitrary!(
+    Schema,
+    schema_keywords().prop_recursive(3, 32, 4, |inner| {
+        let sub = || boolean_or(inner.clone()).prop_map(Arc::new);
+        let one = || opt(sub());
+        let many = || opt(list(sub()));
+        let named = || opt(map(sub()));
+        (
+            inner.clone(),
+            (
+                named(),
+                many(),
+                many(),
+                many(),
+                one(),
+                one(),
+                one(),
+                one(),
+                named(),
+            ),
+            (
+                many(),
+                one(),
+                one(),
+                named(),
+                named(),
+                one(),
+                one(),
+                one(),
+                one(),
+                one(),
+            ),
+        )
+            .prop_map(
+                |(
+                    mut schema,
+                    (defs, all_of, any_of, one_of, not, r#if, then, r#else, dependent_schemas),
+                    (
+                        prefix_items,
+                        items,
+                        contains,
+                        properties,
+                        pattern_properties,
+                        additional_properties,
+                        property_names,
+                        unevaluated_items,
+                        unevaluated_properties,
+                        content_schema,
+                    ),
+                )| {
+                    schema.all_of = all_of;
+                    schema.any_of = any_of;
+                    schema.one_of = one_of;
+                    schema.items = items;
+                    schema.properties = properties;
+                    schema.additional_properties = additional_properties;
+                    schema.applicators = boxed(SchemaApplicators {
+                        not,
+                        r#if,
+                        then,
+                        r#else,
+                        dependent_schemas,
+                        prefix_items,
+                        contains,
+                        pattern_properties,
+                        property_names,
+                        unevaluated_items,
+                        unevaluated_properties,
+                    });
+                    schema.metadata_mut().defs = defs;
+                    schema.metadata = schema.metadata.take().and_then(|m| boxed(*m));
+                    schema.content_mut().content_schema = content_schema;
+                    schema.content = schema.content.take().and_then(|c| boxed(*c));
+                    schema
+                },
+            )
+    })
+);
+
+// keywords other than subschemas.
+fn schema_keywords() -> BoxedStrategy<Schema> {
+    (
+        (
+            opt(any::<bool>()),
+            opt(text()),
+            opt(any::<SchemaTypes>()),
+            opt(list(any::<Any>())),
+            opt(list(text())),
+            opt(text()),
+            opt(text()),
+            opt(text()),
+            opt(value()),
+        ),
+        (
+            any::<SchemaValidation>().prop_map(boxed),
+            any::<SchemaMetadata>().prop_map(boxed),
+            any::<SchemaContent>().prop_map(boxed),
+            any::<SchemaOas>().prop_map(boxed),
+            any::<KeyValues<Any>>(),
+        ),
+    )
+        .prop_map(
+            |(
+                (nullable, r#ref, r#type, r#enum, required, format, title, description, default),
+                (validation, metadata, content, oas, extensions),
+            )| Schema {
+                nullable,
+                r#ref,
+                r#type,
+                r#enum,
+                required,
+                format,
+                title,
+                description,
+                default,
+                validation,
+                metadata,
+                content,
+                oas,
+                extensions,
+                ..Default::default()
+            },
+        )
+        .boxed()
+}
+
+arbitrary!(
+    SchemaValidation,
+    (
+        (
+            opt(value()),
+            opt(any::<u32>()),
+            opt(any::<i32>()),
+            opt(any::<i32>()),
+            opt(any::<i32>()),
+            opt(any::<i32>()),
+            opt(any::<u32>()),
+            opt(any::<u32>()),
+            opt(text()),
+        ),
+        (
+            opt(any::<u32>()),
+            opt(any::<u32>()),
+            opt(any::<bool>()),
+            opt(any::<u32>()),
+            opt(any::<u32>()),
+            opt(any::<u32>()),
+            opt(any::<u32>()),
+            opt(map(list(text()))),
+        ),
+    )
+        .prop_map(
+            |(
+                (
+                    r#const,
+                    multiple_of,
+                    maximum,
+                    exclusive_maximum,
+                    minimum,
+                    exclusive_minimum,
+                    max_length,
+                    min_length,
+                    pattern,
+                ),
+                (
+                    max_items,
+                    min_items,
+                    unique_items,
+                    max_contains,
+                    min_contains,
+                    max_properties,
+                    min_properties,
+                    dependent_required,
+                ),
+            )| SchemaValidation {
+                r#const,
+                multiple_of,
+                maximum,
+                exclusive_maximum,
+                minimum,
+                exclusive_minimum,
+                max_length,
+                min_length,
+                pattern,
+                max_items,
+                min_items,
+                unique_items,
+                max_contains,
+                min_contains,
+                max_properties,
+                min_properties,
+                dependent_required,
+            },
+        )
+);
+
+// without `$defs`, which are subschemas.
+arbitrary!(
+    SchemaMetadata,
+    (
+        (
+            opt(text()),
+            opt(map(any::<bool>())),
+            opt(text()),
+            opt(text()),
+            opt(text()),
+            opt(text()),
+        ),
+        (
+            opt(text()),
+            opt(any::<bool>()),
+            opt(any::<bool>()),
+            opt(any::<bool>()),
+            opt(list(any::<Any>())),
+        ),
+    )
+        .prop_map(
+            |(
+                (schema, vocabulary, id, anchor, dynamic_anchor, dynamic_ref),
+                (comment, deprecated, read_only, write_only, examples),
+            )| SchemaMetadata {
+                schema,
+                vocabulary,
+                id,
+                anchor,
+                dynamic_anchor,
+                dynamic_ref,
+                defs: None,
+                comment,
+                deprecated,
+                read_only,
+                write_only,
+                examples,
+            },
+        )
+);
+
+// without `contentSchema`, which is a subschema.
+arbitrary!(
+    SchemaContent,
+    (opt(text()), opt(text())).prop_map(|(content_encoding, content_media_type)| {
+        SchemaContent {
+            content_encoding,
+            content_media_type,
+            content_schema: None,
+        }
+    })
+);
+
+arbitrary!(
+    SchemaOas,
+    (
+        opt(any::<Discriminator>()),
+        opt(any::<Xml>()),
+        opt(any::<ExternalDocumentation>()),
+        opt(value()),
+    )
+        .prop_map(|(discriminator, xml, external_docs, example)| SchemaOas {
+            discriminator,
+            xml,
+            external_docs,
+            example,
+        })
+);
+
+arbitrary!(
+    SchemaType,
+    prop_oneof![
+        Just(SchemaType::Null),
+        Just(SchemaType::Boolean),
+        Just(SchemaType::Object),
+        Just(SchemaType::Array),
+        Just(SchemaType::Number),
+        Just(SchemaType::String),
+        Just(SchemaType::Integer),
+    ]
+);
+
+arbitrary!(
+    SchemaTypes,
+    prop_oneof![
+        any::<SchemaType>().prop_map(SchemaTypes::Unit),
+        list(any::<SchemaType>()).prop_map(SchemaTypes::Array),
+    ]
+);
+
+arbitrary!(
+    Discriminator,
+    (text(), opt(map(text())), any::<Extensions>()).prop_map(
+        |(property_name, mapping, extensions)| Discriminator {
+            property_name,
+            mapping,
+            extensions,
+        }
+    )
+);
+
+arbitrary!(
+    Xml,
+    (
+        opt(text()),
+        opt(text()),
+        opt(text()),
+        opt(any::<bool>()),
+        opt(any::<bool>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(name, namespace, prefix, attribute, wrapped, extensions)| Xml {
+                name,
+                namespace,
+                prefix,
+                attribute,
+                wrapped,
+                extensions,
+            }
+        )
+);
+
+// ---------------------------------------------------------------------------
+
+arbitrary!(
+    SecurityScheme,
+    prop_oneof![
+        any::<SecuritySchemeApiKey>().prop_map(SecurityScheme::ApiKey),
+        any::<SecuritySchemeHttp>().prop_map(SecurityScheme::Http),
+        any::<SecuritySchemeOauth2>().prop_map(SecurityScheme::Oauth2),
+        any::<SecuritySchemeOpenIdConnect>().prop_map(SecurityScheme::OpenIdConnect),
+        any::<SecuritySchemeMutualTls>().prop_map(SecurityScheme::MutualTls),
+    ]
+);
+
+arbitrary!(
+    SecuritySchemeApiKey,
+    (
+        opt(value()),
+        text(),
+        any::<SecuritySchemeIn>(),
+        opt(any::<bool>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(description, name, r#in, deprecated, extensions)| SecuritySchemeApiKey {
+                description,
+                name,
+                r#in,
+                deprecated,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    SecuritySchemeHttp,
+    (
+        opt(value()),
+        text(),
+        opt(text()),
+        opt(any::<bool>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(description, scheme, bearer_format, deprecated, extensions)| SecuritySchemeHttp {
+                description,
+                scheme,
+                bearer_format,
+                deprecated,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    SecuritySchemeMutualTls,
+    (opt(value()), opt(any::<bool>()), any::<Extensions>()).prop_map(
+        |(description, deprecated, extensions)| SecuritySchemeMutualTls {
+            description,
+            deprecated,
+            extensions,
+        }
+    )
+);
+
+arbitrary!(
+    SecuritySchemeOauth2,
+    (
+        opt(value()),
+        any::<OAuthFlows>(),
+        opt(any::<bool>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(description, flows, deprecated, extensions)| SecuritySchemeOauth2 {
+                description,
+                flows,
+                deprecated,
+                extensions,
+            }
+        )
+);
+
+arbitrary!(
+    SecuritySchemeOpenIdConnect,
+    (
+        opt(value()),
+        text(),
+        opt(any::<bool>()),
+        any::<Extensions>()
+    )
+        .prop_map(
+            |(description, open_id_connect_url, deprecated, extensions)| {
+                SecuritySchemeOpenIdConnect {
+                    description,
+                    open_id_connect_url,
+                    deprecated,
+                    extensions,
+                }
+            }
+        )
+);
+
+arbitrary!(
+    SecuritySchemeIn,
+    prop_oneof![
+        Just(SecuritySchemeIn::Query),
+        Just(SecuritySchemeIn::Header),
+        Just(SecuritySchemeIn::Cookie),
+    ]
+);
+
+arbitrary!(
+    OAuthFlows,
+    (
+        opt(any::<OAuthFlowImplicit>()),
+        opt(any::<OAuthFlowPassword>()),
+        opt(any::<OAuthFlowClientCredentials>()),
+        opt(any::<OAuthFlowAuthorizationCode>()),
+        opt(any::<OAuthFlowDeviceAuthorization>()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(
+                implicit,
+                password,
+                client_credentials,
+                authorization_code,
+                device_authorization,
+                extensions,
+            )| {
+                OAuthFlows {
+                    implicit,
+                    password,
+                    client_credentials,
+                    authorization_code,
+                    device_authorization,
+                    extensions,
+                }
+            }
+        )
+);
+
+arbitrary!(
+    OAuthFlowImplicit,
+    (text(), opt(text()), map(text()), any::<Extensions>()).prop_map(
+        |(authrization_url, refresh_url, scopes, extensions)| OAuthFlowImplicit {
+            authrization_url,
+            refresh_url,
+            scopes,
+            extensions,
+        }
+    )
+);
+
+arbitrary!(
+    OAuthFlowPassword,
+    (text(), opt(text()), map(text()), any::<Extensions>()).prop_map(
+        |(token_url, refresh_url, scopes, extensions)| OAuthFlowPassword {
+            token_url,
+            refresh_url,
+            scopes,
+            extensions,
+        }
+    )
+);
+
+arbitrary!(
+    OAuthFlowClientCredentials,
+    (text(), opt(text()), map(text()), any::<Extensions>()).prop_map(
+        |(token_url, refresh_url, scopes, extensions)| OAuthFlowClientCredentials {
+            token_url,
+            refresh_url,
+            scopes,
+            extensions,
+        }
+    )
+);
+
+arbitrary!(
+    OAuthFlowAuthorizationCode,
+    (
+        text(),
+        text(),
+        opt(text()),
+        map(text()),
+        any::<Extensions>(),
+    )
+        .prop_map(
+            |(authrization_url, token_url, refresh_url, scopes, extensions)| {
+                OAuthFlowAuthorizationCode {
+                    authrization_url,
+                    token_url,
+                    refresh_url,
+                    scopes,
+                    extensions,
+                }
+            }
+        )
+);
+
+arbitrary!(
+    OAuthFlowDeviceAuthorization,
+    (
+        text(),
+        text(),
+        opt(text()),
+        map(text()),
+        any::<Extensions>()
+    )
+        .prop_map(
+            |(device_authorization_url, token_url, refresh_url, scopes, extensions)| {
+                OAuthFlowDeviceAuthorization {
+                    device_authorization_url,
+                    token_url,
+                    refresh_url,
+                    scopes,
+                    extensions,
+                }
+            }
+        )
+);
+
+fn security_requirement() -> BoxedStrategy<SecurityRequirement> {
+    map(list(text()))
+}
+
+arbitrary!(
+    PartOpenApi,
+    (
+        opt(text()),
+        opt(list(any::<Server>())),
+        opt(any::<Paths>()),
+        opt(map(reference_or(path_item(DEPTH)))),
+        opt(any::<Components>()),
+        opt(list(security_requirement())),
+        opt(list(any::<Tag>())),
+        opt(any::<ExternalDocumentation>()),
+        any::<KeyValues<Any>>(),
+    )
+        .prop_map(
+            |(
+                json_schema_dialect,
+                servers,
+                paths,
+                webhooks,
+                components,
+                security,
+                tags,
+                external_docs,
+                extensions,
+            )| PartOpenApi {
+                json_schema_dialect,
+                servers,
+                paths,
+                webhooks,
+                components,
+                security,
+                tags,
+                external_docs,
+                extensions,
+            }
+        )
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json<T>(v: &T) -> T
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        serde_json::from_str(&serde_json::to_string(v).unwrap()).unwrap()
+    }
+
+    fn yaml<T>(v: &T) -> T
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        serde_yaml::from_str(&serde_yaml::to_string(v).unwrap()).unwrap()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn arbitrary_openapi(v in any::<OpenApi>()) {
+            prop_assert_eq!(&v, &json(&v));
+            prop_assert_eq!(&v, &yaml(&v));
+        }
+
+        #[test]
+        fn arbitrary_part_openapi(v in any::<PartOpenApi>()) {
+            prop_assert_eq!(&v, &json(&v));
+            prop_assert_eq!(&v, &yaml(&v));
+        }
+
+        #[test]
+        fn arbitrary_schema(v in any::<Schema>()) {
+            prop_assert_eq!(&v, &json(&v));
+            prop_assert_eq!(&v, &yaml(&v));
+        }
+
+        #[test]
+        fn arbitrary_borrowed_openapi(v in any::<OpenApi>()) {
+            let s = serde_json::to_string(&v).unwrap();
+            let r = serde_json::from_str::<borrowed::OpenApi>(&s).unwrap();
+            prop_assert_eq!(&v, &r.to_owned_model().unwrap());
+        }
+
+        #[test]
+        fn arbitrary_borrowed_part_openapi(v in any::<PartOpenApi>()) {
+            let s = serde_json::to_string(&v).unwrap();
+            let r = serde_json::from_str::<borrowed::PartOpenApi>(&s).unwrap();
+            prop_assert_eq!(&v, &r.to_owned_model().unwrap());
+        }
+
+        #[test]
+        fn arbitrary_strict(v in any::<OpenApi>()) {
+            let mut input = serde_json::to_value(&v).unwrap();
+            input["unknown"] = serde_json::Value::Bool(true);
+
+            let (r, fields) = strict::from_json_strict(&input.to_string()).unwrap();
+            prop_assert_eq!(&v, &r);
+            let (r, yaml_fields) = strict::from_yaml_strict(&serde_yaml::to_string(&input).unwrap()).unwrap();
+            prop_assert_eq!(&v, &r);
+            prop_assert_eq!(&fields, &yaml_fields);
+
+            // the others are unknown keywords of schemas.
+            let unknown = strict::UnknownField {
+                pointer: "".to_string(),
+                key: "unknown".to_string(),
+            };
+            prop_assert!(fields.contains(&unknown));
+            for field in fields.iter().filter(|f| **f != unknown) {
+                let schema = input.pointer(&field.pointer).cloned().unwrap();
+                let schema = serde_json::from_value::<Schema>(schema).unwrap();
+                prop_assert!(schema.extensions.values.contains_key(&field.key));
+            }
+        }
+    }
+}
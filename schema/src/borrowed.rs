@@ -0,0 +1,1948 @@
+use super::de::{ReferenceOrModel, ReferenceOrVisitor, license_error};
+use super::model::HttpStatuses;
+use super::{BooleanOr, ParameterIn, SchemaTypes, SecuritySchemeIn, group};
+use serde::de::{self, DeserializeOwned, IgnoredAny, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Arc;
+
+// The document model which borrows strings from the input buffer.
+//
+// Strings are borrowed from JSON and YAML unless they contain escapes.
+
+/// A string borrowed from the input, or owned when it can not be borrowed.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Str<'a>(pub Cow<'a, str>);
+
+impl Str<'_> {
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+
+    pub fn into_owned(self) -> String {
+        self.0.into_owned()
+    }
+}
+
+impl Deref for Str<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Str<'_> {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Str<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'a> From<&'a str> for Str<'a> {
+    fn from(value: &'a str) -> Self {
+        Str(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for Str<'_> {
+    fn from(value: String) -> Self {
+        Str(Cow::Owned(value))
+    }
+}
+
+struct StrVisitor;
+
+impl<'de> Visitor<'de> for StrVisitor {
+    type Value = Str<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(Str(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Str(Cow::Owned(v.to_string())))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Str(Cow::Owned(v)))
+    }
+}
+
+impl Serialize for Str<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Str<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+// the extension name without `x-`.
+fn extension_name(key: Str<'_>) -> Option<Str<'_>> {
+    match key.0 {
+        Cow::Borrowed(k) => k.strip_prefix("x-").map(Str::from),
+        Cow::Owned(k) => k.strip_prefix("x-").map(|k| Str::from(k.to_string())),
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Any<'a> {
+    Boolean(bool),
+    Integer(i32),
+    Number(f32),
+    #[serde(borrow)]
+    String(Str<'a>),
+    #[serde(borrow)]
+    Array(Vec<Any<'a>>),
+    #[serde(borrow)]
+    Object(HashMap<Str<'a>, Any<'a>>),
+    Null,
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Extensions<'a> {
+    pub values: HashMap<Str<'a>, Any<'a>>,
+}
+
+struct ExtensionsVisitor<'a> {
+    marker: PhantomData<Extensions<'a>>,
+}
+
+impl<'de: 'a, 'a> Visitor<'de> for ExtensionsVisitor<'a> {
+    type Value = Extensions<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("OpenAPI specification extensions")
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut map = Extensions::default();
+        while let Some(key) = access.next_key::<Str<'a>>()? {
+            match extension_name(key) {
+                Some(name) => {
+                    map.values.insert(name, access.next_value()?);
+                }
+                None => {
+                    access.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl Serialize for Extensions<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.values.len()))?;
+        for (k, v) in &self.values {
+            map.serialize_entry(&format!("x-{k}"), v)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Extensions<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ExtensionsVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyValues<'a, T> {
+    pub values: HashMap<Str<'a>, T>,
+    pub extensions: HashMap<Str<'a>, Any<'a>>,
+}
+
+impl<T> Default for KeyValues<'_, T> {
+    fn default() -> Self {
+        KeyValues {
+            values: HashMap::new(),
+            extensions: HashMap::new(),
+        }
+    }
+}
+
+struct KeyValuesVisitor<'a, T> {
+    marker: PhantomData<KeyValues<'a, T>>,
+}
+
+impl<'de: 'a, 'a, T> Visitor<'de> for KeyValuesVisitor<'a, T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = KeyValues<'a, T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("OpenAPI key and value with specification extensions")
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut map = KeyValues::default();
+        while let Some(key) = access.next_key::<Str<'a>>()? {
+            if key.starts_with("x-") {
+                let name = extension_name(key).unwrap();
+                map.extensions.insert(name, access.next_value()?);
+            } else {
+                map.values.insert(key, access.next_value()?);
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl<T> Serialize for KeyValues<'_, T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.values.len() + self.extensions.len()))?;
+        for (k, v) in &self.values {
+            map.serialize_entry(k, v)?;
+        }
+        for (k, v) in &self.extensions {
+            map.serialize_entry(&format!("x-{k}"), v)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de: 'a, 'a, T> Deserialize<'de> for KeyValues<'a, T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(KeyValuesVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl OpenApi<'_> {
+    /// Copy into the owned model.
+    pub fn to_owned_model(&self) -> Result<super::OpenApi, serde_json::Error> {
+        convert(self)
+    }
+}
+
+impl PartOpenApi<'_> {
+    /// Copy into the owned model.
+    pub fn to_owned_model(&self) -> Result<super::PartOpenApi, serde_json::Error> {
+        convert(self)
+    }
+}
+
+// both models have the same representation.
+fn convert<T: Serialize, U: DeserializeOwned>(value: &T) -> Result<U, serde_json::Error> {
+    serde_json::from_value(serde_json::to_value(value)?)
+}
+
+// ---------------------------------------------------------------------------
+
+// since v3.1.0
+// https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-4.3.2
+pub type SchemaOrBool<'a> = BooleanOr<Schema<'a>>;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ReferenceOr<'a, T> {
+    // objects with `$ref` are `Ref`, even if they also match `Value`.
+    Ref(Reference<'a>),
+    Value(T),
+}
+
+impl<'de: 'a, 'a, T> ReferenceOrModel<'de> for ReferenceOr<'a, T>
+where
+    T: Deserialize<'de>,
+{
+    type Str = Str<'a>;
+    type Value = T;
+
+    fn from_ref(r#ref: Str<'a>, summary: Option<Str<'a>>, description: Option<Str<'a>>) -> Self {
+        ReferenceOr::Ref(Reference {
+            r#ref,
+            summary,
+            description,
+        })
+    }
+
+    fn from_value(value: T) -> Self {
+        ReferenceOr::Value(value)
+    }
+}
+
+impl<'de: 'a, 'a, T> Deserialize<'de> for ReferenceOr<'a, T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ReferenceOrVisitor(PhantomData))
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OpenApi<'a> {
+    #[serde(borrow)]
+    pub openapi: Str<'a>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$self")]
+    #[serde(borrow)]
+    pub self_uri: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub info: Info<'a>,
+
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none", rename = "jsonSchemaDialect")]
+    #[serde(borrow)]
+    pub json_schema_dialect: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub servers: Option<Vec<Server<'a>>>,
+
+    // required until v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub paths: Option<Paths<'a>>,
+
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub webhooks: Option<HashMap<Str<'a>, ReferenceOr<'a, PathItem<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub components: Option<Components<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub security: Option<Vec<SecurityRequirement<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub tags: Option<Vec<Tag<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    #[serde(borrow)]
+    pub external_docs: Option<ExternalDocumentation<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Info<'a> {
+    #[serde(borrow)]
+    pub title: Str<'a>,
+
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub summary: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "termsOfService")]
+    #[serde(borrow)]
+    pub terms_of_service: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub contact: Option<Contact<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub license: Option<License<'a>>,
+
+    #[serde(borrow)]
+    pub version: Str<'a>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Contact<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub name: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub url: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub email: Option<Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum License<'a> {
+    Url(LicenseUrl<'a>),
+    // since v3.1.0
+    Id(LicenseId<'a>),
+}
+
+#[derive(Deserialize)]
+struct LicenseFields<'a> {
+    #[serde(borrow)]
+    name: Str<'a>,
+    #[serde(borrow)]
+    url: Option<Str<'a>>,
+    #[serde(borrow)]
+    identifier: Option<Str<'a>>,
+    #[serde(flatten)]
+    #[serde(borrow)]
+    extensions: Extensions<'a>,
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for License<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = LicenseFields::deserialize(deserializer)?;
+        if fields.url.is_some() && fields.identifier.is_some() {
+            return Err(license_error());
+        }
+        Ok(match fields.url {
+            Some(url) => License::Url(LicenseUrl {
+                name: fields.name,
+                url,
+                extensions: fields.extensions,
+            }),
+            None => License::Id(LicenseId {
+                name: fields.name,
+                identifier: fields.identifier,
+                extensions: fields.extensions,
+            }),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LicenseId<'a> {
+    #[serde(borrow)]
+    pub name: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub identifier: Option<Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LicenseUrl<'a> {
+    #[serde(borrow)]
+    pub name: Str<'a>,
+
+    #[serde(borrow)]
+    pub url: Str<'a>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Server<'a> {
+    #[serde(borrow)]
+    pub url: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub name: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub variables: Option<HashMap<Str<'a>, ServerVariable<'a>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ServerVariable<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub r#enum: Option<Vec<Str<'a>>>,
+
+    #[serde(borrow)]
+    pub default: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Components<'a> {
+    // or reference until v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub schemas: Option<HashMap<Str<'a>, Schema<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub responses: Option<HashMap<Str<'a>, ReferenceOr<'a, Response<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub parameters: Option<HashMap<Str<'a>, ReferenceOr<'a, Parameter<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub examples: Option<HashMap<Str<'a>, ReferenceOr<'a, Example<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBodies")]
+    #[serde(borrow)]
+    pub request_bodies: Option<HashMap<Str<'a>, ReferenceOr<'a, RequestBody<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub headers: Option<HashMap<Str<'a>, ReferenceOr<'a, Header<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "securitySchemes")]
+    #[serde(borrow)]
+    pub security_schemes: Option<HashMap<Str<'a>, ReferenceOr<'a, SecurityScheme<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub links: Option<HashMap<Str<'a>, ReferenceOr<'a, Link<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub callbacks: Option<HashMap<Str<'a>, ReferenceOr<'a, Callback<'a>>>>,
+
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pathItems")]
+    #[serde(borrow)]
+    pub path_items: Option<HashMap<Str<'a>, ReferenceOr<'a, PathItem<'a>>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+type Paths<'a> = KeyValues<'a, PathItem<'a>>;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PathItem<'a> {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref")]
+    #[serde(borrow)]
+    pub r#ref: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub summary: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub get: Option<Operation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub put: Option<Operation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub post: Option<Operation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub delete: Option<Operation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub options: Option<Operation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub head: Option<Operation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub patch: Option<Operation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub trace: Option<Operation<'a>>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub query: Option<Operation<'a>>,
+
+    // since v3.2.0
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "additionalOperations"
+    )]
+    #[serde(borrow)]
+    pub additional_operations: Option<HashMap<Str<'a>, Operation<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub servers: Option<Vec<Server<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub parameters: Option<Vec<ReferenceOr<'a, Parameter<'a>>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Operation<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub tags: Option<Vec<Str<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub summary: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    #[serde(borrow)]
+    pub external_docs: Option<ExternalDocumentation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "operationId")]
+    #[serde(borrow)]
+    pub operation_id: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub parameters: Option<Vec<ReferenceOr<'a, Parameter<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBody")]
+    #[serde(borrow)]
+    pub request_body: Option<ReferenceOr<'a, RequestBody<'a>>>,
+
+    // required until v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub responses: Option<Responses<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub callbacks: Option<HashMap<Str<'a>, ReferenceOr<'a, Callback<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub security: Option<Vec<SecurityRequirement<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub servers: Option<Vec<Server<'a>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ExternalDocumentation<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub url: Str<'a>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Parameter<'a> {
+    #[serde(borrow)]
+    pub name: Str<'a>,
+
+    pub r#in: ParameterIn,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowEmptyValue")]
+    pub allow_empty_value: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub pattern: ParameterPattern<'a>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ParameterPattern<'a> {
+    Content(ParameterContent<'a>),
+    Style(ParameterStyle<'a>),
+}
+
+#[derive(Deserialize)]
+struct ParameterPatternFields<'a> {
+    #[serde(borrow)]
+    content: Option<HashMap<Str<'a>, MediaType<'a>>>,
+    #[serde(borrow)]
+    style: Option<Str<'a>>,
+    explode: Option<bool>,
+    #[serde(rename = "allowReserved")]
+    allow_reserved: Option<bool>,
+    #[serde(borrow)]
+    schema: Option<SchemaOrBool<'a>>,
+    #[serde(borrow)]
+    examples: Option<HashMap<Str<'a>, ReferenceOr<'a, Example<'a>>>>,
+    #[serde(borrow)]
+    example: Option<Any<'a>>,
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ParameterPattern<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = ParameterPatternFields::deserialize(deserializer)?;
+        Ok(match fields.content {
+            Some(content) => ParameterPattern::Content(ParameterContent { content }),
+            None => ParameterPattern::Style(ParameterStyle {
+                style: fields.style,
+                explode: fields.explode,
+                allow_reserved: fields.allow_reserved,
+                schema: fields.schema,
+                example: examples(fields.examples, fields.example),
+            }),
+        })
+    }
+}
+
+impl Default for ParameterPattern<'_> {
+    fn default() -> Self {
+        ParameterPattern::Style(ParameterStyle::default())
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ParameterStyle<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    style: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explode: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
+    allow_reserved: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    schema: Option<SchemaOrBool<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    example: Examples<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Examples<'a> {
+    Values {
+        examples: HashMap<Str<'a>, ReferenceOr<'a, Example<'a>>>,
+    },
+    Value {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        example: Option<Any<'a>>,
+    },
+}
+
+#[derive(Deserialize)]
+struct ExamplesFields<'a> {
+    #[serde(borrow)]
+    examples: Option<HashMap<Str<'a>, ReferenceOr<'a, Example<'a>>>>,
+    #[serde(borrow)]
+    example: Option<Any<'a>>,
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Examples<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = ExamplesFields::deserialize(deserializer)?;
+        Ok(examples(fields.examples, fields.example))
+    }
+}
+
+fn examples<'a>(
+    examples: Option<HashMap<Str<'a>, ReferenceOr<'a, Example<'a>>>>,
+    example: Option<Any<'a>>,
+) -> Examples<'a> {
+    match examples {
+        Some(examples) => Examples::Values { examples },
+        None => Examples::Value { example },
+    }
+}
+
+impl Default for Examples<'_> {
+    fn default() -> Self {
+        Examples::Value { example: None }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ParameterContent<'a> {
+    #[serde(borrow)]
+    content: HashMap<Str<'a>, MediaType<'a>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub content: HashMap<Str<'a>, MediaType<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct MediaType<'a> {
+    // or reference until v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub schema: Option<SchemaOrBool<'a>>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none", rename = "itemSchema")]
+    #[serde(borrow)]
+    pub item_schema: Option<SchemaOrBool<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    example: Examples<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub encoding: Option<HashMap<Str<'a>, Encoding<'a>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Encoding<'a> {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentType")]
+    #[serde(borrow)]
+    pub content_type: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub headers: Option<HashMap<Str<'a>, ReferenceOr<'a, Header<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub style: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
+    pub allow_reserved: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Responses<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub r#default: Option<ReferenceOr<'a, Response<'a>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub statuses: HttpStatuses<ReferenceOr<'a, Response<'a>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Response<'a> {
+    #[serde(borrow)]
+    pub description: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub headers: Option<HashMap<Str<'a>, ReferenceOr<'a, Header<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub content: Option<HashMap<Str<'a>, MediaType<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub links: Option<HashMap<Str<'a>, ReferenceOr<'a, Link<'a>>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+type Callback<'a> = KeyValues<'a, ReferenceOr<'a, PathItem<'a>>>;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Example<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub summary: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub value: ExampleValue<'a>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ExampleValue<'a> {
+    Url {
+        #[serde(rename = "externalValue")]
+        external_value: Str<'a>,
+    },
+    Literal {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<Any<'a>>,
+    },
+}
+
+#[derive(Deserialize)]
+struct ExampleValueFields<'a> {
+    #[serde(rename = "externalValue")]
+    #[serde(borrow)]
+    external_value: Option<Str<'a>>,
+    #[serde(borrow)]
+    value: Option<Any<'a>>,
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ExampleValue<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = ExampleValueFields::deserialize(deserializer)?;
+        Ok(match fields.external_value {
+            Some(external_value) => ExampleValue::Url { external_value },
+            None => ExampleValue::Literal {
+                value: fields.value,
+            },
+        })
+    }
+}
+
+impl Default for ExampleValue<'_> {
+    fn default() -> Self {
+        ExampleValue::Literal { value: None }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Link<'a> {
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub operation: LinkOperation<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub parameters: Option<HashMap<Str<'a>, Any<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBody")]
+    #[serde(borrow)]
+    pub request_body: Option<Any<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub server: Option<Server<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum LinkOperation<'a> {
+    #[serde(rename = "operationRef")]
+    #[serde(borrow)]
+    Ref(Str<'a>),
+
+    #[serde(rename = "operationId")]
+    #[serde(borrow)]
+    Id(Str<'a>),
+}
+
+impl Default for LinkOperation<'_> {
+    fn default() -> Self {
+        LinkOperation::Id(Str::default())
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Header<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowEmptyValue")]
+    pub allow_empty_value: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub pattern: ParameterPattern<'a>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Tag<'a> {
+    #[serde(borrow)]
+    pub name: Str<'a>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub summary: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    #[serde(borrow)]
+    pub external_docs: Option<ExternalDocumentation<'a>>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub parent: Option<Str<'a>>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub kind: Option<Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Reference<'a> {
+    #[serde(rename = "$ref")]
+    #[serde(borrow)]
+    pub r#ref: Str<'a>,
+
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub summary: Option<Str<'a>>,
+
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Schema<'a> {
+    // until v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2.3.1
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref")]
+    #[serde(borrow)]
+    pub r#ref: Option<Str<'a>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.2.1
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allOf")]
+    #[serde(borrow)]
+    pub all_of: Option<Vec<SchemaOrBool<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "anyOf")]
+    #[serde(borrow)]
+    pub any_of: Option<Vec<SchemaOrBool<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "oneOf")]
+    #[serde(borrow)]
+    pub one_of: Option<Vec<SchemaOrBool<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub items: Option<Arc<SchemaOrBool<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub properties: Option<HashMap<Str<'a>, SchemaOrBool<'a>>>,
+
+    // bool support until v3.1.0
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "additionalProperties"
+    )]
+    #[serde(borrow)]
+    pub additional_properties: Option<Arc<SchemaOrBool<'a>>>,
+
+    // unit only until v3.1.0
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<SchemaTypes>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub r#enum: Option<Vec<Any<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.5
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub required: Option<Vec<Str<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-7
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub format: Option<Str<'a>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-9
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub title: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub default: Option<Any<'a>>,
+
+    // groups take their keywords before `extensions`.
+    #[serde(flatten, deserialize_with = "group")]
+    #[serde(borrow)]
+    pub validation: Option<Box<SchemaValidation<'a>>>,
+
+    #[serde(flatten, deserialize_with = "group")]
+    #[serde(borrow)]
+    pub applicators: Option<Box<SchemaApplicators<'a>>>,
+
+    #[serde(flatten, deserialize_with = "group")]
+    #[serde(borrow)]
+    pub metadata: Option<Box<SchemaMetadata<'a>>>,
+
+    #[serde(flatten, deserialize_with = "group")]
+    #[serde(borrow)]
+    pub content: Option<Box<SchemaContent<'a>>>,
+
+    #[serde(flatten, deserialize_with = "group")]
+    #[serde(borrow)]
+    pub oas: Option<Box<SchemaOas<'a>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: KeyValues<'a, Any<'a>>,
+}
+
+/// Validation keywords other than `type`, `enum`, `required` and `format`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaValidation<'a> {
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub r#const: Option<Any<'a>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.2
+    #[serde(skip_serializing_if = "Option::is_none", rename = "multipleOf")]
+    pub multiple_of: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "exclusiveMaximum")]
+    pub exclusive_maximum: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "exclusiveMinimum")]
+    pub exclusive_minimum: Option<i32>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.3
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxLength")]
+    pub max_length: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minLength")]
+    pub min_length: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub pattern: Option<Str<'a>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.4
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxItems")]
+    pub max_items: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minItems")]
+    pub min_items: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "uniqueItems")]
+    pub unique_items: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxContains")]
+    pub max_contains: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minContains")]
+    pub min_contains: Option<u32>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.5
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxProperties")]
+    pub max_properties: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "minProperties")]
+    pub min_properties: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentRequired")]
+    #[serde(borrow)]
+    pub dependent_required: Option<HashMap<Str<'a>, Vec<Str<'a>>>>,
+}
+
+/// Applicators other than `allOf`, `anyOf`, `oneOf`, `items`, `properties`
+/// and `additionalProperties`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaApplicators<'a> {
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.2.1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub not: Option<Arc<SchemaOrBool<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.2.2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub r#if: Option<Arc<SchemaOrBool<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub then: Option<Arc<SchemaOrBool<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub r#else: Option<Arc<SchemaOrBool<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentSchemas")]
+    #[serde(borrow)]
+    pub dependent_schemas: Option<HashMap<Str<'a>, SchemaOrBool<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.1
+    #[serde(skip_serializing_if = "Option::is_none", rename = "prefixItems")]
+    #[serde(borrow)]
+    pub prefix_items: Option<Vec<SchemaOrBool<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub contains: Option<Arc<SchemaOrBool<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.2
+    #[serde(skip_serializing_if = "Option::is_none", rename = "patternProperties")]
+    #[serde(borrow)]
+    pub pattern_properties: Option<HashMap<Str<'a>, SchemaOrBool<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "propertyNames")]
+    #[serde(borrow)]
+    pub property_names: Option<Arc<SchemaOrBool<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-11
+    #[serde(skip_serializing_if = "Option::is_none", rename = "unevaluatedItems")]
+    #[serde(borrow)]
+    pub unevaluated_items: Option<Arc<SchemaOrBool<'a>>>,
+
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "unevaluatedProperties"
+    )]
+    #[serde(borrow)]
+    pub unevaluated_properties: Option<Arc<SchemaOrBool<'a>>>,
+}
+
+/// Core keywords other than `$ref`, and annotations other than `title`,
+/// `description` and `default`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaMetadata<'a> {
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.1
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$schema")]
+    #[serde(borrow)]
+    pub schema: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$vocabulary")]
+    #[serde(borrow)]
+    pub vocabulary: Option<HashMap<Str<'a>, bool>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$id")]
+    #[serde(borrow)]
+    pub id: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$anchor")]
+    #[serde(borrow)]
+    pub anchor: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$dynamicAnchor")]
+    #[serde(borrow)]
+    pub dynamic_anchor: Option<Str<'a>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2.3.2
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$dynamicRef")]
+    #[serde(borrow)]
+    pub dynamic_ref: Option<Str<'a>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2.4
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$defs")]
+    #[serde(borrow)]
+    pub defs: Option<HashMap<Str<'a>, SchemaOrBool<'a>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.3
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$comment")]
+    #[serde(borrow)]
+    pub comment: Option<Str<'a>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-9
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "readOnly")]
+    pub read_only: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub examples: Option<Vec<Any<'a>>>,
+}
+
+// https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-8
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaContent<'a> {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentEncoding")]
+    #[serde(borrow)]
+    pub content_encoding: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentMediaType")]
+    #[serde(borrow)]
+    pub content_media_type: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentSchema")]
+    #[serde(borrow)]
+    pub content_schema: Option<Arc<SchemaOrBool<'a>>>,
+}
+
+/// Keywords defined by OpenAPI, other than `nullable`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaOas<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub discriminator: Option<Discriminator<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub xml: Option<Xml<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    #[serde(borrow)]
+    pub external_docs: Option<ExternalDocumentation<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub example: Option<Any<'a>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Discriminator<'a> {
+    #[serde(rename = "propertyName")]
+    #[serde(borrow)]
+    pub property_name: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub mapping: Option<HashMap<Str<'a>, Str<'a>>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Xml<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub name: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub namespace: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub prefix: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrapped: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum SecurityScheme<'a> {
+    #[serde(rename = "apiKey")]
+    #[serde(borrow)]
+    ApiKey(SecuritySchemeApiKey<'a>),
+
+    #[serde(rename = "http")]
+    #[serde(borrow)]
+    Http(SecuritySchemeHttp<'a>),
+
+    #[serde(rename = "oauth2")]
+    #[serde(borrow)]
+    Oauth2(SecuritySchemeOauth2<'a>),
+
+    #[serde(rename = "openIdConnect")]
+    #[serde(borrow)]
+    OpenIdConnect(SecuritySchemeOpenIdConnect<'a>),
+
+    // since v3.1.0
+    #[serde(rename = "mutualTLS")]
+    #[serde(borrow)]
+    MutualTls(SecuritySchemeMutualTls<'a>),
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SecuritySchemeApiKey<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Any<'a>>,
+
+    #[serde(borrow)]
+    pub name: Str<'a>,
+
+    pub r#in: SecuritySchemeIn,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SecuritySchemeHttp<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Any<'a>>,
+
+    #[serde(borrow)]
+    pub scheme: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bearerFormat")]
+    #[serde(borrow)]
+    pub bearer_format: Option<Str<'a>>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SecuritySchemeMutualTls<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Any<'a>>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SecuritySchemeOauth2<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Any<'a>>,
+
+    #[serde(borrow)]
+    pub flows: OAuthFlows<'a>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SecuritySchemeOpenIdConnect<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub description: Option<Any<'a>>,
+
+    #[serde(rename = "openIdConnectUrl")]
+    #[serde(borrow)]
+    pub open_id_connect_url: Str<'a>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OAuthFlows<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub implicit: Option<OAuthFlowImplicit<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub password: Option<OAuthFlowPassword<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "clientCredentials")]
+    #[serde(borrow)]
+    pub client_credentials: Option<OAuthFlowClientCredentials<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "authorizationCode")]
+    #[serde(borrow)]
+    pub authorization_code: Option<OAuthFlowAuthorizationCode<'a>>,
+
+    // since v3.2.0
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "deviceAuthorization"
+    )]
+    #[serde(borrow)]
+    pub device_authorization: Option<OAuthFlowDeviceAuthorization<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OAuthFlowImplicit<'a> {
+    #[serde(rename = "authorizationUrl")]
+    #[serde(borrow)]
+    pub authrization_url: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "refreshUrl")]
+    #[serde(borrow)]
+    pub refresh_url: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub scopes: HashMap<Str<'a>, Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OAuthFlowPassword<'a> {
+    #[serde(rename = "tokenUrl")]
+    #[serde(borrow)]
+    pub token_url: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "refreshUrl")]
+    #[serde(borrow)]
+    pub refresh_url: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub scopes: HashMap<Str<'a>, Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OAuthFlowClientCredentials<'a> {
+    #[serde(rename = "tokenUrl")]
+    #[serde(borrow)]
+    pub token_url: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "refreshUrl")]
+    #[serde(borrow)]
+    pub refresh_url: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub scopes: HashMap<Str<'a>, Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OAuthFlowAuthorizationCode<'a> {
+    #[serde(rename = "authorizationUrl")]
+    #[serde(borrow)]
+    pub authrization_url: Str<'a>,
+
+    #[serde(rename = "tokenUrl")]
+    #[serde(borrow)]
+    pub token_url: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "refreshUrl")]
+    #[serde(borrow)]
+    pub refresh_url: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub scopes: HashMap<Str<'a>, Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+// since v3.2.0
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OAuthFlowDeviceAuthorization<'a> {
+    #[serde(rename = "deviceAuthorizationUrl")]
+    #[serde(borrow)]
+    pub device_authorization_url: Str<'a>,
+
+    #[serde(rename = "tokenUrl")]
+    #[serde(borrow)]
+    pub token_url: Str<'a>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "refreshUrl")]
+    #[serde(borrow)]
+    pub refresh_url: Option<Str<'a>>,
+
+    #[serde(borrow)]
+    pub scopes: HashMap<Str<'a>, Str<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: Extensions<'a>,
+}
+
+type SecurityRequirement<'a> = HashMap<Str<'a>, Vec<Str<'a>>>;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PartOpenApi<'a> {
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none", rename = "jsonSchemaDialect")]
+    #[serde(borrow)]
+    pub json_schema_dialect: Option<Str<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub servers: Option<Vec<Server<'a>>>,
+
+    // required until v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub paths: Option<Paths<'a>>,
+
+    // since v3.1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub webhooks: Option<HashMap<Str<'a>, ReferenceOr<'a, PathItem<'a>>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub components: Option<Components<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub security: Option<Vec<SecurityRequirement<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub tags: Option<Vec<Tag<'a>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    #[serde(borrow)]
+    pub external_docs: Option<ExternalDocumentation<'a>>,
+
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub extensions: KeyValues<'a, Any<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn petstore() -> String {
+        let v = serde_yaml::from_str::<serde_json::Value>(include_str!(
+            "../testdata/oas/v3.0/petstore.yaml"
+        ))
+        .unwrap();
+        serde_json::to_string(&v).unwrap()
+    }
+
+    #[test]
+    fn borrowed_from_json() {
+        let s = petstore();
+        let doc = serde_json::from_str::<OpenApi>(&s).unwrap();
+        assert!(doc.info.title.is_borrowed());
+        assert!(
+            doc.paths
+                .as_ref()
+                .unwrap()
+                .values
+                .keys()
+                .all(Str::is_borrowed)
+        );
+
+        let schemas = doc.components.as_ref().unwrap().schemas.as_ref().unwrap();
+        let items = schemas["Pets"].items.as_ref().unwrap();
+        match items.as_ref() {
+            BooleanOr::Value(s) => assert!(s.r#ref.as_ref().unwrap().is_borrowed()),
+            BooleanOr::Boolean(_) => unreachable!(),
+        }
+
+        let owned = serde_json::from_str::<crate::OpenApi>(&s).unwrap();
+        assert_eq!(owned, doc.to_owned_model().unwrap());
+    }
+
+    #[test]
+    fn borrowed_testdata() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        for sub in ["oas/v3.0", "oas/v3.1", "version"] {
+            for entry in std::fs::read_dir(dir.join(sub)).unwrap() {
+                let path = entry.unwrap().path();
+                let content = std::fs::read_to_string(&path).unwrap();
+                let v = serde_yaml::from_str::<serde_json::Value>(&content).unwrap();
+                let s = serde_json::to_string(&v).unwrap();
+
+                let owned = serde_json::from_str::<crate::OpenApi>(&s).unwrap();
+                let doc = serde_json::from_str::<OpenApi>(&s).unwrap();
+                assert_eq!(owned, doc.to_owned_model().unwrap(), "{}", path.display());
+            }
+        }
+    }
+
+    #[test]
+    fn borrowed_copied() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r#"{"openapi": "3.1.0", "info": {"title": "a\nb", "version": "1", "x-a": "c"}}"#,
+        )
+        .unwrap();
+        assert_eq!("a\nb", &*doc.info.title);
+        assert!(!doc.info.title.is_borrowed());
+        assert!(doc.info.version.is_borrowed());
+        assert_eq!(
+            Some(&Any::String("c".into())),
+            doc.info.extensions.values.get("a")
+        );
+
+        let s = "openapi: 3.1.0\ninfo: {title: \"a\\nb\", version: '1'}";
+        let doc = serde_yaml::from_str::<OpenApi>(s).unwrap();
+        assert_eq!("a\nb", &*doc.info.title);
+        assert!(!doc.info.title.is_borrowed());
+        assert!(doc.info.version.is_borrowed());
+    }
+
+    #[test]
+    fn borrowed_dispatch() {
+        let s = r#"{"$ref":"a","description":"b","in":"query"}"#;
+        let r = serde_json::from_str::<ReferenceOr<Parameter>>(s).unwrap();
+        let ReferenceOr::Ref(r) = r else {
+            panic!("{r:?}")
+        };
+        assert!(r.r#ref.is_borrowed());
+        assert_eq!(Some("b"), r.description.as_deref());
+
+        let s = r##"{"get":{"responses":{}},"$ref":"#/a"}"##;
+        let r = serde_json::from_str::<ReferenceOr<PathItem>>(s).unwrap();
+        let owned = serde_json::from_str::<crate::ReferenceOr<crate::PathItem>>(s).unwrap();
+        assert_eq!(owned, convert(&r).unwrap());
+        let ReferenceOr::Value(r) = r else {
+            panic!("{r:?}")
+        };
+        assert_eq!(Some("#/a"), r.r#ref.as_deref());
+
+        let s = r#"{"name":"a","in":"query","content":[]}"#;
+        let r = serde_json::from_str::<ReferenceOr<Parameter>>(s);
+        assert!(r.unwrap_err().to_string().contains("expected a map"));
+
+        let s = r#"{"externalValue":"a","summary":"b"}"#;
+        let r = serde_json::from_str::<Example>(s).unwrap();
+        assert_eq!(
+            ExampleValue::Url {
+                external_value: "a".into()
+            },
+            r.value
+        );
+
+        let s = r#"{"name":"a","url":"b","identifier":"c"}"#;
+        let r = serde_json::from_str::<License>(s);
+        assert!(
+            r.unwrap_err()
+                .to_string()
+                .contains("both `url` and `identifier`")
+        );
+    }
+}
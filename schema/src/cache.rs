@@ -0,0 +1,260 @@
+//! An on-disk cache of parsed documents in CBOR.
+//!
+//! An entry is keyed by the path of the document and the type of the value,
+//! and is valid while the format, the modified time and the hash of the
+//! content match.
+
+use super::error::{Error, ErrorKind};
+use super::load;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::any;
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::UNIX_EPOCH;
+use xxhash_rust::xxh3::{Xxh3, xxh3_64};
+
+/// The environment variable of the directory which [`load::from_path`] caches to.
+pub const CACHE_DIR_ENV: &str = "OPENAPI_SPEC_CACHE_DIR";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+#[derive(Deserialize, PartialEq, Serialize)]
+struct Key {
+    format: u32,
+    version: String,
+    path: String,
+    modified: u128,
+    hash: u64,
+}
+
+impl Key {
+    // bump when the serialized form of the model changes.
+    const FORMAT_VERSION: u32 = 1;
+}
+
+#[derive(Serialize)]
+struct EntryRef<'a, T> {
+    key: &'a Key,
+    value: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Entry<T> {
+    key: Key,
+    value: T,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    /// The cache in the directory of [`CACHE_DIR_ENV`], if it is set.
+    pub fn from_env() -> Option<Self> {
+        env::var_os(CACHE_DIR_ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(Cache::new)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Read a document like [`load::from_path`], from the cache if it is valid.
+    pub fn load<T>(&self, path: &Path) -> Result<T, Error>
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        let uri = path.display().to_string();
+        let content = fs::read_to_string(path).map_err(|e| Error::from(e).with_uri(&uri))?;
+        let key = key(path, &content).map_err(|e| e.with_uri(&uri))?;
+        if let Some(value) = self.get(path, &key) {
+            return Ok(value);
+        }
+
+        let value = load::from_content(path, &content).map_err(|e| e.with_uri(&uri))?;
+        // a failure to write only loses the next hit.
+        let _ = self.put(path, &key, &value);
+        Ok(value)
+    }
+
+    // the file of the entry, by the path and the type.
+    fn file<T>(&self, path: &Path) -> PathBuf {
+        let mut hasher = Xxh3::new();
+        hasher.update(path.as_os_str().as_encoded_bytes());
+        hasher.update(&[0]);
+        hasher.update(any::type_name::<T>().as_bytes());
+        self.dir.join(format!("{:016x}.cbor", hasher.digest()))
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &Path, key: &Key) -> Option<T> {
+        let file = File::open(self.file::<T>(path)).ok()?;
+        let entry: Entry<T> = ciborium::from_reader(BufReader::new(file)).ok()?;
+        (entry.key == *key).then_some(entry.value)
+    }
+
+    fn put<T: Serialize>(&self, path: &Path, key: &Key, value: &T) -> Result<(), Error> {
+        fs::create_dir_all(&self.dir)?;
+
+        // write to a temporary file not to read a partial entry.
+        let file = self.file::<T>(path);
+        let temp = file.with_extension(format!("{}.tmp", process::id()));
+        let mut writer = BufWriter::new(File::create(&temp)?);
+        ciborium::into_writer(&EntryRef { key, value }, &mut writer)
+            .map_err(|e| Error::new(ErrorKind::Convert, "Can not write cache.").with_source(e))?;
+        writer.into_inner().map_err(|e| e.into_error())?;
+        fs::rename(&temp, &file)?;
+        Ok(())
+    }
+}
+
+fn key(path: &Path, content: &str) -> Result<Key, Error> {
+    let modified = fs::metadata(path)?
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    Ok(Key {
+        format: Key::FORMAT_VERSION,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        path: path.display().to_string(),
+        modified,
+        hash: xxh3_64(content.as_bytes()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpenApi, PartOpenApi, ReferenceOr, Response};
+    use proptest::prelude::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    // the directory is removed when the `TempDir` is dropped.
+    fn fixture() -> (TempDir, Cache, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openapi.yaml");
+        fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/oas/v3.0/petstore.yaml"),
+            &path,
+        )
+        .unwrap();
+        let cache = Cache::new(dir.path().join("cache"));
+        (dir, cache, path)
+    }
+
+    fn cached<T: DeserializeOwned + Serialize>(cache: &Cache, path: &Path) -> Option<T> {
+        let content = fs::read_to_string(path).unwrap();
+        cache.get(path, &key(path, &content).unwrap())
+    }
+
+    fn cbor<T: DeserializeOwned + Serialize>(v: &T) -> T {
+        let mut bytes = vec![];
+        ciborium::into_writer(v, &mut bytes).unwrap();
+        ciborium::from_reader(bytes.as_slice()).unwrap()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn cache_cbor_openapi(v in any::<OpenApi>()) {
+            prop_assert_eq!(&v, &cbor(&v));
+        }
+
+        #[test]
+        fn cache_cbor_part_openapi(v in any::<PartOpenApi>()) {
+            prop_assert_eq!(&v, &cbor(&v));
+        }
+    }
+
+    #[test]
+    fn cache_cbor_reference_or() {
+        // the map ends while `ReferenceOr` reads the keys of `Reference`.
+        let v = ReferenceOr::Value(Response::default());
+        assert_eq!(v, cbor(&v));
+    }
+
+    #[test]
+    fn cache_hit() {
+        let (_dir, cache, path) = fixture();
+        assert!(cached::<OpenApi>(&cache, &path).is_none());
+
+        let doc = cache.load::<OpenApi>(&path).unwrap();
+        assert_eq!(Some(doc.clone()), cached::<OpenApi>(&cache, &path));
+        assert_eq!(doc, cache.load::<OpenApi>(&path).unwrap());
+        assert_eq!(doc, load::from_path::<OpenApi>(&path).unwrap());
+
+        // the type is a part of the key.
+        assert!(cached::<PartOpenApi>(&cache, &path).is_none());
+        let part = cache.load::<PartOpenApi>(&path).unwrap();
+        assert_eq!(Some(part), cached::<PartOpenApi>(&cache, &path));
+    }
+
+    #[test]
+    fn cache_invalidate_content() {
+        let (_dir, cache, path) = fixture();
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        cache.load::<OpenApi>(&path).unwrap();
+
+        // keeps the modified time, the hash invalidates the entry.
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replace("Swagger Petstore", "Petstore")).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert!(cached::<OpenApi>(&cache, &path).is_none());
+        assert_eq!("Petstore", cache.load::<OpenApi>(&path).unwrap().info.title);
+        assert!(cached::<OpenApi>(&cache, &path).is_some());
+    }
+
+    #[test]
+    fn cache_invalidate_modified() {
+        let (_dir, cache, path) = fixture();
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        cache.load::<OpenApi>(&path).unwrap();
+
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified + Duration::from_secs(1))
+            .unwrap();
+        assert!(cached::<OpenApi>(&cache, &path).is_none());
+        cache.load::<OpenApi>(&path).unwrap();
+        assert!(cached::<OpenApi>(&cache, &path).is_some());
+    }
+
+    #[test]
+    fn cache_invalidate_corrupted() {
+        let (_dir, cache, path) = fixture();
+        let doc = cache.load::<OpenApi>(&path).unwrap();
+
+        fs::write(cache.file::<OpenApi>(&path), b"corrupted").unwrap();
+        assert!(cached::<OpenApi>(&cache, &path).is_none());
+        assert_eq!(doc, cache.load::<OpenApi>(&path).unwrap());
+        assert!(cached::<OpenApi>(&cache, &path).is_some());
+    }
+
+    #[test]
+    fn cache_error() {
+        let (_dir, cache, path) = fixture();
+        fs::write(&path, "openapi: 3.1.0").unwrap();
+        let e = cache.load::<OpenApi>(&path).unwrap_err();
+        assert_eq!(ErrorKind::Parse, e.kind());
+        assert!(e.uri().unwrap().ends_with("openapi.yaml"));
+        assert!(cached::<OpenApi>(&cache, &path).is_none());
+    }
+}
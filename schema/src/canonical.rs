@@ -0,0 +1,534 @@
+use super::OpenApi;
+use serde::ser::Error as _;
+use serde_yaml::{Mapping, Value};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Style {
+    Json,
+    #[default]
+    Yaml,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatOptions {
+    pub style: Style,
+    // sort `paths` and `components` by name.
+    pub sort: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            style: Style::Yaml,
+            sort: true,
+        }
+    }
+}
+
+/// Serialize `doc` with the conventional key order.
+///
+/// The model doesn't keep the order of maps, so they are always sorted.
+pub fn to_string(doc: &OpenApi, options: &FormatOptions) -> Result<String, serde_yaml::Error> {
+    // go through `serde_json::Value` to sort keys of `HashMap`s.
+    let value = serde_json::to_value(doc).map_err(serde_yaml::Error::custom)?;
+    let value = serde_yaml::to_value(value)?;
+    let options = FormatOptions {
+        sort: true,
+        ..options.clone()
+    };
+    emit(value, &options)
+}
+
+/// Reformat a JSON or YAML document with the conventional key order.
+///
+/// Unlike `to_string`, unknown fields and the order of maps are kept.
+pub fn format_str(input: &str, options: &FormatOptions) -> Result<String, serde_yaml::Error> {
+    let value = serde_yaml::from_str::<Value>(input)?;
+    emit(value, options)
+}
+
+/// Whether `input` is already formatted.
+pub fn is_formatted(input: &str, options: &FormatOptions) -> Result<bool, serde_yaml::Error> {
+    Ok(format_str(input, options)? == input)
+}
+
+fn emit(value: Value, options: &FormatOptions) -> Result<String, serde_yaml::Error> {
+    let value = order(value, Kind::Document, options.sort);
+    match options.style {
+        Style::Yaml => serde_yaml::to_string(&value),
+        Style::Json => serde_json::to_string_pretty(&value)
+            .map(|s| s + "\n")
+            .map_err(serde_yaml::Error::custom),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Document,
+    Info,
+    Server,
+    Paths,
+    PathItem,
+    Operation,
+    Parameter,
+    RequestBody,
+    MediaType,
+    Encoding,
+    Responses,
+    Response,
+    Header,
+    Example,
+    Link,
+    Callback,
+    Components,
+    SecurityScheme,
+    Tag,
+    Schema,
+    // map of the kind.
+    Map(&'static Kind),
+    // map of the kind, sorted by name if requested.
+    Sorted(&'static Kind),
+    // user values like examples, kept as is.
+    Any,
+}
+
+const METHODS: [&str; 9] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace", "query",
+];
+
+impl Kind {
+    fn keys(&self) -> &'static [&'static str] {
+        match self {
+            Kind::Document => &[
+                "openapi",
+                "$self",
+                "info",
+                "jsonSchemaDialect",
+                "servers",
+                "paths",
+                "webhooks",
+                "components",
+                "security",
+                "tags",
+                "externalDocs",
+            ],
+            Kind::Info => &[
+                "title",
+                "summary",
+                "description",
+                "termsOfService",
+                "contact",
+                "license",
+                "version",
+            ],
+            Kind::Server => &["url", "description", "name", "variables"],
+            Kind::PathItem => &[
+                "$ref",
+                "summary",
+                "description",
+                "servers",
+                "parameters",
+                "get",
+                "put",
+                "post",
+                "delete",
+                "options",
+                "head",
+                "patch",
+                "trace",
+                "query",
+                "additionalOperations",
+            ],
+            Kind::Operation => &[
+                "tags",
+                "summary",
+                "description",
+                "operationId",
+                "parameters",
+                "requestBody",
+                "responses",
+                "callbacks",
+                "deprecated",
+                "security",
+                "servers",
+                "externalDocs",
+            ],
+            Kind::Parameter | Kind::Header => &[
+                "$ref",
+                "name",
+                "in",
+                "description",
+                "required",
+                "deprecated",
+                "allowEmptyValue",
+                "style",
+                "explode",
+                "allowReserved",
+                "schema",
+                "example",
+                "examples",
+                "content",
+            ],
+            Kind::RequestBody => &["$ref", "description", "required", "content"],
+            Kind::MediaType => &["schema", "itemSchema", "example", "examples", "encoding"],
+            Kind::Encoding => &[
+                "contentType",
+                "headers",
+                "style",
+                "explode",
+                "allowReserved",
+            ],
+            Kind::Response => &["$ref", "description", "headers", "content", "links"],
+            Kind::Example => &["$ref", "summary", "description", "value", "externalValue"],
+            Kind::Link => &[
+                "$ref",
+                "operationRef",
+                "operationId",
+                "parameters",
+                "requestBody",
+                "description",
+                "server",
+            ],
+            Kind::Components => &[
+                "schemas",
+                "responses",
+                "parameters",
+                "examples",
+                "requestBodies",
+                "headers",
+                "securitySchemes",
+                "links",
+                "callbacks",
+                "pathItems",
+            ],
+            Kind::SecurityScheme => &[
+                "$ref",
+                "type",
+                "description",
+                "name",
+                "in",
+                "scheme",
+                "bearerFormat",
+                "flows",
+                "openIdConnectUrl",
+                "deprecated",
+            ],
+            Kind::Tag => &[
+                "name",
+                "summary",
+                "description",
+                "externalDocs",
+                "parent",
+                "kind",
+            ],
+            Kind::Schema => &[
+                "$schema",
+                "$vocabulary",
+                "$id",
+                "$anchor",
+                "$dynamicAnchor",
+                "$ref",
+                "$dynamicRef",
+                "$comment",
+                "title",
+                "description",
+                "type",
+                "format",
+                "enum",
+                "const",
+                "default",
+                "nullable",
+                "multipleOf",
+                "minimum",
+                "exclusiveMinimum",
+                "maximum",
+                "exclusiveMaximum",
+                "minLength",
+                "maxLength",
+                "pattern",
+                "contentEncoding",
+                "contentMediaType",
+                "contentSchema",
+                "prefixItems",
+                "items",
+                "contains",
+                "minContains",
+                "maxContains",
+                "minItems",
+                "maxItems",
+                "uniqueItems",
+                "unevaluatedItems",
+                "required",
+                "properties",
+                "patternProperties",
+                "additionalProperties",
+                "propertyNames",
+                "minProperties",
+                "maxProperties",
+                "unevaluatedProperties",
+                "dependentRequired",
+                "dependentSchemas",
+                "allOf",
+                "anyOf",
+                "oneOf",
+                "not",
+                "if",
+                "then",
+                "else",
+                "discriminator",
+                "readOnly",
+                "writeOnly",
+                "deprecated",
+                "xml",
+                "externalDocs",
+                "example",
+                "examples",
+                "$defs",
+            ],
+            _ => &[],
+        }
+    }
+
+    fn child(&self, key: &str) -> Kind {
+        if key.starts_with("x-") {
+            return Kind::Any;
+        }
+
+        match (self, key) {
+            (Kind::Map(kind), _) | (Kind::Sorted(kind), _) => **kind,
+            (Kind::Document, "info") => Kind::Info,
+            (Kind::Document, "servers") => Kind::Server,
+            (Kind::Document, "paths") => Kind::Paths,
+            (Kind::Document, "webhooks") => Kind::Map(&Kind::PathItem),
+            (Kind::Document, "components") => Kind::Components,
+            (Kind::Document, "tags") => Kind::Tag,
+            (Kind::Paths, _) => Kind::PathItem,
+            (Kind::PathItem, "servers") => Kind::Server,
+            (Kind::PathItem, "parameters") => Kind::Parameter,
+            (Kind::PathItem, m) if METHODS.contains(&m) => Kind::Operation,
+            (Kind::PathItem, "additionalOperations") => Kind::Map(&Kind::Operation),
+            (Kind::Operation, "parameters") => Kind::Parameter,
+            (Kind::Operation, "requestBody") => Kind::RequestBody,
+            (Kind::Operation, "responses") => Kind::Responses,
+            (Kind::Operation, "callbacks") => Kind::Map(&Kind::Callback),
+            (Kind::Operation, "servers") => Kind::Server,
+            (Kind::Callback, _) => Kind::PathItem,
+            (Kind::Parameter | Kind::Header, "schema") => Kind::Schema,
+            (Kind::Parameter | Kind::Header, "examples") => Kind::Map(&Kind::Example),
+            (Kind::Parameter | Kind::Header, "content") => Kind::Map(&Kind::MediaType),
+            (Kind::RequestBody, "content") => Kind::Map(&Kind::MediaType),
+            (Kind::MediaType, "schema" | "itemSchema") => Kind::Schema,
+            (Kind::MediaType, "examples") => Kind::Map(&Kind::Example),
+            (Kind::MediaType, "encoding") => Kind::Map(&Kind::Encoding),
+            (Kind::Encoding, "headers") => Kind::Map(&Kind::Header),
+            (Kind::Responses, _) => Kind::Response,
+            (Kind::Response, "headers") => Kind::Map(&Kind::Header),
+            (Kind::Response, "content") => Kind::Map(&Kind::MediaType),
+            (Kind::Response, "links") => Kind::Map(&Kind::Link),
+            (Kind::Components, "schemas") => Kind::Sorted(&Kind::Schema),
+            (Kind::Components, "responses") => Kind::Sorted(&Kind::Response),
+            (Kind::Components, "parameters") => Kind::Sorted(&Kind::Parameter),
+            (Kind::Components, "examples") => Kind::Sorted(&Kind::Example),
+            (Kind::Components, "requestBodies") => Kind::Sorted(&Kind::RequestBody),
+            (Kind::Components, "headers") => Kind::Sorted(&Kind::Header),
+            (Kind::Components, "securitySchemes") => Kind::Sorted(&Kind::SecurityScheme),
+            (Kind::Components, "links") => Kind::Sorted(&Kind::Link),
+            (Kind::Components, "callbacks") => Kind::Sorted(&Kind::Callback),
+            (Kind::Components, "pathItems") => Kind::Sorted(&Kind::PathItem),
+            (Kind::Schema, "properties" | "patternProperties" | "dependentSchemas" | "$defs") => {
+                Kind::Map(&Kind::Schema)
+            }
+            (
+                Kind::Schema,
+                "allOf"
+                | "anyOf"
+                | "oneOf"
+                | "prefixItems"
+                | "items"
+                | "contains"
+                | "not"
+                | "if"
+                | "then"
+                | "else"
+                | "additionalProperties"
+                | "unevaluatedItems"
+                | "unevaluatedProperties"
+                | "propertyNames"
+                | "contentSchema",
+            ) => Kind::Schema,
+            _ => Kind::Any,
+        }
+    }
+}
+
+fn order(value: Value, kind: Kind, sort: bool) -> Value {
+    let map = match value {
+        Value::Sequence(items) => {
+            return Value::Sequence(items.into_iter().map(|v| order(v, kind, sort)).collect());
+        }
+        Value::Mapping(map) if kind != Kind::Any => map,
+        value => return value,
+    };
+
+    let keys = kind.keys();
+    let sorted = match kind {
+        Kind::Paths | Kind::Sorted(_) => sort,
+        // status codes, `X` of ranges is ordered after digits.
+        Kind::Responses => true,
+        _ => false,
+    };
+
+    let mut entries = map
+        .into_iter()
+        .enumerate()
+        .map(|(i, (k, v))| {
+            let k = key(k);
+            let rank = if k.starts_with("x-") {
+                (3, 0)
+            } else if kind == Kind::Responses && k == "default" {
+                (1, 0)
+            } else if let Some(p) = keys.iter().position(|key| *key == k) {
+                (0, p)
+            } else if keys.is_empty() {
+                (0, if sorted { 0 } else { i })
+            } else {
+                // unknown fields are placed after known ones.
+                (2, 0)
+            };
+            (rank, k, v)
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|(a, ak, _), (b, bk, _)| a.cmp(b).then_with(|| ak.cmp(bk)));
+
+    let mut map = Mapping::new();
+    for (_, k, v) in entries {
+        let child = kind.child(&k);
+        map.insert(Value::String(k), order(v, child, sort));
+    }
+    Value::Mapping(map)
+}
+
+fn key(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+        value => serde_yaml::to_string(&value)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"
+paths:
+  /b:
+    get:
+      responses:
+        default:
+          description: ""
+        404:
+          description: ""
+        "200":
+          description: ""
+          content:
+            text/plain: {}
+            application/json:
+              schema:
+                properties:
+                  z: {type: string}
+                  a: {type: integer, example: {b: 1, a: 2}}
+                type: object
+      operationId: b
+      x-a: 1
+      summary: ""
+      tags: [a]
+  /a: {}
+info:
+  version: ""
+  title: ""
+openapi: 3.1.0
+components:
+  schemas:
+    B: {}
+    A: {}
+"#;
+
+    #[test]
+    fn canonical_yaml() {
+        let s = format_str(INPUT, &FormatOptions::default()).unwrap();
+        assert_eq!(
+            r#"openapi: 3.1.0
+info:
+  title: ''
+  version: ''
+paths:
+  /a: {}
+  /b:
+    get:
+      tags:
+      - a
+      summary: ''
+      operationId: b
+      responses:
+        '200':
+          description: ''
+          content:
+            text/plain: {}
+            application/json:
+              schema:
+                type: object
+                properties:
+                  z:
+                    type: string
+                  a:
+                    type: integer
+                    example:
+                      b: 1
+                      a: 2
+        '404':
+          description: ''
+        default:
+          description: ''
+      x-a: 1
+components:
+  schemas:
+    A: {}
+    B: {}
+"#,
+            s
+        );
+        assert!(is_formatted(&s, &FormatOptions::default()).unwrap());
+        assert!(!is_formatted(INPUT, &FormatOptions::default()).unwrap());
+    }
+
+    #[test]
+    fn canonical_unsorted_json() {
+        let options = FormatOptions {
+            style: Style::Json,
+            sort: false,
+        };
+        let s = format_str(INPUT, &options).unwrap();
+        assert!(s.starts_with("{\n  \"openapi\": \"3.1.0\",\n  \"info\": {\n    \"title\""));
+        assert!(s.find("\"/b\"").unwrap() < s.find("\"/a\"").unwrap());
+        assert!(s.find("\"B\"").unwrap() < s.find("\"A\"").unwrap());
+        assert!(is_formatted(&s, &options).unwrap());
+    }
+
+    #[test]
+    fn canonical_model() {
+        let doc = serde_yaml::from_str::<OpenApi>(INPUT).unwrap();
+        let s = to_string(&doc, &FormatOptions::default()).unwrap();
+        assert_eq!(
+            s,
+            to_string(&doc.clone(), &FormatOptions::default()).unwrap()
+        );
+        assert!(s.find("  /a:").unwrap() < s.find("  /b:").unwrap());
+        assert!(s.find("'200'").unwrap() < s.find("'404'").unwrap());
+    }
+}
@@ -0,0 +1,900 @@
+use super::generate::Generator;
+use super::merge::MergeError;
+use super::model::Any;
+use super::resolve::Resolver;
+use super::validate::{Validator, equal};
+use super::visit::pointer;
+use super::{BooleanOr, Schema, SchemaOrBool, SchemaType, SchemaTypes};
+
+const MAX_DEPTH: usize = 32;
+// longest string or array tried as a counterexample.
+const MAX_LENGTH: u32 = 256;
+const SEEDS: u64 = 8;
+
+const ALL_TYPES: [SchemaType; 6] = [
+    SchemaType::Null,
+    SchemaType::Boolean,
+    SchemaType::Object,
+    SchemaType::Array,
+    SchemaType::Number,
+    SchemaType::String,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference {
+    // JSON pointer in the instance.
+    pub pointer: String,
+    pub keyword: String,
+    // a value at `pointer` which is accepted by `a` and rejected by `b`.
+    pub counterexample: Option<Any>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Compatibility {
+    Compatible,
+    // some values accepted by `a` are rejected by `b`.
+    Incompatible(Vec<Difference>),
+    // keywords out of the supported subset, which can not be compared.
+    Unknown(Vec<Difference>),
+}
+
+impl Compatibility {
+    pub fn is_compatible(&self) -> bool {
+        *self == Compatibility::Compatible
+    }
+}
+
+/// Check whether every value accepted by `a` is also accepted by `b`.
+///
+/// Types, enums, numeric and length bounds, `required`, `properties` and
+/// `additionalProperties` are compared. Counterexamples are searched from
+/// sample values, so they are given only when one is found.
+pub fn is_subschema<R: Resolver + ?Sized>(a: &Schema, b: &Schema, resolver: &R) -> Compatibility {
+    let mut checker = Checker {
+        resolver,
+        validator: Validator::new(resolver),
+        seen: vec![],
+        incompatible: vec![],
+        unknown: vec![],
+    };
+    checker.check(a, b, "", 0);
+
+    if !checker.incompatible.is_empty() {
+        Compatibility::Incompatible(checker.incompatible)
+    } else if !checker.unknown.is_empty() {
+        Compatibility::Unknown(checker.unknown)
+    } else {
+        Compatibility::Compatible
+    }
+}
+
+enum Normalized {
+    Schema(Box<Schema>),
+    // `allOf` which no value satisfies.
+    Never,
+    Unknown(&'static str),
+}
+
+struct Checker<'a, R: Resolver + ?Sized> {
+    resolver: &'a R,
+    validator: Validator<'a, R>,
+    // pairs of references already compared, to stop at recursive schemas.
+    seen: Vec<(String, String)>,
+    incompatible: Vec<Difference>,
+    unknown: Vec<Difference>,
+}
+
+impl<R: Resolver + ?Sized> Checker<'_, R> {
+    fn check(&mut self, a: &Schema, b: &Schema, ip: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            return self.unknown(ip, "$ref", a, b);
+        }
+        if let (Some(x), Some(y)) = (&a.r#ref, &b.r#ref) {
+            let pair = (x.clone(), y.clone());
+            if self.seen.contains(&pair) {
+                return;
+            }
+            self.seen.push(pair);
+        }
+
+        let a = match self.normalize(a) {
+            Normalized::Schema(s) => *s,
+            Normalized::Never => return,
+            Normalized::Unknown(keyword) => return self.unknown(ip, keyword, a, b),
+        };
+        let b = match self.normalize(b) {
+            Normalized::Schema(s) => *s,
+            Normalized::Never => return self.incompatible(ip, "allOf", &a, b),
+            Normalized::Unknown(keyword) => return self.unknown(ip, keyword, &a, b),
+        };
+        if a == b || accepts_all(&b) {
+            return;
+        }
+
+        // every branch of `a` must be accepted by `b`.
+        if let Some(branches) = a.any_of.as_ref().or(a.one_of.as_ref()) {
+            if a.any_of.is_some() && a.one_of.is_some() {
+                return self.unknown(ip, "oneOf", &a, &b);
+            }
+            let mut base = a.clone();
+            base.any_of = None;
+            base.one_of = None;
+            for s in branches.iter().filter(|s| !s.is_false()) {
+                let branch = Schema {
+                    all_of: Some(vec![base.clone().into(), s.clone()]),
+                    ..Default::default()
+                };
+                self.check(&branch, &b, ip, depth + 1);
+            }
+            return;
+        }
+
+        // `a` must be accepted by one of the branches of `b`.
+        if let Some(branches) = b.any_of.as_ref().or(b.one_of.as_ref()) {
+            let keyword = if b.one_of.is_some() { "oneOf" } else { "anyOf" };
+            if b.any_of.is_some() && b.one_of.is_some() {
+                return self.unknown(ip, keyword, &a, &b);
+            }
+            let mut base = b.clone();
+            base.any_of = None;
+            base.one_of = None;
+            self.check(&a, &base, ip, depth + 1);
+
+            let matched = branches
+                .iter()
+                .position(|s| self.accepts(&a, s.schema(), ip, depth + 1));
+            let exclusive = |i: usize, this: &Self| {
+                branches
+                    .iter()
+                    .enumerate()
+                    .all(|(j, s)| i == j || s.is_false() || this.disjoint(&a, s.schema()))
+            };
+            match matched {
+                Some(_) if b.any_of.is_some() => {}
+                Some(i) if exclusive(i, self) => {}
+                _ => self.unknown(ip, keyword, &a, &b),
+            }
+            return;
+        }
+
+        // the values of `a` are enumerated.
+        if a.r#const().is_some() || a.r#enum.is_some() {
+            let values = a
+                .r#const()
+                .into_iter()
+                .chain(a.r#enum.iter().flatten())
+                .filter(|v| self.validator.is_valid(&a, v))
+                .cloned()
+                .collect::<Vec<_>>();
+            for v in values {
+                if let Some(e) = self.validator.validate(&b, &v).first() {
+                    let keyword = e.schema.rsplit('/').next().unwrap_or_default();
+                    self.incompatible.push(Difference {
+                        pointer: ip.to_string(),
+                        keyword: keyword.to_string(),
+                        counterexample: Some(v),
+                    });
+                }
+            }
+            return;
+        }
+
+        for keyword in unsupported(&a, &b) {
+            self.unknown(ip, keyword, &a, &b);
+        }
+
+        let expected = types(&b);
+        let types = types(&a).unwrap_or(ALL_TYPES.to_vec());
+        if let Some(expected) = expected {
+            if !types.iter().all(|t| covers(&expected, t)) {
+                self.incompatible(ip, "type", &a, &b);
+            }
+        }
+
+        if b.r#const().is_some() || b.r#enum.is_some() {
+            let keyword = if b.r#const().is_some() {
+                "const"
+            } else {
+                "enum"
+            };
+            // only `null` and booleans have finite values.
+            let finite = types
+                .iter()
+                .all(|t| matches!(t, SchemaType::Null | SchemaType::Boolean));
+            let values = [Any::Null, Any::Boolean(false), Any::Boolean(true)];
+            let rejected = values
+                .iter()
+                .any(|v| self.validator.is_valid(&a, v) && !self.validator.is_valid(&b, v));
+            if !finite || rejected {
+                self.incompatible(ip, keyword, &a, &b);
+            }
+        }
+
+        let has = |ts: &[SchemaType]| types.iter().any(|t| ts.contains(t));
+        if has(&[SchemaType::Number, SchemaType::Integer]) {
+            self.numeric(&a, &b, ip);
+        }
+        if has(&[SchemaType::String]) {
+            self.string(&a, &b, ip);
+        }
+        if has(&[SchemaType::Array]) {
+            self.array(&a, &b, ip, depth);
+        }
+        if has(&[SchemaType::Object]) {
+            self.object(&a, &b, ip, depth);
+        }
+    }
+
+    // `false` accepts nothing, and `true` accepts everything.
+    fn subschema(&mut self, a: &SchemaOrBool, b: &SchemaOrBool, ip: &str, depth: usize) {
+        match (a, b) {
+            (BooleanOr::Boolean(false), _) | (_, BooleanOr::Boolean(true)) => {}
+            (a, BooleanOr::Boolean(false)) => {
+                self.incompatible(ip, "false", a.schema(), b.schema())
+            }
+            (a, b) => self.check(a.schema(), b.schema(), ip, depth),
+        }
+    }
+
+    fn numeric(&mut self, a: &Schema, b: &Schema, ip: &str) {
+        // exclusive bounds of integers are inclusive bounds of the next integers.
+        let integer = types(a).is_some_and(|t| !t.contains(&SchemaType::Number));
+
+        let lower = |s: &Schema, integer: bool| {
+            let exclusive = s.exclusive_minimum().map(|m| match integer {
+                true => (m as f64 + 1.0, false),
+                false => (m as f64, true),
+            });
+            tightest(s.minimum().map(|m| (m as f64, false)), exclusive, 1.0)
+        };
+        let upper = |s: &Schema, integer: bool| {
+            let exclusive = s.exclusive_maximum().map(|m| match integer {
+                true => (m as f64 - 1.0, false),
+                false => (m as f64, true),
+            });
+            tightest(s.maximum().map(|m| (m as f64, false)), exclusive, -1.0)
+        };
+
+        if let Some((m, exclusive)) = lower(b, false) {
+            let ok = lower(a, integer).is_some_and(|(n, e)| n > m || (n == m && (e || !exclusive)));
+            if !ok {
+                let keyword = if exclusive {
+                    "exclusiveMinimum"
+                } else {
+                    "minimum"
+                };
+                self.incompatible(ip, keyword, a, b);
+            }
+        }
+        if let Some((m, exclusive)) = upper(b, false) {
+            let ok = upper(a, integer).is_some_and(|(n, e)| n < m || (n == m && (e || !exclusive)));
+            if !ok {
+                let keyword = if exclusive {
+                    "exclusiveMaximum"
+                } else {
+                    "maximum"
+                };
+                self.incompatible(ip, keyword, a, b);
+            }
+        }
+
+        if let Some(m) = b.multiple_of().filter(|m| *m != 0) {
+            let ok = (m == 1 && integer) || a.multiple_of().is_some_and(|n| n % m == 0);
+            if !ok {
+                self.incompatible(ip, "multipleOf", a, b);
+            }
+        }
+    }
+
+    fn string(&mut self, a: &Schema, b: &Schema, ip: &str) {
+        if !at_least(a.min_length(), b.min_length()) {
+            self.incompatible(ip, "minLength", a, b);
+        }
+        if !at_most(a.max_length(), b.max_length()) {
+            self.incompatible(ip, "maxLength", a, b);
+        }
+    }
+
+    fn array(&mut self, a: &Schema, b: &Schema, ip: &str, depth: usize) {
+        if !at_least(a.min_items(), b.min_items()) {
+            self.incompatible(ip, "minItems", a, b);
+        }
+        if !at_most(a.max_items(), b.max_items()) {
+            self.incompatible(ip, "maxItems", a, b);
+        }
+        if b.unique_items() == Some(true) && a.unique_items() != Some(true) {
+            self.incompatible(ip, "uniqueItems", a, b);
+        }
+        if let Some(items) = &b.items {
+            if a.max_items() != Some(0) {
+                let a_items = a.items.as_deref().unwrap_or(&BooleanOr::Boolean(true));
+                self.subschema(a_items, items, &pointer(ip, "0"), depth + 1);
+            }
+        }
+    }
+
+    fn object(&mut self, a: &Schema, b: &Schema, ip: &str, depth: usize) {
+        if !at_least(a.min_properties(), b.min_properties()) {
+            self.incompatible(ip, "minProperties", a, b);
+        }
+        if !at_most(a.max_properties(), b.max_properties()) {
+            self.incompatible(ip, "maxProperties", a, b);
+        }
+
+        let required = a.required.iter().flatten().collect::<Vec<_>>();
+        if b.required
+            .iter()
+            .flatten()
+            .any(|name| !required.contains(&name))
+        {
+            self.incompatible(ip, "required", a, b);
+        }
+
+        let mut names = a
+            .properties
+            .iter()
+            .chain(&b.properties)
+            .flat_map(|p| p.keys())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        for name in &names {
+            let ptr = pointer(ip, name);
+            match (property(a, name), property(b, name)) {
+                (Some(x), Some(y)) => self.check(&x, &y, &ptr, depth + 1),
+                (Some(x), None) => self.incompatible(&ptr, "additionalProperties", &x, &never()),
+                _ => {}
+            }
+        }
+
+        // a name declared in neither side.
+        let mut name = "additional".to_string();
+        while names.contains(&&name) {
+            name.push('_');
+        }
+        let ptr = pointer(ip, &name);
+        match (property(a, &name), property(b, &name)) {
+            (Some(x), Some(y)) => self.check(&x, &y, &ptr, depth + 1),
+            (Some(_), None) => self.incompatible(ip, "additionalProperties", a, b),
+            _ => {}
+        }
+    }
+
+    // run `check` separately, and tell whether no difference is found.
+    fn accepts(&mut self, a: &Schema, b: &Schema, ip: &str, depth: usize) -> bool {
+        let incompatible = std::mem::take(&mut self.incompatible);
+        let unknown = std::mem::take(&mut self.unknown);
+        self.check(a, b, ip, depth);
+        let accepted = self.incompatible.is_empty() && self.unknown.is_empty();
+        self.incompatible = incompatible;
+        self.unknown = unknown;
+        accepted
+    }
+
+    // no value is accepted by both, judged by types or the values of a required property.
+    fn disjoint(&self, a: &Schema, b: &Schema) -> bool {
+        let (a, b) = match (self.normalize(a), self.normalize(b)) {
+            (Normalized::Schema(a), Normalized::Schema(b)) => (a, b),
+            _ => return false,
+        };
+
+        if let (Some(x), Some(y)) = (types(&a), types(&b)) {
+            if !x.iter().any(|t| y.iter().any(|u| overlap(t, u))) {
+                return true;
+            }
+        }
+
+        for name in a.required.iter().flatten() {
+            if !b.required.iter().flatten().any(|n| n == name) {
+                continue;
+            }
+            let values = |s: &Schema| {
+                let p = s.properties.as_ref()?.get(name)?;
+                match self.normalize(p.schema()) {
+                    Normalized::Schema(p) => values(&p),
+                    _ => None,
+                }
+            };
+            if let (Some(x), Some(y)) = (values(&a), values(&b)) {
+                if !x.iter().any(|v| y.iter().any(|w| equal(v, w))) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // resolve `$ref` and merge `allOf`.
+    fn normalize(&self, schema: &Schema) -> Normalized {
+        let mut s = schema;
+        for _ in 0..MAX_DEPTH {
+            match &s.r#ref {
+                Some(r) if *s == reference(r) => match self.resolver.resolve_schema(None, r) {
+                    Some(target) => s = target,
+                    None => return Normalized::Unknown("$ref"),
+                },
+                _ => break,
+            }
+        }
+        if s.r#ref.is_none() && s.all_of.is_none() {
+            return Normalized::Schema(Box::new(s.clone()));
+        }
+
+        let wrapped;
+        let s = match &s.r#ref {
+            Some(r) => {
+                let siblings = Schema {
+                    r#ref: None,
+                    ..s.clone()
+                };
+                wrapped = Schema {
+                    all_of: Some(vec![reference(r).into(), siblings.into()]),
+                    ..Default::default()
+                };
+                &wrapped
+            }
+            None => s,
+        };
+        match s.merge_all_of(self.resolver) {
+            Ok(merged) if merged.all_of.is_none() => Normalized::Schema(Box::new(merged)),
+            Ok(_) => Normalized::Unknown("allOf"),
+            Err(MergeError::Conflict { .. }) => Normalized::Never,
+            Err(_) => Normalized::Unknown("$ref"),
+        }
+    }
+
+    fn incompatible(&mut self, ip: &str, keyword: &str, a: &Schema, b: &Schema) {
+        let counterexample = self.counterexample(a, b);
+        self.incompatible.push(Difference {
+            pointer: ip.to_string(),
+            keyword: keyword.to_string(),
+            counterexample,
+        });
+    }
+
+    // incompatible if a counterexample is found.
+    fn unknown(&mut self, ip: &str, keyword: &str, a: &Schema, b: &Schema) {
+        let counterexample = self.counterexample(a, b);
+        let difference = Difference {
+            pointer: ip.to_string(),
+            keyword: keyword.to_string(),
+            counterexample,
+        };
+        match difference.counterexample {
+            Some(_) => self.incompatible.push(difference),
+            None => self.unknown.push(difference),
+        }
+    }
+
+    fn counterexample(&self, a: &Schema, b: &Schema) -> Option<Any> {
+        self.candidates(a, b)
+            .into_iter()
+            .find(|v| self.validator.is_valid(a, v) && !self.validator.is_valid(b, v))
+    }
+
+    // values around the bounds of both schemas, and values generated from `a`.
+    fn candidates(&self, a: &Schema, b: &Schema) -> Vec<Any> {
+        let mut values = vec![Any::Null, Any::Boolean(false), Any::Boolean(true)];
+
+        let mut numbers = vec![0, 1, -1];
+        for s in [a, b] {
+            let bounds = [
+                s.minimum(),
+                s.maximum(),
+                s.exclusive_minimum(),
+                s.exclusive_maximum(),
+            ];
+            for m in bounds.into_iter().flatten() {
+                numbers.extend([m.saturating_sub(1), m, m.saturating_add(1)]);
+            }
+        }
+        values.extend(numbers.iter().map(|n| Any::Integer(*n)));
+        values.extend(numbers.iter().map(|n| Any::Number(*n as f32 + 0.5)));
+
+        let mut lengths = vec![0, 1];
+        for s in [a, b] {
+            let bounds = [s.min_length(), s.max_length(), s.min_items(), s.max_items()];
+            for m in bounds.into_iter().flatten().filter(|m| *m < MAX_LENGTH) {
+                lengths.extend([m.saturating_sub(1), m, m + 1]);
+            }
+        }
+        let item = a
+            .items
+            .as_ref()
+            .map(|s| Generator::new(self.resolver).generate(s.schema()))
+            .unwrap_or(Any::Null);
+        for n in lengths {
+            values.push(Any::String("a".repeat(n as usize)));
+            values.push(Any::Array(vec![item.clone(); n as usize]));
+        }
+
+        let mut generated = vec![Generator::new(self.resolver).generate(a)];
+        for seed in 0..SEEDS {
+            generated.push(Generator::new(self.resolver).with_seed(seed).generate(a));
+        }
+        for v in generated {
+            if let Any::Object(props) = &v {
+                for name in b.required.iter().flatten() {
+                    let mut props = props.clone();
+                    props.remove(name);
+                    values.push(Any::Object(props));
+                }
+                let mut props = props.clone();
+                props.insert("additional".to_string(), Any::Null);
+                values.push(Any::Object(props));
+            }
+            values.push(v);
+        }
+        values
+    }
+}
+
+// keywords of `b` out of the supported subset, which differ from `a`.
+fn unsupported(a: &Schema, b: &Schema) -> Vec<&'static str> {
+    let mut keywords = vec![];
+    let mut differ = |keyword, differ: bool| {
+        if differ {
+            keywords.push(keyword);
+        }
+    };
+
+    differ("not", b.not().is_some() && a.not() != b.not());
+    differ(
+        "if",
+        b.r#if().is_some() && (a.r#if(), a.then(), a.r#else()) != (b.r#if(), b.then(), b.r#else()),
+    );
+    differ(
+        "dependentSchemas",
+        b.dependent_schemas().is_some() && a.dependent_schemas() != b.dependent_schemas(),
+    );
+    differ(
+        "dependentRequired",
+        b.dependent_required().is_some() && a.dependent_required() != b.dependent_required(),
+    );
+    differ(
+        "prefixItems",
+        b.prefix_items().is_some() && a.prefix_items() != b.prefix_items(),
+    );
+    differ(
+        "contains",
+        b.contains().is_some()
+            && (a.contains(), a.min_contains(), a.max_contains())
+                != (b.contains(), b.min_contains(), b.max_contains()),
+    );
+    differ(
+        "unevaluatedItems",
+        b.unevaluated_items().is_some() && a.unevaluated_items() != b.unevaluated_items(),
+    );
+    differ(
+        "unevaluatedProperties",
+        b.unevaluated_properties().is_some()
+            && a.unevaluated_properties() != b.unevaluated_properties(),
+    );
+    differ(
+        "$dynamicRef",
+        b.dynamic_ref().is_some() && a.dynamic_ref() != b.dynamic_ref(),
+    );
+    differ(
+        "propertyNames",
+        b.property_names().is_some() && a.property_names() != b.property_names(),
+    );
+    // `patternProperties` of `a` change which properties are additional.
+    differ(
+        "patternProperties",
+        (a.pattern_properties().is_some() || b.pattern_properties().is_some())
+            && a.pattern_properties() != b.pattern_properties(),
+    );
+
+    // formats and patterns are checked only for strings.
+    let string = types(a).is_none_or(|t| t.contains(&SchemaType::String));
+    differ(
+        "pattern",
+        string && b.pattern().is_some() && a.pattern() != b.pattern(),
+    );
+    differ(
+        "format",
+        string && b.format.is_some() && a.format != b.format,
+    );
+    keywords
+}
+
+// no keyword other than annotations.
+fn accepts_all(s: &Schema) -> bool {
+    let assertions = Schema {
+        title: None,
+        description: None,
+        default: None,
+        metadata: None,
+        oas: None,
+        extensions: Default::default(),
+        ..s.clone()
+    };
+    // `$dynamicRef` is not an annotation.
+    assertions == Schema::default() && s.dynamic_ref().is_none()
+}
+
+// the schema of the property `name`, or `None` if it is not allowed.
+fn property(s: &Schema, name: &str) -> Option<Schema> {
+    let p = match s.properties.as_ref().and_then(|p| p.get(name)) {
+        Some(p) => Some(p.as_ref()),
+        None => s.additional_properties.as_deref(),
+    };
+    match p {
+        None | Some(BooleanOr::Boolean(true)) => Some(Schema::default()),
+        Some(BooleanOr::Boolean(false)) => None,
+        Some(BooleanOr::Value(s)) => Some(s.clone()),
+    }
+}
+
+// a schema which accepts nothing.
+fn never() -> Schema {
+    SchemaOrBool::Boolean(false).schema().clone()
+}
+
+fn reference(r: &str) -> Schema {
+    Schema {
+        r#ref: Some(r.to_string()),
+        ..Default::default()
+    }
+}
+
+// declared types, with `null` of `nullable`.
+fn types(s: &Schema) -> Option<Vec<SchemaType>> {
+    let mut types = match s.r#type.as_ref()? {
+        SchemaTypes::Unit(t) => vec![t.clone()],
+        SchemaTypes::Array(v) => v.clone(),
+    };
+    if s.nullable == Some(true) && !types.contains(&SchemaType::Null) {
+        types.push(SchemaType::Null);
+    }
+    Some(types)
+}
+
+fn covers(types: &[SchemaType], t: &SchemaType) -> bool {
+    types.contains(t) || (*t == SchemaType::Integer && types.contains(&SchemaType::Number))
+}
+
+fn overlap(t: &SchemaType, u: &SchemaType) -> bool {
+    covers(std::slice::from_ref(t), u) || covers(std::slice::from_ref(u), t)
+}
+
+fn values(s: &Schema) -> Option<Vec<Any>> {
+    match (s.r#const(), &s.r#enum) {
+        (Some(v), _) => Some(vec![v.clone()]),
+        (None, Some(values)) => Some(values.clone()),
+        _ => None,
+    }
+}
+
+// the tighter of an inclusive and an exclusive bound, in `direction`.
+fn tightest(
+    inclusive: Option<(f64, bool)>,
+    exclusive: Option<(f64, bool)>,
+    direction: f64,
+) -> Option<(f64, bool)> {
+    match (inclusive, exclusive) {
+        (Some(i), Some(e)) if (e.0 - i.0) * direction >= 0.0 => Some(e),
+        (Some(i), _) => Some(i),
+        (None, e) => e,
+    }
+}
+
+fn at_least(a: Option<u32>, b: Option<u32>) -> bool {
+    b.is_none_or(|m| m == 0 || a.is_some_and(|n| n >= m))
+}
+
+fn at_most(a: Option<u32>, b: Option<u32>) -> bool {
+    b.is_none_or(|m| a.is_some_and(|n| n <= m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenApi;
+
+    fn schema(s: &str) -> Schema {
+        serde_json::from_str(s).unwrap()
+    }
+
+    fn check(a: &str, b: &str) -> Compatibility {
+        is_subschema(&schema(a), &schema(b), &())
+    }
+
+    fn keywords(c: &Compatibility) -> Vec<(&str, &str)> {
+        match c {
+            Compatibility::Compatible => vec![],
+            Compatibility::Incompatible(d) | Compatibility::Unknown(d) => d
+                .iter()
+                .map(|d| (d.pointer.as_str(), d.keyword.as_str()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn compat_types_and_bounds() {
+        assert!(
+            check(
+                r#"{"type": "integer", "minimum": 1, "exclusiveMaximum": 10}"#,
+                r#"{"type": "number", "minimum": 0, "maximum": 9}"#
+            )
+            .is_compatible()
+        );
+        assert!(
+            check(
+                r#"{"type": "integer", "exclusiveMinimum": 0}"#,
+                r#"{"type": "integer", "minimum": 1}"#
+            )
+            .is_compatible()
+        );
+
+        assert_eq!(
+            Compatibility::Incompatible(vec![Difference {
+                pointer: "".to_string(),
+                keyword: "type".to_string(),
+                counterexample: Some(Any::Integer(0)),
+            }]),
+            check(
+                r#"{"type": ["string", "integer"]}"#,
+                r#"{"type": "string"}"#
+            )
+        );
+        assert_eq!(
+            Compatibility::Incompatible(vec![Difference {
+                pointer: "".to_string(),
+                keyword: "minimum".to_string(),
+                counterexample: Some(Any::Integer(0)),
+            }]),
+            check(
+                r#"{"type": "integer", "minimum": 0}"#,
+                r#"{"type": "integer", "minimum": 1}"#
+            )
+        );
+        assert_eq!(
+            vec![("", "maxLength")],
+            keywords(&check(
+                r#"{"type": "string", "maxLength": 5}"#,
+                r#"{"type": "string", "maxLength": 3}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn compat_enum() {
+        assert!(check(r#"{"enum": ["a", "b"]}"#, r#"{"enum": ["b", "a", "c"]}"#).is_compatible());
+        assert_eq!(
+            Compatibility::Incompatible(vec![Difference {
+                pointer: "".to_string(),
+                keyword: "enum".to_string(),
+                counterexample: Some(Any::String("c".to_string())),
+            }]),
+            check(r#"{"enum": ["a", "b", "c"]}"#, r#"{"enum": ["b", "a"]}"#)
+        );
+        assert_eq!(
+            vec![("", "enum")],
+            keywords(&check(r#"{"type": "string"}"#, r#"{"enum": ["a"]}"#))
+        );
+        assert!(check(r#"{"type": "boolean"}"#, r#"{"enum": [true, false]}"#).is_compatible());
+    }
+
+    #[test]
+    fn compat_object() {
+        let c = check(
+            r#"{
+                "type": "object",
+                "required": ["id"],
+                "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}
+            }"#,
+            r#"{
+                "type": "object",
+                "required": ["id", "name"],
+                "properties": {"id": {"type": "integer", "maximum": 100}},
+                "additionalProperties": false
+            }"#,
+        );
+        assert_eq!(
+            vec![
+                ("", "required"),
+                ("/id", "maximum"),
+                ("/name", "additionalProperties"),
+                ("", "additionalProperties"),
+            ],
+            keywords(&c)
+        );
+        if let Compatibility::Incompatible(d) = &c {
+            assert_eq!(Some(Any::Integer(101)), d[1].counterexample);
+        }
+
+        assert!(
+            check(
+                r#"{
+                    "type": "object",
+                    "required": ["a", "b"],
+                    "properties": {"a": {"type": "string"}, "b": {"type": "array", "items": {"type": "integer"}}},
+                    "additionalProperties": false
+                }"#,
+                r#"{
+                    "type": "object",
+                    "required": ["a"],
+                    "properties": {"b": {"type": "array", "items": {"type": "number"}}}
+                }"#,
+            )
+            .is_compatible()
+        );
+    }
+
+    #[test]
+    fn compat_composition() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "schemas": {
+                    "Cat": {
+                        "type": "object",
+                        "required": ["petType"],
+                        "properties": {"petType": {"const": "cat"}, "next": {"$ref": "#/components/schemas/Cat"}}
+                    },
+                    "Dog": {
+                        "type": "object",
+                        "required": ["petType"],
+                        "properties": {"petType": {"const": "dog"}}
+                    },
+                    "Pet": {
+                        "oneOf": [
+                            {"$ref": "#/components/schemas/Cat"},
+                            {"$ref": "#/components/schemas/Dog"}
+                        ]
+                    }
+                }
+            }
+        }"##,
+        )
+        .unwrap();
+
+        let cat = schema(r##"{"$ref": "#/components/schemas/Cat"}"##);
+        let pet = schema(r##"{"$ref": "#/components/schemas/Pet"}"##);
+        assert!(is_subschema(&cat, &cat, &doc).is_compatible());
+        assert!(is_subschema(&cat, &pet, &doc).is_compatible());
+        assert!(!is_subschema(&pet, &cat, &doc).is_compatible());
+
+        assert!(
+            check(
+                r#"{"anyOf": [{"type": "string"}, {"type": "integer"}]}"#,
+                r#"{"type": ["string", "integer"]}"#
+            )
+            .is_compatible()
+        );
+        assert!(
+            check(
+                r#"{"allOf": [{"type": "string"}, {"type": "integer"}]}"#,
+                r#"{"type": "boolean"}"#
+            )
+            .is_compatible()
+        );
+    }
+
+    #[test]
+    fn compat_unknown() {
+        assert_eq!(
+            Compatibility::Unknown(vec![Difference {
+                pointer: "".to_string(),
+                keyword: "not".to_string(),
+                counterexample: None,
+            }]),
+            check(
+                r#"{"type": "string", "minLength": 1}"#,
+                r#"{"type": "string", "not": {"maxLength": 0}}"#
+            )
+        );
+        // a counterexample proves incompatibility.
+        assert_eq!(
+            vec![("", "pattern")],
+            keywords(&check(
+                r#"{"type": "string"}"#,
+                r#"{"type": "string", "pattern": "^a"}"#
+            ))
+        );
+    }
+}
@@ -0,0 +1,401 @@
+//! `Deserialize` of the enums which the specification distinguishes by a key.
+//!
+//! Unlike `#[serde(untagged)]`, these do not buffer the input nor try every
+//! variant, and an error is the one of the variant which the key selects.
+
+use super::{
+    Any, Example, ExampleValue, Examples, Extensions, License, LicenseId, LicenseUrl, MediaType,
+    ParameterContent, ParameterPattern, ParameterStyle, Reference, ReferenceOr, SchemaOrBool,
+    SchemaType, SchemaTypes,
+};
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{
+    DeserializeSeed, Error, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::{Deserialize, Deserializer};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+// ---------------------------------------------------------------------------
+
+// the owned or the borrowed `ReferenceOr`.
+pub(crate) trait ReferenceOrModel<'de>: Sized {
+    type Str: Deserialize<'de> + Deref<Target = str>;
+    type Value: Deserialize<'de>;
+
+    fn from_ref(
+        r#ref: Self::Str,
+        summary: Option<Self::Str>,
+        description: Option<Self::Str>,
+    ) -> Self;
+
+    fn from_value(value: Self::Value) -> Self;
+}
+
+impl<'de, T> ReferenceOrModel<'de> for ReferenceOr<T>
+where
+    T: Deserialize<'de>,
+{
+    type Str = String;
+    type Value = T;
+
+    fn from_ref(r#ref: String, summary: Option<String>, description: Option<String>) -> Self {
+        ReferenceOr::Ref(Reference {
+            r#ref,
+            summary,
+            description,
+        })
+    }
+
+    fn from_value(value: T) -> Self {
+        ReferenceOr::Value(value)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ReferenceOr<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ReferenceOrVisitor(PhantomData))
+    }
+}
+
+pub(crate) struct ReferenceOrVisitor<R>(pub(crate) PhantomData<R>);
+
+impl<'de, R> Visitor<'de> for ReferenceOrVisitor<R>
+where
+    R: ReferenceOrModel<'de>,
+{
+    type Value = R;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a reference or an object")
+    }
+
+    // `Ref` when the object has only the keys of `Reference`, and `Value`
+    // otherwise, which also reads `$ref` like `PathItem` does. The siblings of
+    // `$ref` are ignored when they are not a value.
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut tap = ReferenceTap::<A, R::Str> {
+            map,
+            r#ref: None,
+            summary: None,
+            description: None,
+            entries: VecDeque::new(),
+            key: None,
+            replay: None,
+            pending: false,
+            end: false,
+        };
+        tap.key = tap.read_reference()?;
+        if tap.key.is_none() {
+            if let Some(r#ref) = tap.r#ref {
+                return Ok(R::from_ref(r#ref, tap.summary, tap.description));
+            }
+        }
+
+        let value = R::Value::deserialize(MapAccessDeserializer::new(&mut tap));
+        if value.is_err() && tap.drain().is_err() {
+            return value.map(R::from_value);
+        }
+        match (value, tap.r#ref) {
+            (Err(_), Some(r#ref)) => Ok(R::from_ref(r#ref, tap.summary, tap.description)),
+            (value, _) => value.map(R::from_value),
+        }
+    }
+}
+
+fn is_reference_key(key: &str) -> bool {
+    matches!(key, "$ref" | "summary" | "description")
+}
+
+// passes the entries to the value, and keeps the ones of `Reference`.
+struct ReferenceTap<A, S> {
+    map: A,
+    r#ref: Option<S>,
+    summary: Option<S>,
+    description: Option<S>,
+    // the entries of `Reference` which were read before the value.
+    entries: VecDeque<(String, serde_json::Value)>,
+    // the first other key, whose value is still in `map`.
+    key: Option<String>,
+    // the value of `Reference` which the value reads next.
+    replay: Option<serde_json::Value>,
+    // whether the value has read a key but not its value.
+    pending: bool,
+    // whether `map` has no more entries.
+    end: bool,
+}
+
+impl<'de, A, S> ReferenceTap<A, S>
+where
+    A: MapAccess<'de>,
+    S: Deserialize<'de> + Deref<Target = str>,
+{
+    // reads and keeps the value of a key of `Reference`.
+    fn read(&mut self, key: &str) -> Result<serde_json::Value, A::Error> {
+        if key == "$ref" {
+            let r#ref = self.map.next_value::<S>()?;
+            let value = serde_json::Value::String(r#ref.to_string());
+            self.r#ref = Some(r#ref);
+            return Ok(value);
+        }
+
+        // `summary` and `description` of `Reference` are strings.
+        let value = self.map.next_value::<serde_json::Value>()?;
+        if let Ok(s) = S::deserialize(value.clone()) {
+            match key {
+                "summary" => self.summary = Some(s),
+                _ => self.description = Some(s),
+            }
+        }
+        Ok(value)
+    }
+
+    // the next key of `map`, which must not be read after its end.
+    fn next_key(&mut self) -> Result<Option<String>, A::Error> {
+        if self.end {
+            return Ok(None);
+        }
+        let key = self.map.next_key::<String>()?;
+        self.end = key.is_none();
+        Ok(key)
+    }
+
+    // reads the entries of `Reference` until the first other key.
+    fn read_reference(&mut self) -> Result<Option<String>, A::Error> {
+        while let Some(key) = self.next_key()? {
+            if !is_reference_key(&key) {
+                return Ok(Some(key));
+            }
+            let value = self.read(&key)?;
+            self.entries.push_back((key, value));
+        }
+        Ok(None)
+    }
+
+    // reads the entries which the value has left, after its error.
+    fn drain(&mut self) -> Result<(), A::Error> {
+        if self.pending || self.key.take().is_some() {
+            self.map.next_value::<IgnoredAny>()?;
+        }
+        while let Some(key) = self.next_key()? {
+            if is_reference_key(&key) {
+                self.read(&key)?;
+            } else {
+                self.map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de, A, S> MapAccess<'de> for ReferenceTap<A, S>
+where
+    A: MapAccess<'de>,
+    S: Deserialize<'de> + Deref<Target = str>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if let Some((key, value)) = self.entries.pop_front() {
+            self.replay = Some(value);
+            return seed.deserialize(key.into_deserializer()).map(Some);
+        }
+
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => match self.next_key()? {
+                Some(key) => key,
+                None => return Ok(None),
+            },
+        };
+        if is_reference_key(&key) {
+            self.replay = Some(self.read(&key)?);
+        } else {
+            self.pending = true;
+        }
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.replay.take() {
+            Some(value) => seed.deserialize(value).map_err(Error::custom),
+            None => {
+                self.pending = false;
+                self.map.next_value_seed(seed)
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct LicenseFields {
+    name: String,
+    url: Option<String>,
+    identifier: Option<String>,
+    #[serde(flatten)]
+    extensions: Extensions,
+}
+
+impl<'de> Deserialize<'de> for License {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = LicenseFields::deserialize(deserializer)?;
+        if fields.url.is_some() && fields.identifier.is_some() {
+            return Err(license_error());
+        }
+        Ok(match fields.url {
+            Some(url) => License::Url(LicenseUrl {
+                name: fields.name,
+                url,
+                extensions: fields.extensions,
+            }),
+            None => License::Id(LicenseId {
+                name: fields.name,
+                identifier: fields.identifier,
+                extensions: fields.extensions,
+            }),
+        })
+    }
+}
+
+// `url` and `identifier` are mutually exclusive.
+pub(crate) fn license_error<E: Error>() -> E {
+    E::custom("a license has both `url` and `identifier`")
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct ParameterPatternFields {
+    content: Option<HashMap<String, MediaType>>,
+    style: Option<String>,
+    explode: Option<bool>,
+    #[serde(rename = "allowReserved")]
+    allow_reserved: Option<bool>,
+    schema: Option<SchemaOrBool>,
+    examples: Option<HashMap<String, ReferenceOr<Example>>>,
+    example: Option<Any>,
+}
+
+impl<'de> Deserialize<'de> for ParameterPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = ParameterPatternFields::deserialize(deserializer)?;
+        Ok(match fields.content {
+            Some(content) => ParameterPattern::Content(ParameterContent { content }),
+            None => ParameterPattern::Style(ParameterStyle {
+                style: fields.style,
+                explode: fields.explode,
+                allow_reserved: fields.allow_reserved,
+                schema: fields.schema,
+                example: examples(fields.examples, fields.example),
+            }),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ExamplesFields {
+    examples: Option<HashMap<String, ReferenceOr<Example>>>,
+    example: Option<Any>,
+}
+
+impl<'de> Deserialize<'de> for Examples {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = ExamplesFields::deserialize(deserializer)?;
+        Ok(examples(fields.examples, fields.example))
+    }
+}
+
+fn examples(
+    examples: Option<HashMap<String, ReferenceOr<Example>>>,
+    example: Option<Any>,
+) -> Examples {
+    match examples {
+        Some(examples) => Examples::Values { examples },
+        None => Examples::Value { example },
+    }
+}
+
+#[derive(Deserialize)]
+struct ExampleValueFields {
+    #[serde(rename = "externalValue")]
+    external_value: Option<String>,
+    value: Option<Any>,
+}
+
+impl<'de> Deserialize<'de> for ExampleValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = ExampleValueFields::deserialize(deserializer)?;
+        Ok(match fields.external_value {
+            Some(external_value) => ExampleValue::Url { external_value },
+            None => ExampleValue::Literal {
+                value: fields.value,
+            },
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<'de> Deserialize<'de> for SchemaTypes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SchemaTypesVisitor)
+    }
+}
+
+struct SchemaTypesVisitor;
+
+impl<'de> Visitor<'de> for SchemaTypesVisitor {
+    type Value = SchemaTypes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a type or an array of types")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        SchemaType::deserialize(v.into_deserializer()).map(SchemaTypes::Unit)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Vec::deserialize(SeqAccessDeserializer::new(seq)).map(SchemaTypes::Array)
+    }
+}
@@ -0,0 +1,277 @@
+use super::Schema;
+use super::model::Any;
+use super::resolve::{Resolver, unescape};
+use std::fmt;
+
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiscriminatorError {
+    NoDiscriminator,
+    NotObject,
+    MissingProperty(String),
+    // the value of the property is not string.
+    InvalidValue(String),
+    UnknownValue(String),
+    NotFoundReference(String),
+}
+
+impl fmt::Display for DiscriminatorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiscriminatorError::NoDiscriminator => write!(f, "Schema has no discriminator."),
+            DiscriminatorError::NotObject => write!(f, "Value is not object."),
+            DiscriminatorError::MissingProperty(p) => {
+                write!(f, "Discriminator property `{p}` is missing.")
+            }
+            DiscriminatorError::InvalidValue(p) => {
+                write!(f, "Discriminator property `{p}` is not string.")
+            }
+            DiscriminatorError::UnknownValue(v) => {
+                write!(f, "Discriminator value `{v}` is not mapped to any schema.")
+            }
+            DiscriminatorError::NotFoundReference(r) => write!(f, "Not found reference `{r}`."),
+        }
+    }
+}
+
+impl std::error::Error for DiscriminatorError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selected<'a> {
+    pub value: String,
+    pub reference: String,
+    pub schema: &'a Schema,
+}
+
+impl Schema {
+    /// Select the concrete schema of `instance` by `discriminator`.
+    ///
+    /// The value of the property is looked up in `mapping`, then matched with
+    /// the component names of `oneOf`/`anyOf` references, then used as a
+    /// component name for `allOf` inheritance.
+    pub fn discriminate<'a, R: Resolver + ?Sized>(
+        &'a self,
+        resolver: &'a R,
+        instance: &Any,
+    ) -> Result<Selected<'a>, DiscriminatorError> {
+        let base = self
+            .with_discriminator(resolver)
+            .ok_or(DiscriminatorError::NoDiscriminator)?;
+        let discriminator = base.discriminator().unwrap();
+
+        let property = &discriminator.property_name;
+        let value = match instance {
+            Any::Object(props) => match props.get(property) {
+                Some(Any::String(v)) => v,
+                Some(_) => return Err(DiscriminatorError::InvalidValue(property.clone())),
+                None => return Err(DiscriminatorError::MissingProperty(property.clone())),
+            },
+            _ => return Err(DiscriminatorError::NotObject),
+        };
+
+        let reference = base
+            .discriminator_mapping()
+            .into_iter()
+            .find(|(v, _)| v == value)
+            .map(|(_, r)| r)
+            .or_else(|| {
+                let r = name_to_reference(value);
+                resolver.resolve_schema(None, &r).map(|_| r)
+            })
+            .ok_or_else(|| DiscriminatorError::UnknownValue(value.clone()))?;
+
+        let schema = resolver
+            .resolve_schema(None, &reference)
+            .ok_or_else(|| DiscriminatorError::NotFoundReference(reference.clone()))?;
+
+        Ok(Selected {
+            value: value.clone(),
+            reference,
+            schema,
+        })
+    }
+
+    /// Discriminator values and references, from `mapping` and the implicit
+    /// names of `oneOf`/`anyOf` references.
+    pub fn discriminator_mapping(&self) -> Vec<(String, String)> {
+        let mut mapping = vec![];
+        let discriminator = match self.discriminator() {
+            Some(d) => d,
+            None => return mapping,
+        };
+
+        let mut explicit = discriminator.mapping.iter().flatten().collect::<Vec<_>>();
+        explicit.sort();
+        for (value, target) in explicit {
+            mapping.push((value.clone(), name_to_reference(target)));
+        }
+
+        for s in self.one_of.iter().chain(&self.any_of).flatten() {
+            if let Some(r) = &s.schema().r#ref {
+                // explicit mapping takes precedence over implicit names.
+                if mapping.iter().any(|(_, m)| m == r) {
+                    continue;
+                }
+                let name = reference_name(r);
+                if !mapping.iter().any(|(v, _)| *v == name) {
+                    mapping.push((name, r.clone()));
+                }
+            }
+        }
+        mapping
+    }
+
+    /// The discriminator value which selects `reference`.
+    pub fn discriminator_value(&self, reference: &str) -> Option<String> {
+        self.discriminator()?;
+        self.discriminator_mapping()
+            .into_iter()
+            .find(|(_, r)| r == reference)
+            .map(|(v, _)| v)
+            .or_else(|| Some(reference_name(reference)))
+    }
+
+    // follow `$ref` to the schema which has `discriminator`.
+    fn with_discriminator<'a, R: Resolver + ?Sized>(
+        &'a self,
+        resolver: &'a R,
+    ) -> Option<&'a Schema> {
+        let mut schema = self;
+        for _ in 0..MAX_DEPTH {
+            if schema.discriminator().is_some() {
+                return Some(schema);
+            }
+            schema = resolver.resolve_schema(None, schema.r#ref.as_ref()?)?;
+        }
+        None
+    }
+}
+
+// mapping values are schema names or references.
+fn name_to_reference(value: &str) -> String {
+    let is_name = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_');
+    if is_name {
+        format!("#/components/schemas/{value}")
+    } else {
+        value.to_string()
+    }
+}
+
+fn reference_name(reference: &str) -> String {
+    let name = reference.rsplit(['/', '#']).next().unwrap_or(reference);
+    unescape(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenApi;
+
+    fn doc() -> OpenApi {
+        serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [
+                            {"$ref": "#/components/schemas/Cat"},
+                            {"$ref": "#/components/schemas/Dog"},
+                            {"$ref": "#/components/schemas/Lizard"}
+                        ],
+                        "discriminator": {
+                            "propertyName": "petType",
+                            "mapping": {"dog": "Dog", "lizard": "#/components/schemas/Lizard"}
+                        }
+                    },
+                    "Base": {
+                        "properties": {"kind": {"type": "string"}},
+                        "discriminator": {"propertyName": "kind"}
+                    },
+                    "Cat": {"properties": {"meow": {"type": "boolean"}}},
+                    "Dog": {"properties": {"bark": {"type": "boolean"}}},
+                    "Lizard": {"allOf": [{"$ref": "#/components/schemas/Base"}]}
+                }
+            }
+        }"##,
+        )
+        .unwrap()
+    }
+
+    fn any(s: &str) -> Any {
+        serde_json::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn discriminator_select() {
+        let doc = doc();
+        let pet = Schema {
+            r#ref: Some("#/components/schemas/Pet".to_string()),
+            ..Default::default()
+        };
+
+        let s = pet
+            .discriminate(&doc, &any(r#"{"petType": "Cat"}"#))
+            .unwrap();
+        assert_eq!("#/components/schemas/Cat", s.reference);
+        assert!(s.schema.properties.as_ref().unwrap().contains_key("meow"));
+
+        let s = pet
+            .discriminate(&doc, &any(r#"{"petType": "dog"}"#))
+            .unwrap();
+        assert_eq!("#/components/schemas/Dog", s.reference);
+
+        let s = pet
+            .discriminate(&doc, &any(r#"{"petType": "lizard"}"#))
+            .unwrap();
+        assert_eq!("#/components/schemas/Lizard", s.reference);
+
+        assert_eq!(
+            Err(DiscriminatorError::UnknownValue("Bird".to_string())),
+            pet.discriminate(&doc, &any(r#"{"petType": "Bird"}"#))
+        );
+        assert_eq!(
+            Err(DiscriminatorError::MissingProperty("petType".to_string())),
+            pet.discriminate(&doc, &any(r#"{}"#))
+        );
+        assert_eq!(
+            Err(DiscriminatorError::NoDiscriminator),
+            Schema::default().discriminate(&doc, &any(r#"{}"#))
+        );
+    }
+
+    #[test]
+    fn discriminator_inheritance() {
+        let doc = doc();
+        let base = &doc.components.as_ref().unwrap().schemas.as_ref().unwrap()["Base"];
+        let s = base
+            .discriminate(&doc, &any(r#"{"kind": "Lizard"}"#))
+            .unwrap();
+        assert_eq!("#/components/schemas/Lizard", s.reference);
+    }
+
+    #[test]
+    fn discriminator_values() {
+        let doc = doc();
+        let pet = &doc.components.as_ref().unwrap().schemas.as_ref().unwrap()["Pet"];
+        assert_eq!(
+            vec![
+                ("dog".to_string(), "#/components/schemas/Dog".to_string()),
+                (
+                    "lizard".to_string(),
+                    "#/components/schemas/Lizard".to_string()
+                ),
+                ("Cat".to_string(), "#/components/schemas/Cat".to_string()),
+            ],
+            pet.discriminator_mapping()
+        );
+        assert_eq!(
+            Some("dog".to_string()),
+            pet.discriminator_value("#/components/schemas/Dog")
+        );
+    }
+}
@@ -0,0 +1,1020 @@
+use super::model::Any;
+use super::resolve::component_name;
+use super::{
+    BooleanOr, Examples, MediaType, OAuthFlows, OpenApi, Operation, Parameter, ParameterIn,
+    ParameterPattern, ReferenceOr, Response, Schema, SchemaOrBool, SchemaType, SchemaTypes,
+    SecurityScheme, SecuritySchemeIn, Server,
+};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+/// Render an API reference of `doc`.
+///
+/// Operations are grouped by tags, and `$ref`s to schemas and security
+/// schemes are rendered as links to their sections.
+pub fn render(doc: &OpenApi, format: DocFormat) -> String {
+    let mut r = Renderer {
+        doc,
+        blocks: vec![],
+    };
+    r.render();
+    match format {
+        DocFormat::Markdown => markdown(&r.blocks),
+        DocFormat::Html => html(&doc.info.title, &r.blocks),
+    }
+}
+
+pub fn render_markdown(doc: &OpenApi) -> String {
+    render(doc, DocFormat::Markdown)
+}
+
+pub fn render_html(doc: &OpenApi) -> String {
+    render(doc, DocFormat::Html)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Inline {
+    Text(String),
+    Code(String),
+    Strong(String),
+    Link(String, String),
+}
+
+type Line = Vec<Inline>;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    Heading(usize, String, Option<String>),
+    Paragraph(Line),
+    List(Vec<Line>),
+    Table(Vec<&'static str>, Vec<Vec<Line>>),
+    Code(&'static str, String),
+}
+
+fn text(s: &str) -> Line {
+    vec![Inline::Text(s.to_string())]
+}
+
+fn code(s: &str) -> Line {
+    vec![Inline::Code(s.to_string())]
+}
+
+pub fn schema_anchor(name: &str) -> String {
+    format!("schema-{}", slug(name))
+}
+
+pub fn security_scheme_anchor(name: &str) -> String {
+    format!("security-{}", slug(name))
+}
+
+pub fn operation_anchor(path: &str, method: &str, op: &Operation) -> String {
+    match &op.operation_id {
+        Some(id) => format!("operation-{}", slug(id)),
+        None => format!("operation-{}-{}", method, slug(path)),
+    }
+}
+
+fn slug(s: &str) -> String {
+    let mut slug = String::new();
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+struct Renderer<'a> {
+    doc: &'a OpenApi,
+    blocks: Vec<Block>,
+}
+
+impl<'a> Renderer<'a> {
+    fn push(&mut self, block: Block) {
+        self.blocks.push(block);
+    }
+
+    fn heading(&mut self, level: usize, title: &str, anchor: Option<String>) {
+        self.push(Block::Heading(level, title.to_string(), anchor));
+    }
+
+    fn paragraph(&mut self, s: &Option<String>) {
+        if let Some(s) = s.as_ref().filter(|s| !s.trim().is_empty()) {
+            self.push(Block::Paragraph(text(s.trim())));
+        }
+    }
+
+    fn render(&mut self) {
+        let info = &self.doc.info;
+        self.heading(1, &format!("{} {}", info.title, info.version), None);
+        self.paragraph(&info.summary);
+        self.paragraph(&info.description);
+
+        if let Some(servers) = self.doc.servers.as_ref().filter(|s| !s.is_empty()) {
+            self.heading(2, "Servers", Some("servers".to_string()));
+            self.servers(servers);
+        }
+
+        self.operations();
+        self.security_schemes();
+        self.schemas();
+    }
+
+    fn servers(&mut self, servers: &[Server]) {
+        let mut rows = vec![];
+        for server in servers {
+            let mut variables = vec![];
+            let mut names = server.variables.iter().flatten().collect::<Vec<_>>();
+            names.sort_by_key(|(name, _)| *name);
+            for (name, var) in names {
+                if !variables.is_empty() {
+                    variables.push(Inline::Text(", ".to_string()));
+                }
+                variables.push(Inline::Code(name.clone()));
+                let mut s = format!(" = {}", var.default);
+                if let Some(values) = &var.r#enum {
+                    s.push_str(&format!(" ({})", values.join(" | ")));
+                }
+                variables.push(Inline::Text(s));
+            }
+            rows.push(vec![
+                code(&server.url),
+                text(server.description.as_deref().unwrap_or("")),
+                variables,
+            ]);
+        }
+        self.push(Block::Table(vec!["URL", "Description", "Variables"], rows));
+    }
+
+    fn operations(&mut self) {
+        let paths = match &self.doc.paths {
+            Some(paths) => paths,
+            None => return,
+        };
+
+        let mut paths = paths.values.iter().collect::<Vec<_>>();
+        paths.sort_by_key(|(path, _)| *path);
+
+        // declared tags first, then undeclared tags and untagged operations.
+        let mut groups: Vec<(String, Option<&Option<String>>, Vec<_>)> = self
+            .doc
+            .tags
+            .iter()
+            .flatten()
+            .map(|t| (t.name.clone(), Some(&t.description), vec![]))
+            .collect();
+        for (path, item) in paths {
+            for (method, op) in item.operations() {
+                let tags = match &op.tags {
+                    Some(tags) if !tags.is_empty() => tags.clone(),
+                    _ => vec!["default".to_string()],
+                };
+                for tag in tags {
+                    let group = match groups.iter().position(|(name, _, _)| *name == tag) {
+                        Some(i) => i,
+                        None => {
+                            groups.push((tag, None, vec![]));
+                            groups.len() - 1
+                        }
+                    };
+                    groups[group].2.push((path.as_str(), method, item, op));
+                }
+            }
+        }
+
+        for (name, description, ops) in groups {
+            if ops.is_empty() {
+                continue;
+            }
+            self.heading(2, &name, Some(format!("tag-{}", slug(&name))));
+            if let Some(description) = description {
+                self.paragraph(description);
+            }
+            for (path, method, item, op) in ops {
+                self.operation(path, method, &item.parameters, op);
+            }
+        }
+    }
+
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        common: &Option<Vec<ReferenceOr<Parameter>>>,
+        op: &Operation,
+    ) {
+        let title = format!("{} {}", method.to_uppercase(), path);
+        self.heading(3, &title, Some(operation_anchor(path, method, op)));
+        if op.deprecated == Some(true) {
+            self.push(Block::Paragraph(vec![Inline::Strong(
+                "Deprecated".to_string(),
+            )]));
+        }
+        self.paragraph(&op.summary);
+        self.paragraph(&op.description);
+
+        // operation level parameters override path level ones.
+        let mut parameters: Vec<&Parameter> = vec![];
+        for p in common
+            .iter()
+            .flatten()
+            .chain(op.parameters.iter().flatten())
+        {
+            if let Some(p) = self.doc.resolve(p) {
+                parameters.retain(|q| !(q.name == p.name && q.r#in == p.r#in));
+                parameters.push(p);
+            }
+        }
+        if !parameters.is_empty() {
+            self.heading(4, "Parameters", None);
+            let rows = parameters.iter().map(|p| self.parameter(p)).collect();
+            self.push(Block::Table(
+                vec!["Name", "In", "Type", "Required", "Description"],
+                rows,
+            ));
+        }
+
+        if let Some(body) = op.request_body.as_ref().and_then(|b| self.doc.resolve(b)) {
+            let title = if body.required == Some(true) {
+                "Request body (required)"
+            } else {
+                "Request body"
+            };
+            self.heading(4, title, None);
+            self.paragraph(&body.description);
+            self.content(&body.content);
+        }
+
+        if let Some(responses) = &op.responses {
+            self.heading(4, "Responses", None);
+            let mut statuses = responses.statuses.values.iter().collect::<Vec<_>>();
+            statuses.sort_by_key(|(status, _)| **status);
+            let mut list = statuses
+                .into_iter()
+                .filter_map(|(s, r)| Some((s.to_string(), self.doc.resolve(r)?)))
+                .collect::<Vec<(String, &Response)>>();
+            if let Some(r) = responses
+                .r#default
+                .as_ref()
+                .and_then(|r| self.doc.resolve(r))
+            {
+                list.push(("default".to_string(), r));
+            }
+
+            let rows = list
+                .iter()
+                .map(|(status, r)| {
+                    let mut types = r
+                        .content
+                        .iter()
+                        .flatten()
+                        .map(|(k, _)| k.as_str())
+                        .collect::<Vec<_>>();
+                    types.sort();
+                    vec![code(status), text(&r.description), text(&types.join(", "))]
+                })
+                .collect();
+            self.push(Block::Table(vec!["Status", "Description", "Content"], rows));
+
+            for (status, r) in list {
+                if let Some(content) = r.content.as_ref().filter(|c| !c.is_empty()) {
+                    self.heading(5, &format!("Response {status}"), None);
+                    self.content(content);
+                }
+            }
+        }
+
+        if let Some(security) = op.security.as_ref().or(self.doc.security.as_ref()) {
+            self.heading(4, "Security", None);
+            if security.is_empty() {
+                self.push(Block::Paragraph(text("None")));
+            } else {
+                let items = security.iter().map(|r| self.requirement(r)).collect();
+                self.push(Block::List(items));
+            }
+        }
+    }
+
+    fn parameter(&self, p: &Parameter) -> Vec<Line> {
+        let r#in = match p.r#in {
+            ParameterIn::Query => "query",
+            ParameterIn::Header => "header",
+            ParameterIn::Path => "path",
+            ParameterIn::Cookie => "cookie",
+            ParameterIn::Querystring => "querystring",
+        };
+        let ty = match &p.pattern {
+            ParameterPattern::Style(style) => style.schema.as_ref().map(|s| self.subtype_of(s)),
+            ParameterPattern::Content(content) => {
+                let mut types = content.content.keys().cloned().collect::<Vec<_>>();
+                types.sort();
+                Some(text(&types.join(", ")))
+            }
+        };
+        let required = p.required.unwrap_or(p.r#in == ParameterIn::Path);
+        let schema = match &p.pattern {
+            ParameterPattern::Style(style) => style.schema.as_ref().map(|s| s.schema()),
+            ParameterPattern::Content(_) => None,
+        };
+        let description = description(p.description.as_deref(), schema);
+        vec![
+            code(&p.name),
+            text(r#in),
+            ty.unwrap_or_default(),
+            text(if required { "yes" } else { "no" }),
+            description,
+        ]
+    }
+
+    fn requirement(&self, r: &HashMap<String, Vec<String>>) -> Line {
+        let mut names = r.iter().collect::<Vec<_>>();
+        names.sort_by_key(|(name, _)| *name);
+        let mut line = vec![];
+        for (name, scopes) in names {
+            if !line.is_empty() {
+                line.push(Inline::Text(" and ".to_string()));
+            }
+            line.push(Inline::Link(name.clone(), security_scheme_anchor(name)));
+            if !scopes.is_empty() {
+                line.push(Inline::Text(format!(" ({})", scopes.join(", "))));
+            }
+        }
+        line
+    }
+
+    fn content(&mut self, content: &HashMap<String, MediaType>) {
+        let mut types = content.iter().collect::<Vec<_>>();
+        types.sort_by_key(|(k, _)| *k);
+        for (media_type, media) in types {
+            let mut line = code(media_type);
+            if let Some(schema) = &media.schema {
+                line.push(Inline::Text(": ".to_string()));
+                line.extend(self.subtype_of(schema));
+            }
+            self.push(Block::Paragraph(line));
+
+            if let Some(BooleanOr::Value(schema)) = &media.schema {
+                if schema.r#ref.is_none() {
+                    self.properties(schema);
+                }
+            }
+            self.examples(&media.example);
+        }
+    }
+
+    fn examples(&mut self, examples: &Examples) {
+        match examples {
+            Examples::Value { example: Some(v) } => self.example(None, v),
+            Examples::Value { example: None } => {}
+            Examples::Values { examples } => {
+                let mut names = examples.iter().collect::<Vec<_>>();
+                names.sort_by_key(|(name, _)| *name);
+                for (name, example) in names {
+                    let example = match self.doc.resolve(example) {
+                        Some(e) => e,
+                        None => continue,
+                    };
+                    let title = example.summary.as_deref().unwrap_or(name);
+                    match &example.value {
+                        super::ExampleValue::Literal { value: Some(v) } => {
+                            self.example(Some(title), v)
+                        }
+                        super::ExampleValue::Literal { value: None } => {}
+                        super::ExampleValue::Url { external_value } => {
+                            self.push(Block::Paragraph(vec![
+                                Inline::Text(format!("Example {title}: ")),
+                                Inline::Code(external_value.clone()),
+                            ]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn example(&mut self, title: Option<&str>, value: &Any) {
+        let title = match title {
+            Some(t) => format!("Example {t}:"),
+            None => "Example:".to_string(),
+        };
+        self.push(Block::Paragraph(text(&title)));
+        let s = serde_json::to_string_pretty(value).unwrap_or_default();
+        self.push(Block::Code("json", s));
+    }
+
+    fn security_schemes(&mut self) {
+        let schemes = match self
+            .doc
+            .components
+            .as_ref()
+            .and_then(|c| c.security_schemes.as_ref())
+        {
+            Some(schemes) if !schemes.is_empty() => schemes,
+            _ => return,
+        };
+
+        self.heading(2, "Security schemes", Some("security-schemes".to_string()));
+        let mut names = schemes.iter().collect::<Vec<_>>();
+        names.sort_by_key(|(name, _)| *name);
+        for (name, scheme) in names {
+            let scheme = match self.doc.resolve(scheme) {
+                Some(s) => s,
+                None => continue,
+            };
+            self.heading(3, name, Some(security_scheme_anchor(name)));
+
+            let (description, items) = match scheme {
+                SecurityScheme::ApiKey(s) => {
+                    let r#in = match s.r#in {
+                        SecuritySchemeIn::Query => "query",
+                        SecuritySchemeIn::Header => "header",
+                        SecuritySchemeIn::Cookie => "cookie",
+                    };
+                    (
+                        &s.description,
+                        vec![
+                            text("Type: API key"),
+                            vec![
+                                Inline::Text(format!("In: {in} ")),
+                                Inline::Code(s.name.clone()),
+                            ],
+                        ],
+                    )
+                }
+                SecurityScheme::Http(s) => {
+                    let mut items =
+                        vec![text("Type: HTTP"), text(&format!("Scheme: {}", s.scheme))];
+                    if let Some(format) = &s.bearer_format {
+                        items.push(text(&format!("Bearer format: {format}")));
+                    }
+                    (&s.description, items)
+                }
+                SecurityScheme::Oauth2(s) => (&s.description, vec![text("Type: OAuth 2.0")]),
+                SecurityScheme::OpenIdConnect(s) => (
+                    &s.description,
+                    vec![
+                        text("Type: OpenID Connect"),
+                        vec![
+                            Inline::Text("URL: ".to_string()),
+                            Inline::Code(s.open_id_connect_url.clone()),
+                        ],
+                    ],
+                ),
+                SecurityScheme::MutualTls(s) => (&s.description, vec![text("Type: mutual TLS")]),
+            };
+            if let Some(Any::String(s)) = description {
+                self.paragraph(&Some(s.clone()));
+            }
+            self.push(Block::List(items));
+
+            if let SecurityScheme::Oauth2(s) = scheme {
+                self.flows(&s.flows);
+            }
+        }
+    }
+
+    fn flows(&mut self, flows: &OAuthFlows) {
+        let mut list = vec![];
+        if let Some(f) = &flows.implicit {
+            list.push(("Implicit", Some(&f.authrization_url), None, &f.scopes));
+        }
+        if let Some(f) = &flows.password {
+            list.push(("Password", None, Some(&f.token_url), &f.scopes));
+        }
+        if let Some(f) = &flows.client_credentials {
+            list.push(("Client credentials", None, Some(&f.token_url), &f.scopes));
+        }
+        if let Some(f) = &flows.authorization_code {
+            list.push((
+                "Authorization code",
+                Some(&f.authrization_url),
+                Some(&f.token_url),
+                &f.scopes,
+            ));
+        }
+
+        for (name, authorization_url, token_url, scopes) in list {
+            self.heading(4, &format!("{name} flow"), None);
+            let mut items = vec![];
+            if let Some(url) = authorization_url {
+                items.push(vec![
+                    Inline::Text("Authorization URL: ".to_string()),
+                    Inline::Code(url.clone()),
+                ]);
+            }
+            if let Some(url) = token_url {
+                items.push(vec![
+                    Inline::Text("Token URL: ".to_string()),
+                    Inline::Code(url.clone()),
+                ]);
+            }
+            if !items.is_empty() {
+                self.push(Block::List(items));
+            }
+
+            let scopes = scopes.iter().collect::<BTreeMap<_, _>>();
+            if !scopes.is_empty() {
+                let rows = scopes
+                    .into_iter()
+                    .map(|(k, v)| vec![code(k), text(v)])
+                    .collect();
+                self.push(Block::Table(vec!["Scope", "Description"], rows));
+            }
+        }
+    }
+
+    fn schemas(&mut self) {
+        let schemas = match self
+            .doc
+            .components
+            .as_ref()
+            .and_then(|c| c.schemas.as_ref())
+        {
+            Some(schemas) if !schemas.is_empty() => schemas,
+            _ => return,
+        };
+
+        self.heading(2, "Schemas", Some("schemas".to_string()));
+        let mut names = schemas.iter().collect::<Vec<_>>();
+        names.sort_by_key(|(name, _)| *name);
+        for (name, schema) in names {
+            self.heading(3, name, Some(schema_anchor(name)));
+            if schema.deprecated() == Some(true) {
+                self.push(Block::Paragraph(vec![Inline::Strong(
+                    "Deprecated".to_string(),
+                )]));
+            }
+            self.paragraph(&schema.description);
+
+            let mut line = text("Type: ");
+            line.extend(self.type_of(schema));
+            let constraints = constraints(schema);
+            if !constraints.is_empty() {
+                line.push(Inline::Text(format!(" ({})", constraints.join(", "))));
+            }
+            self.push(Block::Paragraph(line));
+
+            self.properties(schema);
+            if let Some(example) = schema.example() {
+                self.example(None, example);
+            }
+        }
+    }
+
+    fn properties(&mut self, schema: &Schema) {
+        let mut rows = vec![];
+        self.property_rows("", schema, &mut rows, 0);
+        if !rows.is_empty() {
+            self.push(Block::Table(
+                vec!["Name", "Type", "Required", "Description"],
+                rows,
+            ));
+        }
+    }
+
+    // flatten inline object properties to dotted names.
+    fn property_rows(
+        &self,
+        prefix: &str,
+        schema: &Schema,
+        rows: &mut Vec<Vec<Line>>,
+        depth: usize,
+    ) {
+        if depth > 4 {
+            return;
+        }
+        for s in schema.all_of.iter().flatten() {
+            if let BooleanOr::Value(s) = s.as_ref() {
+                if s.r#ref.is_none() {
+                    self.property_rows(prefix, s, rows, depth + 1);
+                }
+            }
+        }
+
+        let properties = match &schema.properties {
+            Some(properties) => properties,
+            None => return,
+        };
+        let required = schema.required.clone().unwrap_or_default();
+        let mut names = properties.iter().collect::<Vec<_>>();
+        names.sort_by_key(|(name, _)| *name);
+        for (name, property) in names {
+            let full = format!("{prefix}{name}");
+            rows.push(vec![
+                code(&full),
+                self.subtype_of(property),
+                text(if required.contains(name) { "yes" } else { "no" }),
+                description(
+                    property.schema().description.as_deref(),
+                    Some(property.schema()),
+                ),
+            ]);
+
+            if let BooleanOr::Value(property) = property.as_ref() {
+                if property.r#ref.is_none() {
+                    self.property_rows(&format!("{full}."), property, rows, depth + 1);
+                    if let Some(BooleanOr::Value(items)) = property.items.as_deref() {
+                        if items.r#ref.is_none() {
+                            self.property_rows(&format!("{full}[]."), items, rows, depth + 1);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn subtype_of(&self, schema: &SchemaOrBool) -> Line {
+        match schema {
+            BooleanOr::Value(s) => self.type_of(s),
+            BooleanOr::Boolean(true) => text("any"),
+            BooleanOr::Boolean(false) => text("never"),
+        }
+    }
+
+    fn type_of(&self, schema: &Schema) -> Line {
+        if let Some(r) = &schema.r#ref {
+            return match component_name(r, "schemas") {
+                Some(name) => {
+                    let anchor = schema_anchor(&name);
+                    vec![Inline::Link(name, anchor)]
+                }
+                None => code(r),
+            };
+        }
+
+        for (keyword, list) in [
+            ("allOf", &schema.all_of),
+            ("oneOf", &schema.one_of),
+            ("anyOf", &schema.any_of),
+        ] {
+            if let Some(list) = list {
+                let mut line = text(&format!("{keyword}("));
+                for (i, s) in list.iter().enumerate() {
+                    if i > 0 {
+                        line.push(Inline::Text(", ".to_string()));
+                    }
+                    line.extend(self.subtype_of(s));
+                }
+                line.push(Inline::Text(")".to_string()));
+                return line;
+            }
+        }
+
+        let types = match &schema.r#type {
+            Some(SchemaTypes::Unit(t)) => vec![t.clone()],
+            Some(SchemaTypes::Array(t)) => t.clone(),
+            None if schema.properties.is_some() => vec![SchemaType::Object],
+            None if schema.items.is_some() => vec![SchemaType::Array],
+            None => vec![],
+        };
+        if types.is_empty() {
+            return text("any");
+        }
+
+        let mut line = vec![];
+        for (i, t) in types.iter().enumerate() {
+            if i > 0 {
+                line.push(Inline::Text(" | ".to_string()));
+            }
+            match t {
+                SchemaType::Array => {
+                    line.push(Inline::Text("array of ".to_string()));
+                    match &schema.items {
+                        Some(items) => line.extend(self.subtype_of(items)),
+                        None => line.push(Inline::Text("any".to_string())),
+                    }
+                }
+                t => {
+                    let name = type_name(t);
+                    match &schema.format {
+                        Some(format) => line.push(Inline::Text(format!("{name} ({format})"))),
+                        None => line.push(Inline::Text(name.to_string())),
+                    }
+                }
+            }
+        }
+        if schema.nullable == Some(true) {
+            line.push(Inline::Text(" | null".to_string()));
+        }
+        line
+    }
+}
+
+// description followed by constraints of the schema.
+fn description(description: Option<&str>, schema: Option<&Schema>) -> Line {
+    let mut s = description.unwrap_or("").trim().to_string();
+    let constraints = schema.map(constraints).unwrap_or_default();
+    if !constraints.is_empty() {
+        if !s.is_empty() {
+            s.push(' ');
+        }
+        s.push_str(&format!("({})", constraints.join(", ")));
+    }
+    text(&s)
+}
+
+fn type_name(t: &SchemaType) -> &'static str {
+    match t {
+        SchemaType::Null => "null",
+        SchemaType::Boolean => "boolean",
+        SchemaType::Object => "object",
+        SchemaType::Array => "array",
+        SchemaType::Number => "number",
+        SchemaType::String => "string",
+        SchemaType::Integer => "integer",
+    }
+}
+
+fn constraints(schema: &Schema) -> Vec<String> {
+    let mut list = vec![];
+    let mut push = |name: &str, value: Option<String>| {
+        if let Some(v) = value {
+            list.push(format!("{name}: {v}"));
+        }
+    };
+    let json = |v: &Any| serde_json::to_string(v).unwrap_or_default();
+
+    push("minimum", schema.minimum().map(|v| v.to_string()));
+    push(
+        "exclusiveMinimum",
+        schema.exclusive_minimum().map(|v| v.to_string()),
+    );
+    push("maximum", schema.maximum().map(|v| v.to_string()));
+    push(
+        "exclusiveMaximum",
+        schema.exclusive_maximum().map(|v| v.to_string()),
+    );
+    push("multipleOf", schema.multiple_of().map(|v| v.to_string()));
+    push("minLength", schema.min_length().map(|v| v.to_string()));
+    push("maxLength", schema.max_length().map(|v| v.to_string()));
+    push("pattern", schema.pattern().map(str::to_string));
+    push("minItems", schema.min_items().map(|v| v.to_string()));
+    push("maxItems", schema.max_items().map(|v| v.to_string()));
+    push(
+        "minProperties",
+        schema.min_properties().map(|v| v.to_string()),
+    );
+    push(
+        "maxProperties",
+        schema.max_properties().map(|v| v.to_string()),
+    );
+    push(
+        "enum",
+        schema
+            .r#enum
+            .as_ref()
+            .map(|e| e.iter().map(json).collect::<Vec<_>>().join(" | ")),
+    );
+    push("const", schema.r#const().map(json));
+    push("default", schema.default.as_ref().map(json));
+
+    if schema.unique_items() == Some(true) {
+        list.push("unique".to_string());
+    }
+    if schema.read_only() == Some(true) {
+        list.push("read only".to_string());
+    }
+    if schema.write_only() == Some(true) {
+        list.push("write only".to_string());
+    }
+    if schema.deprecated() == Some(true) {
+        list.push("deprecated".to_string());
+    }
+    list
+}
+
+fn markdown(blocks: &[Block]) -> String {
+    fn inline(line: &Line, cell: bool) -> String {
+        let mut s = String::new();
+        for i in line {
+            match i {
+                Inline::Text(t) if cell => s.push_str(&t.replace('|', "\\|").replace('\n', "<br>")),
+                Inline::Text(t) => s.push_str(t),
+                Inline::Code(t) => {
+                    let t = if cell {
+                        t.replace('|', "\\|")
+                    } else {
+                        t.clone()
+                    };
+                    s.push_str(&format!("`{t}`"));
+                }
+                Inline::Strong(t) => s.push_str(&format!("**{t}**")),
+                Inline::Link(t, anchor) => s.push_str(&format!("[{t}](#{anchor})")),
+            }
+        }
+        s
+    }
+
+    let mut out = vec![];
+    for block in blocks {
+        let s = match block {
+            Block::Heading(level, title, anchor) => {
+                let heading = format!("{} {}", "#".repeat(*level), title);
+                match anchor {
+                    Some(anchor) => format!("<a id=\"{anchor}\"></a>\n\n{heading}"),
+                    None => heading,
+                }
+            }
+            Block::Paragraph(line) => inline(line, false),
+            Block::List(items) => items
+                .iter()
+                .map(|i| format!("- {}", inline(i, false)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Table(headers, rows) => {
+                let mut s = format!("| {} |\n", headers.join(" | "));
+                s.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                for row in rows {
+                    let cells = row.iter().map(|c| inline(c, true)).collect::<Vec<_>>();
+                    s.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                s.trim_end().to_string()
+            }
+            Block::Code(lang, code) => format!("```{lang}\n{code}\n```"),
+        };
+        out.push(s);
+    }
+    out.join("\n\n") + "\n"
+}
+
+fn html(title: &str, blocks: &[Block]) -> String {
+    fn inline(line: &Line) -> String {
+        let mut s = String::new();
+        for i in line {
+            match i {
+                Inline::Text(t) => s.push_str(&escape(t)),
+                Inline::Code(t) => s.push_str(&format!("<code>{}</code>", escape(t))),
+                Inline::Strong(t) => s.push_str(&format!("<strong>{}</strong>", escape(t))),
+                Inline::Link(t, anchor) => s.push_str(&format!(
+                    "<a href=\"#{}\">{}</a>",
+                    escape(anchor),
+                    escape(t)
+                )),
+            }
+        }
+        s
+    }
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(title)));
+    out.push_str(concat!(
+        "<style>\n",
+        "body { font-family: sans-serif; max-width: 960px; margin: auto; padding: 1em; }\n",
+        "table { border-collapse: collapse; }\n",
+        "th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }\n",
+        "pre { background: #f6f8fa; padding: 8px; overflow: auto; }\n",
+        "</style>\n",
+    ));
+    out.push_str("</head>\n<body>\n");
+    for block in blocks {
+        match block {
+            Block::Heading(level, title, anchor) => {
+                let id = match anchor {
+                    Some(anchor) => format!(" id=\"{}\"", escape(anchor)),
+                    None => "".to_string(),
+                };
+                out.push_str(&format!("<h{level}{id}>{}</h{level}>\n", escape(title)));
+            }
+            Block::Paragraph(line) => out.push_str(&format!("<p>{}</p>\n", inline(line))),
+            Block::List(items) => {
+                out.push_str("<ul>\n");
+                for i in items {
+                    out.push_str(&format!("<li>{}</li>\n", inline(i)));
+                }
+                out.push_str("</ul>\n");
+            }
+            Block::Table(headers, rows) => {
+                out.push_str("<table>\n<tr>");
+                for h in headers {
+                    out.push_str(&format!("<th>{}</th>", escape(h)));
+                }
+                out.push_str("</tr>\n");
+                for row in rows {
+                    out.push_str("<tr>");
+                    for c in row {
+                        out.push_str(&format!("<td>{}</td>", inline(c)));
+                    }
+                    out.push_str("</tr>\n");
+                }
+                out.push_str("</table>\n");
+            }
+            Block::Code(lang, code) => out.push_str(&format!(
+                "<pre><code class=\"language-{lang}\">{}</code></pre>\n",
+                escape(code)
+            )),
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> OpenApi {
+        serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "Pets", "version": "1.0"},
+            "servers": [{"url": "https://{host}/v1", "variables": {"host": {"default": "example.com"}}}],
+            "tags": [{"name": "pet"}],
+            "paths": {
+                "/pets/{id}": {
+                    "get": {
+                        "tags": ["pet"],
+                        "operationId": "getPet",
+                        "parameters": [
+                            {"name": "id", "in": "path", "schema": {"type": "integer", "minimum": 1}}
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "A pet.",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/Pet"},
+                                        "example": {"name": "cat"}
+                                    }
+                                }
+                            }
+                        },
+                        "security": [{"oauth": ["read"]}]
+                    }
+                },
+                "/health": {
+                    "get": {"responses": {"204": {"description": ""}}}
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"type": "string", "maxLength": 10, "description": "a | b"},
+                            "owner": {"type": "object", "properties": {"id": {"type": "string", "format": "uuid"}}},
+                            "tags": {"type": "array", "items": {"$ref": "#/components/schemas/Tag"}}
+                        }
+                    },
+                    "Tag": {"type": "string", "enum": ["a", "b"]}
+                },
+                "securitySchemes": {
+                    "oauth": {
+                        "type": "oauth2",
+                        "flows": {
+                            "clientCredentials": {"tokenUrl": "https://example.com/token", "scopes": {"read": "Read pets."}}
+                        }
+                    }
+                }
+            }
+        }"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn render_markdown_reference() {
+        let s = render_markdown(&doc());
+        assert!(s.starts_with("# Pets 1.0\n"));
+        assert!(s.contains("| `https://{host}/v1` |  | `host` = example.com |"));
+        assert!(s.contains("## pet\n\n<a id=\"operation-getpet\"></a>\n\n### GET /pets/{id}"));
+        assert!(s.contains("## default"));
+        assert!(s.contains("| `id` | path | integer | yes | (minimum: 1) |"));
+        assert!(s.contains("`application/json`: [Pet](#schema-pet)"));
+        assert!(s.contains("- [oauth](#security-oauth) (read)"));
+        assert!(s.contains("| `read` | Read pets. |"));
+        assert!(s.contains("| `name` | string | yes | a \\| b (maxLength: 10) |"));
+        assert!(s.contains("| `owner.id` | string (uuid) | no |  |"));
+        assert!(s.contains("| `tags` | array of [Tag](#schema-tag) | no |  |"));
+        assert!(s.contains("Type: string (enum: \"a\" | \"b\")"));
+        assert!(s.contains("```json\n{\n  \"name\": \"cat\"\n}\n```"));
+
+        // tagged operations are rendered before untagged ones.
+        assert!(s.find("## pet").unwrap() < s.find("## default").unwrap());
+    }
+
+    #[test]
+    fn render_html_reference() {
+        let s = render_html(&doc());
+        assert!(s.contains("<title>Pets</title>"));
+        assert!(s.contains("<h3 id=\"schema-pet\">Pet</h3>"));
+        assert!(s.contains("<a href=\"#schema-tag\">Tag</a>"));
+        assert!(s.contains("<td>a | b (maxLength: 10)</td>"));
+        assert!(s.contains("<h3 id=\"operation-get-health\">GET /health</h3>"));
+    }
+}
@@ -0,0 +1,286 @@
+use super::discriminator::DiscriminatorError;
+use super::form::FormError;
+use super::media_type::MediaTypeError;
+use super::merge::MergeError;
+use super::runtime::ExpressionError;
+use super::server::ServerError;
+use super::validate::ValidationError;
+use super::xml::XmlError;
+use std::fmt;
+
+type Source = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    Io,
+    // not JSON nor YAML, or not an OpenAPI document.
+    Parse,
+    // `$ref` which is not found.
+    Resolve,
+    // a value which does not match the schema.
+    Validate,
+    // a value which can not be encoded or decoded, like XML or form data.
+    Convert,
+}
+
+/// A position in the source text, 1-based.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An error with the location of the failure.
+///
+/// The underlying error is available by [`std::error::Error::source`].
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    uri: Option<String>,
+    pointer: Option<String>,
+    span: Option<Span>,
+    source: Option<Source>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Error {
+            kind,
+            message: message.into(),
+            uri: None,
+            pointer: None,
+            span: None,
+            source: None,
+        }
+    }
+
+    pub fn not_found_reference(reference: &str) -> Self {
+        Error::new(
+            ErrorKind::Resolve,
+            format!("Not found reference `{reference}`."),
+        )
+    }
+
+    /// The URI or path of the document.
+    pub fn with_uri(mut self, uri: impl Into<String>) -> Self {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// The JSON pointer in the document or the value.
+    pub fn with_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.pointer = Some(pointer.into());
+        self
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    pub fn with_source(mut self, source: impl Into<Source>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn uri(&self) -> Option<&str> {
+        self.uri.as_deref()
+    }
+
+    pub fn pointer(&self) -> Option<&str> {
+        self.pointer.as_deref()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // like `openapi.yaml:3:5: message (at /paths)`.
+        if let Some(uri) = &self.uri {
+            write!(f, "{uri}:")?;
+        }
+        if let Some(span) = &self.span {
+            write!(f, "{}:{}:", span.line, span.column)?;
+        }
+        if self.uri.is_some() || self.span.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(pointer) = &self.pointer {
+            let pointer = if pointer.is_empty() { "/" } else { pointer };
+            write!(f, " (at {pointer})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::new(ErrorKind::Io, "Failed to read document.").with_source(value)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        let mut e = Error::new(ErrorKind::Parse, "Invalid JSON document.");
+        if value.line() > 0 {
+            e = e.with_span(Span {
+                line: value.line(),
+                column: value.column(),
+            });
+        }
+        e.with_source(value)
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(value: serde_yaml::Error) -> Self {
+        let mut e = Error::new(ErrorKind::Parse, "Invalid YAML document.");
+        if let Some(location) = value.location() {
+            e = e.with_span(Span {
+                line: location.line(),
+                column: location.column(),
+            });
+        }
+        e.with_source(value)
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(value: ValidationError) -> Self {
+        Error::new(ErrorKind::Validate, value.message.clone())
+            .with_pointer(value.instance.clone())
+            .with_source(value)
+    }
+}
+
+impl From<MergeError> for Error {
+    fn from(value: MergeError) -> Self {
+        match &value {
+            MergeError::NotFoundReference(r) => Error::not_found_reference(r),
+            MergeError::Conflict { pointer, .. } => {
+                Error::new(ErrorKind::Convert, "Failed to merge `allOf`.").with_pointer(pointer)
+            }
+            MergeError::TooDeep => Error::new(ErrorKind::Convert, "Failed to merge `allOf`."),
+        }
+        .with_source(value)
+    }
+}
+
+impl From<XmlError> for Error {
+    fn from(value: XmlError) -> Self {
+        match &value {
+            XmlError::NotFoundReference(r) => Error::not_found_reference(r),
+            XmlError::InvalidValue { pointer, .. } => {
+                Error::new(ErrorKind::Convert, "Invalid XML value.").with_pointer(pointer)
+            }
+            _ => Error::new(ErrorKind::Convert, "Invalid XML value."),
+        }
+        .with_source(value)
+    }
+}
+
+impl From<FormError> for Error {
+    fn from(value: FormError) -> Self {
+        match &value {
+            FormError::NotFoundReference(r) => Error::not_found_reference(r),
+            _ => Error::new(ErrorKind::Convert, "Invalid form data."),
+        }
+        .with_source(value)
+    }
+}
+
+impl From<DiscriminatorError> for Error {
+    fn from(value: DiscriminatorError) -> Self {
+        match &value {
+            DiscriminatorError::NotFoundReference(r) => Error::not_found_reference(r),
+            _ => Error::new(
+                ErrorKind::Validate,
+                "Failed to select schema by discriminator.",
+            ),
+        }
+        .with_source(value)
+    }
+}
+
+impl From<MediaTypeError> for Error {
+    fn from(value: MediaTypeError) -> Self {
+        Error::new(ErrorKind::Parse, "Invalid media type.").with_source(value)
+    }
+}
+
+impl From<ServerError> for Error {
+    fn from(value: ServerError) -> Self {
+        Error::new(ErrorKind::Convert, "Invalid server URL.").with_source(value)
+    }
+}
+
+impl From<ExpressionError> for Error {
+    fn from(value: ExpressionError) -> Self {
+        Error::new(ErrorKind::Convert, "Invalid runtime expression.").with_source(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenApi;
+    use std::error::Error as _;
+
+    #[test]
+    fn error_parse() {
+        let e =
+            Error::from(serde_yaml::from_str::<OpenApi>("openapi: 3.1.0\ninfo: 1").unwrap_err())
+                .with_uri("openapi.yaml");
+        assert_eq!(ErrorKind::Parse, e.kind());
+        assert_eq!(Some(Span { line: 2, column: 7 }), e.span());
+        assert_eq!("openapi.yaml:2:7: Invalid YAML document.", e.to_string());
+        assert!(e.source().unwrap().is::<serde_yaml::Error>());
+
+        let e = Error::from(serde_json::from_str::<OpenApi>("{\n}").unwrap_err());
+        assert_eq!(Some(Span { line: 2, column: 1 }), e.span());
+    }
+
+    #[test]
+    fn error_chain() {
+        let e = Error::from(MergeError::NotFoundReference("#/a".to_string()));
+        assert_eq!(ErrorKind::Resolve, e.kind());
+        assert_eq!("Not found reference `#/a`.", e.to_string());
+
+        let e = Error::from(FormError::Merge(MergeError::Conflict {
+            pointer: "/allOf/1".to_string(),
+            keyword: "type".to_string(),
+        }))
+        .with_uri("a.yaml");
+        assert_eq!("a.yaml: Invalid form data.", e.to_string());
+        assert!(e.source().unwrap().is::<FormError>());
+
+        let e = Error::from(ValidationError {
+            instance: "/id".to_string(),
+            schema: "/type".to_string(),
+            message: "Expected integer.".to_string(),
+        });
+        assert_eq!(ErrorKind::Validate, e.kind());
+        assert_eq!("Expected integer. (at /id)", e.to_string());
+    }
+}
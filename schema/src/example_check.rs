@@ -0,0 +1,410 @@
+use super::model::Any;
+use super::resolve::{SchemaIndex, component_name};
+use super::validate::Validator;
+use super::visit::{Visitor, pointer, walk};
+use super::{
+    Example, ExampleValue, Examples, Header, MediaType, OpenApi, Parameter, ParameterPattern,
+    ReferenceOr, Schema,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExampleError {
+    // JSON pointer of the example value.
+    pub example: String,
+    // JSON pointer in the example value.
+    pub instance: String,
+    // JSON pointer of the failed schema keyword.
+    pub schema: String,
+    pub message: String,
+}
+
+/// Validate every example in `doc` against its schema.
+///
+/// `externalValue` is read relative to `base_dir`. Remote URLs are not read.
+pub fn check_examples(doc: &OpenApi, base_dir: Option<&Path>) -> Vec<ExampleError> {
+    let index = SchemaIndex::new(doc.components.as_ref());
+    let mut checker = ExampleChecker {
+        doc,
+        base_dir: base_dir.map(Path::to_path_buf),
+        validator: Validator::new(&index),
+        errors: vec![],
+    };
+    walk(doc, &mut checker);
+    checker.errors.sort_by(|a, b| {
+        (&a.example, &a.instance, &a.schema).cmp(&(&b.example, &b.instance, &b.schema))
+    });
+    checker.errors.dedup();
+    checker.errors
+}
+
+struct ExampleChecker<'a, 'i> {
+    doc: &'a OpenApi,
+    base_dir: Option<PathBuf>,
+    validator: Validator<'i, SchemaIndex<'a>>,
+    errors: Vec<ExampleError>,
+}
+
+impl<'a> ExampleChecker<'a, '_> {
+    fn check(&mut self, schema: &Schema, schema_ptr: &str, example_ptr: &str, value: &Any) {
+        for e in self.validator.validate_at(schema, schema_ptr, value) {
+            self.errors.push(ExampleError {
+                example: example_ptr.to_string(),
+                instance: e.instance,
+                schema: e.schema,
+                message: e.message,
+            });
+        }
+    }
+
+    fn check_examples(&mut self, schema: &Schema, schema_ptr: &str, ptr: &str, ex: &Examples) {
+        match ex {
+            Examples::Value { example: Some(v) } => {
+                self.check(schema, schema_ptr, &pointer(ptr, "example"), v)
+            }
+            Examples::Value { example: None } => {}
+            Examples::Values { examples } => {
+                let ptr = pointer(ptr, "examples");
+                for (name, example) in examples {
+                    let example_ptr = pointer(&ptr, name);
+                    let (example_ptr, example) = match self.resolve(&example_ptr, example) {
+                        Ok(Some(v)) => v,
+                        Ok(None) => continue,
+                        Err(message) => {
+                            self.errors.push(ExampleError {
+                                example: example_ptr,
+                                instance: "".to_string(),
+                                schema: schema_ptr.to_string(),
+                                message,
+                            });
+                            continue;
+                        }
+                    };
+                    match self.example_value(&example_ptr, example) {
+                        Ok(Some(v)) => self.check(schema, schema_ptr, &example_ptr, &v),
+                        Ok(None) => {}
+                        Err(message) => self.errors.push(ExampleError {
+                            example: example_ptr,
+                            instance: "".to_string(),
+                            schema: schema_ptr.to_string(),
+                            message,
+                        }),
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_pattern(&mut self, ptr: &str, pattern: &ParameterPattern) {
+        if let ParameterPattern::Style(style) = pattern {
+            if let Some(schema) = &style.schema {
+                self.check_examples(
+                    schema.schema(),
+                    &pointer(ptr, "schema"),
+                    ptr,
+                    &style.example,
+                );
+            }
+        }
+    }
+
+    // resolve the `Example` object and return the pointer of it, or `None`
+    // if a `$ref` is not found. A cycle of `$ref` is an error.
+    fn resolve<'b>(
+        &self,
+        ptr: &str,
+        example: &'b ReferenceOr<Example>,
+    ) -> Result<Option<(String, &'b Example)>, String>
+    where
+        'a: 'b,
+    {
+        let examples = self
+            .doc
+            .components
+            .as_ref()
+            .and_then(|c| c.examples.as_ref());
+        let mut example_ptr = ptr.to_string();
+        let mut example = example;
+        for _ in 0..MAX_DEPTH {
+            match example {
+                ReferenceOr::Value(v) => return Ok(Some((example_ptr, v))),
+                ReferenceOr::Ref(r) => {
+                    let name = match component_name(&r.r#ref, "examples") {
+                        Some(name) => name,
+                        None => return Ok(None),
+                    };
+                    example = match examples.and_then(|e| e.get(&name)) {
+                        Some(example) => example,
+                        None => return Ok(None),
+                    };
+                    example_ptr = pointer(&pointer("/components", "examples"), &name);
+                }
+            }
+        }
+        Err(format!("Too deep or cyclic `$ref` of the example at {ptr}"))
+    }
+
+    fn example_value(&self, ptr: &str, example: &Example) -> Result<Option<Any>, String> {
+        match &example.value {
+            ExampleValue::Literal { value } => Ok(value.clone()),
+            ExampleValue::Url { external_value } => {
+                read_external_value(self.base_dir.as_deref(), external_value)
+                    .map_err(|e| format!("Failed to read `{external_value}` at {ptr}: {e}"))
+            }
+        }
+    }
+}
+
+impl Visitor for ExampleChecker<'_, '_> {
+    fn visit_schema(&mut self, ptr: &str, value: &Schema) {
+        if let Some(example) = value.example() {
+            self.check(value, ptr, &pointer(ptr, "example"), example);
+        }
+
+        if let Some(examples) = value.examples() {
+            let examples_ptr = pointer(ptr, "examples");
+            for (i, example) in examples.iter().enumerate() {
+                self.check(value, ptr, &pointer(&examples_ptr, &i.to_string()), example);
+            }
+        }
+
+        if let Some(default) = &value.default {
+            self.check(value, ptr, &pointer(ptr, "default"), default);
+        }
+    }
+
+    fn visit_media_type(&mut self, ptr: &str, _media_type: &str, value: &MediaType) {
+        // examples of streaming media types are items of the stream.
+        let (name, schema) = match (&value.item_schema, &value.schema) {
+            (Some(schema), _) => ("itemSchema", schema),
+            (None, Some(schema)) => ("schema", schema),
+            (None, None) => return,
+        };
+        self.check_examples(schema.schema(), &pointer(ptr, name), ptr, &value.example);
+    }
+
+    fn visit_parameter(&mut self, ptr: &str, value: &Parameter) {
+        self.check_pattern(ptr, &value.pattern);
+    }
+
+    fn visit_header(&mut self, ptr: &str, _name: &str, value: &Header) {
+        self.check_pattern(ptr, &value.pattern);
+    }
+}
+
+fn read_external_value(base_dir: Option<&Path>, url: &str) -> Result<Option<Any>, String> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return Ok(None);
+    }
+
+    let path = PathBuf::from(url.strip_prefix("file://").unwrap_or(url));
+    let path = match base_dir {
+        Some(base) if path.is_relative() => base.join(path),
+        _ => path,
+    };
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let value = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => serde_json::from_str::<Any>(&content).map_err(|e| e.to_string())?,
+        Some("yml") | Some("yaml") => {
+            serde_yaml::from_str::<Any>(&content).map_err(|e| e.to_string())?
+        }
+        _ => Any::String(content),
+    };
+    Ok(Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(s: &str) -> OpenApi {
+        serde_json::from_str::<OpenApi>(s).unwrap()
+    }
+
+    fn pointers(errors: &[ExampleError]) -> Vec<(&str, &str, &str)> {
+        errors
+            .iter()
+            .map(|e| (e.example.as_str(), e.instance.as_str(), e.schema.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn check_media_type() {
+        let v = doc(r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "paths": {
+                "/a": {
+                    "get": {
+                        "parameters": [
+                            {"name": "b", "in": "query", "schema": {"type": "integer"}, "example": "c"}
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/A"},
+                                        "examples": {
+                                            "ok": {"value": {"id": 1}},
+                                            "ng": {"$ref": "#/components/examples/NG"}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "A": {
+                        "type": "object",
+                        "required": ["id"],
+                        "properties": {"id": {"type": "integer", "default": "0"}}
+                    }
+                },
+                "examples": {
+                    "NG": {"value": {"id": "1"}}
+                }
+            }
+        }"##);
+        let r = check_examples(&v, None);
+        assert_eq!(
+            vec![
+                (
+                    "/components/examples/NG",
+                    "/id",
+                    "/components/schemas/A/properties/id/type"
+                ),
+                (
+                    "/components/schemas/A/properties/id/default",
+                    "",
+                    "/components/schemas/A/properties/id/type"
+                ),
+                (
+                    "/paths/~1a/get/parameters/0/example",
+                    "",
+                    "/paths/~1a/get/parameters/0/schema/type"
+                ),
+            ],
+            pointers(&r)
+        );
+    }
+
+    #[test]
+    fn check_item_schema() {
+        let v = doc(r##"{
+            "openapi": "3.2.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "responses": {
+                    "A": {
+                        "description": "",
+                        "content": {
+                            "text/event-stream": {
+                                "itemSchema": {"type": "object", "required": ["data"]},
+                                "examples": {
+                                    "ok": {"value": {"data": "a"}},
+                                    "ng": {"value": {"event": "a"}}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }"##);
+        let r = check_examples(&v, None);
+        let ptr = "/components/responses/A/content/text~1event-stream";
+        let ng = format!("{ptr}/examples/ng");
+        let required = format!("{ptr}/itemSchema/required");
+        assert_eq!(vec![(ng.as_str(), "", required.as_str())], pointers(&r));
+    }
+
+    #[test]
+    fn check_cyclic_ref() {
+        let v = doc(r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "parameters": {
+                    "A": {
+                        "name": "a",
+                        "in": "query",
+                        "schema": {"type": "integer"},
+                        "examples": {
+                            "self": {"$ref": "#/components/examples/A"},
+                            "cycle": {"$ref": "#/components/examples/B"}
+                        }
+                    }
+                },
+                "examples": {
+                    "A": {"$ref": "#/components/examples/A"},
+                    "B": {"$ref": "#/components/examples/C"},
+                    "C": {"$ref": "#/components/examples/B"}
+                }
+            }
+        }"##);
+        let r = check_examples(&v, None);
+        let ptr = "/components/parameters/A";
+        let cycle = format!("{ptr}/examples/cycle");
+        let own = format!("{ptr}/examples/self");
+        let schema = format!("{ptr}/schema");
+        assert_eq!(
+            vec![
+                (cycle.as_str(), "", schema.as_str()),
+                (own.as_str(), "", schema.as_str())
+            ],
+            pointers(&r)
+        );
+        assert_eq!(
+            format!("Too deep or cyclic `$ref` of the example at {cycle}"),
+            r[0].message
+        );
+    }
+
+    #[test]
+    fn check_external_value() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.json"), "{\"id\":\"1\"}").unwrap();
+
+        let v = doc(r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "requestBodies": {
+                    "A": {
+                        "content": {
+                            "application/json": {
+                                "schema": {"properties": {"id": {"type": "integer"}}},
+                                "examples": {
+                                    "file": {"externalValue": "a.json"},
+                                    "missing": {"externalValue": "b.json"},
+                                    "remote": {"externalValue": "https://example.com/c.json"}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }"##);
+        let r = check_examples(&v, Some(dir.path()));
+
+        let ptr = "/components/requestBodies/A/content/application~1json";
+        let file = format!("{ptr}/examples/file");
+        let missing = format!("{ptr}/examples/missing");
+        let schema = format!("{ptr}/schema");
+        let id = format!("{ptr}/schema/properties/id/type");
+        assert_eq!(
+            vec![
+                (file.as_str(), "/id", id.as_str()),
+                (missing.as_str(), "", schema.as_str())
+            ],
+            pointers(&r)
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use super::model::Any;
+use super::validate::Validator;
+use super::visit::{Visitor, pointer, walk};
+use super::{OpenApi, Schema, SchemaType, SchemaTypes};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// https://spec.openapis.org/oas/v3.1.0#specification-extensions
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtensionError {
+    // JSON pointer of the extension, like `/info/x-logo`.
+    pub extension: String,
+    // JSON pointer in the extension value.
+    pub instance: String,
+    pub message: String,
+}
+
+/// Known specification extensions and the schemas of their values.
+///
+/// Names are stored without `x-`, like [`super::model::Extensions`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExtensionRegistry {
+    schemas: HashMap<String, Schema>,
+}
+
+impl ExtensionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extensions commonly used by code generators and Redfish.
+    pub fn well_known() -> Self {
+        let strings = Schema {
+            r#type: Some(SchemaTypes::Unit(SchemaType::Array)),
+            items: Some(Arc::new(typed(SchemaType::String).into())),
+            ..Default::default()
+        };
+        Self::new()
+            .with_extension("x-enumNames", strings.clone())
+            .with_extension("x-enum-varnames", strings.clone())
+            .with_extension("x-enum-descriptions", strings)
+            .with_extension("x-nullable", typed(SchemaType::Boolean))
+            .with_extension("x-longDescription", typed(SchemaType::String))
+    }
+
+    /// Declare the extension `name`, with or without `x-`, whose value must match `schema`.
+    ///
+    /// `$ref` in `schema` is resolved in the checked document.
+    pub fn with_extension(mut self, name: &str, schema: Schema) -> Self {
+        let name = name.strip_prefix("x-").unwrap_or(name);
+        self.schemas.insert(name.to_string(), schema);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Schema> {
+        self.schemas.get(name.strip_prefix("x-").unwrap_or(name))
+    }
+
+    /// Validate the value of every known extension in `doc`.
+    ///
+    /// Unknown extensions are ignored.
+    pub fn check(&self, doc: &OpenApi) -> Vec<ExtensionError> {
+        let mut checker = ExtensionChecker {
+            registry: self,
+            validator: Validator::new(doc),
+            errors: vec![],
+        };
+        walk(doc, &mut checker);
+        checker
+            .errors
+            .sort_by(|a, b| (&a.extension, &a.instance).cmp(&(&b.extension, &b.instance)));
+        checker.errors.dedup();
+        checker.errors
+    }
+}
+
+fn typed(t: SchemaType) -> Schema {
+    Schema {
+        r#type: Some(SchemaTypes::Unit(t)),
+        ..Default::default()
+    }
+}
+
+struct ExtensionChecker<'a> {
+    registry: &'a ExtensionRegistry,
+    validator: Validator<'a, OpenApi>,
+    errors: Vec<ExtensionError>,
+}
+
+impl Visitor for ExtensionChecker<'_> {
+    fn visit_extensions(&mut self, ptr: &str, values: &HashMap<String, Any>) {
+        for (name, value) in values {
+            let schema = match self.registry.get(name) {
+                Some(s) => s,
+                None => continue,
+            };
+            let extension = pointer(ptr, &format!("x-{name}"));
+            for e in self.validator.validate(schema, value) {
+                self.errors.push(ExtensionError {
+                    extension: extension.clone(),
+                    instance: e.instance,
+                    message: e.message,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Extensions;
+
+    #[test]
+    fn extension_typed_access() {
+        let mut ext = serde_json::from_str::<Extensions>(
+            r#"{"x-rust-type": "u64", "x-limits": [1, 2], "x-nullable": "yes"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some("u64".to_string()),
+            ext.get_as::<String>("rust-type").unwrap()
+        );
+        assert_eq!(Some(vec![1, 2]), ext.get_as::<Vec<u8>>("x-limits").unwrap());
+        assert_eq!(None, ext.get_as::<bool>("missing").unwrap());
+        assert!(ext.get_as::<bool>("nullable").is_err());
+
+        ext.set_from("x-nullable", &true).unwrap();
+        assert_eq!(Some(&Any::Boolean(true)), ext.values.get("nullable"));
+
+        let mut schema = Schema::default();
+        schema
+            .extensions
+            .set_from("enumNames", &["A", "B"])
+            .unwrap();
+        assert_eq!(
+            r#"{"x-enumNames":["A","B"]}"#,
+            serde_json::to_string(&schema).unwrap()
+        );
+    }
+
+    #[test]
+    fn extension_check() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r#"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": "", "x-longDescription": 1},
+            "components": {
+                "schemas": {
+                    "Color": {
+                        "enum": [0, 1],
+                        "x-enumNames": ["Red", 2],
+                        "x-nullable": false,
+                        "x-unknown": null
+                    }
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+
+        let errors = ExtensionRegistry::well_known().check(&doc);
+        assert_eq!(
+            vec![
+                "/components/schemas/Color/x-enumNames",
+                "/info/x-longDescription"
+            ],
+            errors
+                .iter()
+                .map(|e| e.extension.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("/1", errors[0].instance);
+
+        assert!(ExtensionRegistry::new().check(&doc).is_empty());
+        let registry = ExtensionRegistry::new().with_extension("unknown", typed(SchemaType::Null));
+        assert!(registry.check(&doc).is_empty());
+    }
+}
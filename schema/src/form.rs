@@ -0,0 +1,707 @@
+use super::media_type::{MediaRange, MediaTypeError, split_unquoted, unquote};
+use super::merge::MergeError;
+use super::model::Any;
+use super::resolve::Resolver;
+use super::server::{percent_decode, percent_encode};
+use super::{BooleanOr, Encoding, MediaType, Schema, SchemaType, SchemaTypes};
+use std::collections::HashMap;
+use std::fmt;
+
+// https://spec.openapis.org/oas/v3.1.0#encoding-object
+// https://datatracker.ietf.org/doc/html/rfc7578
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormError {
+    NotObject,
+    // a value which `style` can not serialize, like nested arrays.
+    Unsupported { name: String, style: String },
+    MissingBoundary,
+    InvalidMultipart(String),
+    InvalidJson { name: String, message: String },
+    NotFoundReference(String),
+    Merge(MergeError),
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormError::NotObject => write!(f, "Value is not object."),
+            FormError::Unsupported { name, style } => {
+                write!(
+                    f,
+                    "Property `{name}` can not be serialized in style `{style}`."
+                )
+            }
+            FormError::MissingBoundary => write!(f, "Boundary of multipart is missing."),
+            FormError::InvalidMultipart(m) => write!(f, "Invalid multipart: {m}."),
+            FormError::InvalidJson { name, message } => {
+                write!(f, "Invalid JSON of property `{name}`: {message}")
+            }
+            FormError::NotFoundReference(r) => write!(f, "Not found reference `{r}`."),
+            FormError::Merge(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FormError {}
+
+impl From<MergeError> for FormError {
+    fn from(e: MergeError) -> Self {
+        match e {
+            MergeError::NotFoundReference(r) => FormError::NotFoundReference(r),
+            e => FormError::Merge(e),
+        }
+    }
+}
+
+impl From<MediaTypeError> for FormError {
+    fn from(e: MediaTypeError) -> Self {
+        FormError::InvalidMultipart(e.to_string())
+    }
+}
+
+/// Converts between form bodies and objects, following `schema` and `encoding`
+/// of media types.
+///
+/// Properties are written in the order of their names.
+pub struct FormCodec<'a, R: Resolver + ?Sized> {
+    resolver: &'a R,
+}
+
+impl<'a, R: Resolver + ?Sized> FormCodec<'a, R> {
+    pub fn new(resolver: &'a R) -> Self {
+        FormCodec { resolver }
+    }
+
+    /// Serialize `value` to `application/x-www-form-urlencoded`.
+    pub fn encode_urlencoded(&self, media: &MediaType, value: &Any) -> Result<String, FormError> {
+        let values = object(value)?;
+        let mut pairs = vec![];
+        for name in sorted(values) {
+            let v = &values[name];
+            if *v == Any::Null {
+                continue;
+            }
+            let encoding = encoding(media, name);
+            let reserved = encoding.and_then(|e| e.allow_reserved) == Some(true);
+            let encode = |s: &str| encode(s, reserved);
+
+            if content_type(encoding).is_some_and(|c| c.is_json()) {
+                let json = serde_json::to_string(v).unwrap_or_default();
+                pairs.push(format!("{}={}", encode(name), encode(&json)));
+                continue;
+            }
+
+            let (style, explode) = style(encoding);
+            let unsupported = || FormError::Unsupported {
+                name: name.clone(),
+                style: style.to_string(),
+            };
+            match v {
+                Any::Array(items) => {
+                    let items = items
+                        .iter()
+                        .map(|v| text(v).map(|t| encode(&t)).ok_or_else(unsupported))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let delimiter = match style {
+                        "form" if explode => {
+                            for item in items {
+                                pairs.push(format!("{}={item}", encode(name)));
+                            }
+                            continue;
+                        }
+                        "form" => ",",
+                        "spaceDelimited" => "%20",
+                        "pipeDelimited" => "|",
+                        _ => return Err(unsupported()),
+                    };
+                    pairs.push(format!("{}={}", encode(name), items.join(delimiter)));
+                }
+                Any::Object(props) => {
+                    let mut joined = vec![];
+                    for key in sorted(props) {
+                        let t = text(&props[key]).ok_or_else(unsupported)?;
+                        match style {
+                            "form" if explode => {
+                                pairs.push(format!("{}={}", encode(key), encode(&t)))
+                            }
+                            "form" => joined.extend([encode(key), encode(&t)]),
+                            "deepObject" => pairs.push(format!(
+                                "{}[{}]={}",
+                                encode(name),
+                                encode(key),
+                                encode(&t)
+                            )),
+                            _ => return Err(unsupported()),
+                        }
+                    }
+                    if style == "form" && !explode {
+                        pairs.push(format!("{}={}", encode(name), joined.join(",")));
+                    }
+                }
+                v => pairs.push(format!("{}={}", encode(name), encode(&text(v).unwrap()))),
+            }
+        }
+        Ok(pairs.join("&"))
+    }
+
+    /// Parse `application/x-www-form-urlencoded`, with the types of the properties.
+    pub fn decode_urlencoded(&self, media: &MediaType, body: &str) -> Result<Any, FormError> {
+        let pairs = body
+            .split('&')
+            .filter(|p| !p.is_empty())
+            .map(|p| match p.split_once('=') {
+                Some((k, v)) => (percent_decode(k), percent_decode(v)),
+                None => (percent_decode(p), String::new()),
+            })
+            .collect::<Vec<_>>();
+        let mut used = vec![false; pairs.len()];
+        let mut take = |name: &str| {
+            let mut values = vec![];
+            for (i, (k, v)) in pairs.iter().enumerate() {
+                if !used[i] && k == name {
+                    used[i] = true;
+                    values.push(v.clone());
+                }
+            }
+            values
+        };
+
+        let schema = self.schema(media)?;
+        let mut values = HashMap::new();
+        let mut properties = schema.properties.iter().flatten().collect::<Vec<_>>();
+        properties.sort_by_key(|(name, _)| *name);
+        for (name, property) in properties {
+            let p = self.normalize(property.schema())?;
+            let encoding = encoding(media, name);
+
+            if content_type(encoding).is_some_and(|c| c.is_json()) {
+                if let Some(v) = take(name).first() {
+                    values.insert(name.clone(), json(name, v.as_bytes())?);
+                }
+                continue;
+            }
+
+            let (style, explode) = style(encoding);
+            let value = match schema_type(&p) {
+                Some(SchemaType::Array) => {
+                    let items = self.items(&p)?;
+                    let found = take(name);
+                    let found = match style {
+                        "form" if explode => found,
+                        _ => {
+                            let delimiter = match style {
+                                "spaceDelimited" => " ",
+                                "pipeDelimited" => "|",
+                                _ => ",",
+                            };
+                            found
+                                .iter()
+                                .flat_map(|v| v.split(delimiter))
+                                .map(|v| v.to_string())
+                                .collect()
+                        }
+                    };
+                    if found.is_empty() {
+                        continue;
+                    }
+                    Any::Array(found.iter().map(|v| scalar(v, &items)).collect())
+                }
+                Some(SchemaType::Object) => {
+                    let mut props = HashMap::new();
+                    match style {
+                        "deepObject" => {
+                            let prefix = format!("{name}[");
+                            let keys = pairs
+                                .iter()
+                                .filter_map(|(k, _)| k.strip_prefix(&prefix)?.strip_suffix(']'))
+                                .collect::<Vec<_>>();
+                            for key in keys {
+                                if let Some(v) = take(&format!("{name}[{key}]")).pop() {
+                                    props.insert(
+                                        key.to_string(),
+                                        scalar(&v, &self.property(&p, key)?),
+                                    );
+                                }
+                            }
+                        }
+                        // only declared properties are known to belong to the object.
+                        "form" if explode => {
+                            for key in p.properties.iter().flat_map(|p| p.keys()) {
+                                if let Some(v) = take(key).pop() {
+                                    props.insert(key.clone(), scalar(&v, &self.property(&p, key)?));
+                                }
+                            }
+                        }
+                        _ => {
+                            let joined = take(name).join(",");
+                            let tokens = joined.split(',').collect::<Vec<_>>();
+                            for kv in tokens.chunks(2) {
+                                if let [k, v] = kv {
+                                    props.insert(k.to_string(), scalar(v, &self.property(&p, k)?));
+                                }
+                            }
+                        }
+                    }
+                    if props.is_empty() {
+                        continue;
+                    }
+                    Any::Object(props)
+                }
+                _ => match take(name).pop() {
+                    Some(v) => scalar(&v, &p),
+                    None => continue,
+                },
+            };
+            values.insert(name.clone(), value);
+        }
+
+        // undeclared pairs, with `additionalProperties`.
+        if schema.additional_properties.as_deref() != Some(&BooleanOr::Boolean(false)) {
+            for (i, (k, v)) in pairs.iter().enumerate() {
+                if used[i] {
+                    continue;
+                }
+                let v = scalar(v, &self.property(&schema, k)?);
+                add(&mut values, k, v);
+            }
+        }
+        Ok(Any::Object(values))
+    }
+
+    /// Serialize `value` to `multipart/form-data`, with a part for each item of arrays.
+    pub fn encode_multipart(
+        &self,
+        media: &MediaType,
+        value: &Any,
+        boundary: &str,
+    ) -> Result<Vec<u8>, FormError> {
+        let values = object(value)?;
+        let schema = self.schema(media)?;
+        let mut body = vec![];
+        for name in sorted(values) {
+            let v = &values[name];
+            if *v == Any::Null {
+                continue;
+            }
+            let p = self.property(&schema, name)?;
+            let encoding = encoding(media, name);
+            let explicit = content_type(encoding);
+
+            let parts = match v {
+                Any::Array(items) if !explicit.as_ref().is_some_and(|c| c.is_json()) => {
+                    let schema = self.items(&p)?;
+                    items.iter().map(|v| (v, schema.clone())).collect()
+                }
+                v => vec![(v, p.clone())],
+            };
+            for (v, schema) in parts {
+                let content_type = match &explicit {
+                    Some(c) => c.essence(),
+                    None => default_content_type(&schema, v).to_string(),
+                };
+                let data = match (v, &explicit) {
+                    (Any::String(s), _) if !MediaRange::parse(&content_type)?.is_json() => {
+                        s.as_bytes().to_vec()
+                    }
+                    (Any::Array(_) | Any::Object(_), _) | (_, Some(_)) => {
+                        serde_json::to_vec(v).unwrap_or_default()
+                    }
+                    (v, None) => text(v).unwrap_or_default().into_bytes(),
+                };
+                let name = name.replace('"', "%22");
+                body.extend(format!("--{boundary}\r\n").as_bytes());
+                body.extend(
+                    format!("Content-Disposition: form-data; name=\"{name}\"\r\n").as_bytes(),
+                );
+                body.extend(format!("Content-Type: {content_type}\r\n\r\n").as_bytes());
+                body.extend(data);
+                body.extend(b"\r\n");
+            }
+        }
+        body.extend(format!("--{boundary}--\r\n").as_bytes());
+        Ok(body)
+    }
+
+    /// Parse `multipart/form-data`, where `content_type` has the boundary.
+    pub fn decode_multipart(
+        &self,
+        media: &MediaType,
+        content_type: &str,
+        body: &[u8],
+    ) -> Result<Any, FormError> {
+        let boundary = MediaRange::parse(content_type)?
+            .parameter("boundary")
+            .ok_or(FormError::MissingBoundary)?
+            .to_string();
+        let schema = self.schema(media)?;
+        let mut values = HashMap::new();
+        for part in parts(body, &boundary)? {
+            let p = self.property(&schema, &part.name)?;
+            let array = schema_type(&p) == Some(SchemaType::Array);
+            let item = if array { self.items(&p)? } else { p };
+
+            let content_type =
+                MediaRange::parse(part.content_type.as_deref().unwrap_or("text/plain"))?;
+            let v = if content_type.is_json() {
+                json(&part.name, part.data)?
+            } else if content_type.essence() == "text/plain" {
+                scalar(&String::from_utf8_lossy(part.data), &item)
+            } else {
+                Any::String(String::from_utf8_lossy(part.data).to_string())
+            };
+
+            match values.get_mut(&part.name) {
+                None if array && !matches!(v, Any::Array(_)) => {
+                    values.insert(part.name, Any::Array(vec![v]));
+                }
+                _ => add(&mut values, &part.name, v),
+            }
+        }
+        Ok(Any::Object(values))
+    }
+
+    fn schema(&self, media: &MediaType) -> Result<Schema, FormError> {
+        match &media.schema {
+            Some(schema) => self.normalize(schema.schema()),
+            None => Ok(Schema::default()),
+        }
+    }
+
+    // the schema of the property `name`, with `additionalProperties` for undeclared ones.
+    fn property(&self, schema: &Schema, name: &str) -> Result<Schema, FormError> {
+        let property = match schema.properties.as_ref().and_then(|p| p.get(name)) {
+            Some(p) => p,
+            None => match schema.additional_properties.as_deref() {
+                Some(s) => s,
+                None => return Ok(Schema::default()),
+            },
+        };
+        self.normalize(property.schema())
+    }
+
+    fn items(&self, schema: &Schema) -> Result<Schema, FormError> {
+        match &schema.items {
+            Some(items) => self.normalize(items.schema()),
+            None => Ok(Schema::default()),
+        }
+    }
+
+    fn normalize(&self, schema: &Schema) -> Result<Schema, FormError> {
+        if schema.r#ref.is_none() && schema.all_of.is_none() {
+            return Ok(schema.clone());
+        }
+        let wrapped = Schema {
+            all_of: Some(vec![schema.clone().into()]),
+            ..Default::default()
+        };
+        Ok(wrapped.merge_all_of(self.resolver)?)
+    }
+}
+
+struct Part<'b> {
+    name: String,
+    content_type: Option<String>,
+    data: &'b [u8],
+}
+
+fn parts<'b>(body: &'b [u8], boundary: &str) -> Result<Vec<Part<'b>>, FormError> {
+    let invalid = |m: &str| FormError::InvalidMultipart(m.to_string());
+    let delimiter = format!("--{boundary}");
+    let delimiter = delimiter.as_bytes();
+
+    // the preamble before the first delimiter is ignored.
+    let mut pos = find(body, delimiter, 0).ok_or_else(|| invalid("missing boundary"))?;
+    let mut parts = vec![];
+    loop {
+        pos += delimiter.len();
+        if body[pos..].starts_with(b"--") {
+            return Ok(parts);
+        }
+        pos += find(&body[pos..], b"\r\n", 0).ok_or_else(|| invalid("missing line break"))? + 2;
+
+        let headers_end =
+            find(body, b"\r\n\r\n", pos).ok_or_else(|| invalid("missing end of headers"))?;
+        let headers = String::from_utf8_lossy(&body[pos..headers_end]).to_string();
+        let data_start = headers_end + 4;
+        let end = find(body, &[b"\r\n", delimiter].concat(), data_start - 2)
+            .ok_or_else(|| invalid("missing boundary"))?;
+
+        let mut name = None;
+        let mut content_type = None;
+        for line in headers.split("\r\n") {
+            let (key, value) = match line.split_once(':') {
+                Some(kv) => kv,
+                None => continue,
+            };
+            match key.trim().to_lowercase().as_str() {
+                "content-disposition" => name = disposition_name(value),
+                "content-type" => content_type = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+        let name = name.ok_or_else(|| invalid("part without name"))?;
+        parts.push(Part {
+            name,
+            content_type,
+            data: &body[data_start.min(end)..end],
+        });
+        pos = end + 2;
+    }
+}
+
+fn disposition_name(value: &str) -> Option<String> {
+    split_unquoted(value, ';')
+        .into_iter()
+        .skip(1)
+        .find_map(|p| {
+            let (k, v) = p.split_once('=')?;
+            if !k.trim().eq_ignore_ascii_case("name") {
+                return None;
+            }
+            let v = v.trim();
+            match v.strip_prefix('"') {
+                Some(quoted) => unquote(quoted),
+                None => Some(v.to_string()),
+            }
+        })
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i + from)
+}
+
+fn object(value: &Any) -> Result<&HashMap<String, Any>, FormError> {
+    match value {
+        Any::Object(values) => Ok(values),
+        _ => Err(FormError::NotObject),
+    }
+}
+
+fn sorted(values: &HashMap<String, Any>) -> Vec<&String> {
+    let mut keys = values.keys().collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+// repeated names are collected to an array.
+fn add(values: &mut HashMap<String, Any>, name: &str, v: Any) {
+    match values.get_mut(name) {
+        Some(Any::Array(array)) => array.push(v),
+        Some(other) => *other = Any::Array(vec![other.clone(), v]),
+        None => {
+            values.insert(name.to_string(), v);
+        }
+    }
+}
+
+fn encoding<'m>(media: &'m MediaType, name: &str) -> Option<&'m Encoding> {
+    media.encoding.as_ref()?.get(name)
+}
+
+// the first of the comma-separated `contentType`.
+fn content_type(encoding: Option<&Encoding>) -> Option<MediaRange> {
+    let content_type = encoding?.content_type.as_deref()?;
+    MediaRange::parse(content_type.split(',').next()?).ok()
+}
+
+// `form` is exploded by default.
+fn style(encoding: Option<&Encoding>) -> (&str, bool) {
+    let style = encoding.and_then(|e| e.style.as_deref()).unwrap_or("form");
+    let explode = encoding.and_then(|e| e.explode).unwrap_or(style == "form");
+    (style, explode)
+}
+
+fn default_content_type(schema: &Schema, value: &Any) -> &'static str {
+    let binary = matches!(schema.format.as_deref(), Some("binary" | "byte" | "base64"))
+        || schema.content_encoding().is_some()
+        || schema.content_media_type().is_some();
+    match value {
+        _ if binary => "application/octet-stream",
+        Any::Array(_) | Any::Object(_) => "application/json",
+        _ => "text/plain",
+    }
+}
+
+// `:/?[]@!$'()*,;` are kept with `allowReserved`, but not `&=+#` of the form syntax.
+fn encode(s: &str, allow_reserved: bool) -> String {
+    if !allow_reserved {
+        return percent_encode(s);
+    }
+    let mut encoded = String::new();
+    for c in s.chars() {
+        match c {
+            ':' | '/' | '?' | '[' | ']' | '@' | '!' | '$' | '\'' | '(' | ')' | '*' | ',' | ';' => {
+                encoded.push(c)
+            }
+            c => encoded.push_str(&percent_encode(&c.to_string())),
+        }
+    }
+    encoded
+}
+
+fn text(value: &Any) -> Option<String> {
+    match value {
+        Any::Boolean(b) => Some(b.to_string()),
+        Any::Integer(i) => Some(i.to_string()),
+        Any::Number(n) => Some(n.to_string()),
+        Any::String(s) => Some(s.clone()),
+        Any::Null => Some(String::new()),
+        Any::Array(_) | Any::Object(_) => None,
+    }
+}
+
+fn json(name: &str, data: &[u8]) -> Result<Any, FormError> {
+    serde_json::from_slice(data).map_err(|e| FormError::InvalidJson {
+        name: name.to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn schema_type(schema: &Schema) -> Option<SchemaType> {
+    match &schema.r#type {
+        Some(SchemaTypes::Unit(t)) => Some(t.clone()),
+        Some(SchemaTypes::Array(t)) => t.iter().find(|t| **t != SchemaType::Null).cloned(),
+        None => None,
+    }
+}
+
+// text of the type of `schema`, or the string as it is.
+fn scalar(s: &str, schema: &Schema) -> Any {
+    let string = || Any::String(s.to_string());
+    match schema_type(schema) {
+        Some(SchemaType::Integer) => s.parse().map(Any::Integer).unwrap_or_else(|_| string()),
+        Some(SchemaType::Number) => match s.parse() {
+            Ok(i) => Any::Integer(i),
+            Err(_) => s.parse().map(Any::Number).unwrap_or_else(|_| string()),
+        },
+        Some(SchemaType::Boolean) => s.parse().map(Any::Boolean).unwrap_or_else(|_| string()),
+        _ => string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn media(s: &str) -> MediaType {
+        serde_json::from_str(s).unwrap()
+    }
+
+    fn any(s: &str) -> Any {
+        serde_json::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn form_urlencoded() {
+        let m = media(
+            r#"{
+                "schema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "integer"},
+                        "tags": {"type": "array", "items": {"type": "string"}},
+                        "colors": {"type": "array", "items": {"type": "string"}},
+                        "point": {"type": "object", "properties": {"x": {"type": "number"}}},
+                        "meta": {"type": "object"},
+                        "q": {"type": "string"}
+                    }
+                },
+                "encoding": {
+                    "colors": {"style": "pipeDelimited", "explode": false},
+                    "point": {"style": "deepObject", "explode": true},
+                    "meta": {"contentType": "application/json"},
+                    "q": {"allowReserved": true}
+                }
+            }"#,
+        );
+        let value = any(
+            r#"{"id": 1, "tags": ["a b", "c"], "colors": ["red", "blue"], "point": {"x": 1.5},
+                "meta": {"k": [1]}, "q": "a/b&c", "extra": "e"}"#,
+        );
+        let codec = FormCodec::new(&());
+        let body = codec.encode_urlencoded(&m, &value).unwrap();
+        assert_eq!(
+            concat!(
+                "colors=red|blue&extra=e&id=1&meta=%7B%22k%22%3A%5B1%5D%7D&point[x]=1.5",
+                "&q=a/b%26c&tags=a%20b&tags=c"
+            ),
+            body
+        );
+        assert_eq!(value, codec.decode_urlencoded(&m, &body).unwrap());
+
+        assert_eq!(
+            any(r#"{"id": "x", "tags": ["a"], "extra": ["1", "2"]}"#),
+            codec
+                .decode_urlencoded(&m, "id=x&tags=a&extra=1&extra=2")
+                .unwrap()
+        );
+        assert_eq!(
+            Err(FormError::Unsupported {
+                name: "tags".to_string(),
+                style: "form".to_string()
+            }),
+            codec.encode_urlencoded(&m, &any(r#"{"tags": [[1]]}"#))
+        );
+    }
+
+    #[test]
+    fn form_multipart() {
+        let m = media(
+            r#"{
+                "schema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "integer"},
+                        "address": {"type": "object"},
+                        "file": {"type": "string", "contentMediaType": "image/png"},
+                        "names": {"type": "array", "items": {"type": "string"}}
+                    }
+                },
+                "encoding": {"file": {"contentType": "image/png, image/jpeg"}}
+            }"#,
+        );
+        let value =
+            any(r#"{"id": 1, "address": {"city": "a"}, "file": "PNG", "names": ["x", "y"]}"#);
+        let codec = FormCodec::new(&());
+        let body = codec.encode_multipart(&m, &value, "b0").unwrap();
+        assert_eq!(
+            concat!(
+                "--b0\r\nContent-Disposition: form-data; name=\"address\"\r\n",
+                "Content-Type: application/json\r\n\r\n{\"city\":\"a\"}\r\n",
+                "--b0\r\nContent-Disposition: form-data; name=\"file\"\r\n",
+                "Content-Type: image/png\r\n\r\nPNG\r\n",
+                "--b0\r\nContent-Disposition: form-data; name=\"id\"\r\n",
+                "Content-Type: text/plain\r\n\r\n1\r\n",
+                "--b0\r\nContent-Disposition: form-data; name=\"names\"\r\n",
+                "Content-Type: text/plain\r\n\r\nx\r\n",
+                "--b0\r\nContent-Disposition: form-data; name=\"names\"\r\n",
+                "Content-Type: text/plain\r\n\r\ny\r\n",
+                "--b0--\r\n"
+            ),
+            String::from_utf8_lossy(&body)
+        );
+        let content_type = "multipart/form-data; boundary=b0";
+        assert_eq!(
+            value,
+            codec.decode_multipart(&m, content_type, &body).unwrap()
+        );
+
+        let body =
+            "preamble\r\n--b0\r\ncontent-disposition: form-data; name=names\r\n\r\nz\r\n--b0--";
+        assert_eq!(
+            any(r#"{"names": ["z"]}"#),
+            codec
+                .decode_multipart(&m, content_type, body.as_bytes())
+                .unwrap()
+        );
+        assert_eq!(
+            Err(FormError::MissingBoundary),
+            codec.decode_multipart(&m, "multipart/form-data", body.as_bytes())
+        );
+    }
+}
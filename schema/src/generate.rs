@@ -0,0 +1,1093 @@
+use super::model::Any;
+use super::resolve::{Resolver, join, ref_base};
+use super::{Schema, SchemaOrBool, SchemaType, SchemaTypes};
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// Generates sample values from schemas.
+///
+/// By default the generator is deterministic and prefers values declared in
+/// the schema (`example`, `examples`, `default`, `const` and `enum`). With a
+/// seed, values are synthesized randomly from the constraints instead, so that
+/// each seed explores a different value. `example`, `examples` and `default`
+/// are skipped then, as they are single values, but `const` and `enum` are
+/// constraints and still apply.
+pub struct Generator<'a, R: Resolver + ?Sized> {
+    resolver: &'a R,
+    max_depth: usize,
+    rng: Option<Rng>,
+    // base URIs changed by `$id` and `$ref`, the innermost last.
+    bases: Vec<Option<String>>,
+}
+
+impl<'a, R: Resolver + ?Sized> Generator<'a, R> {
+    pub fn new(resolver: &'a R) -> Self {
+        Generator {
+            resolver,
+            max_depth: DEFAULT_MAX_DEPTH,
+            rng: None,
+            bases: vec![],
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Some(Rng::new(seed));
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn generate(&mut self, schema: &Schema) -> Any {
+        self.value(schema, 0)
+    }
+
+    fn value(&mut self, schema: &Schema, depth: usize) -> Any {
+        let id = schema.id().map(|id| join(self.base().unwrap_or(""), id));
+        if id.is_none() {
+            return self.keywords(schema, depth);
+        }
+        self.bases.push(id);
+        let v = self.keywords(schema, depth);
+        self.bases.pop();
+        v
+    }
+
+    fn base(&self) -> Option<&str> {
+        self.bases.last().and_then(Option::as_deref)
+    }
+
+    fn keywords(&mut self, schema: &Schema, depth: usize) -> Any {
+        if let Some(r) = &schema.r#ref {
+            let base = self.base();
+            return match self.resolver.resolve_schema(base, r) {
+                Some(s) if depth < self.max_depth => {
+                    self.bases.push(ref_base(base, r));
+                    let v = self.value(s, depth + 1);
+                    self.bases.pop();
+                    v
+                }
+                _ => Any::Null,
+            };
+        }
+
+        if let Some(v) = self.declared(schema) {
+            return v;
+        }
+
+        if let Some(all_of) = &schema.all_of {
+            let mut merged = Any::Null;
+            for s in all_of {
+                merged = merge(merged, self.subvalue(s, depth).unwrap_or(Any::Null));
+            }
+            return merge(merged, self.synthesize(schema, depth));
+        }
+
+        for branches in [&schema.one_of, &schema.any_of].into_iter().flatten() {
+            let branches = branches
+                .iter()
+                .filter(|s| !s.is_false())
+                .collect::<Vec<_>>();
+            if !branches.is_empty() {
+                let branch = branches[self.index(branches.len())].schema();
+                let v = self.value(branch, depth);
+                let v = merge(v, self.synthesize(schema, depth));
+                return discriminated(schema, branch, v);
+            }
+        }
+
+        self.synthesize(schema, depth)
+    }
+
+    // no value is generated from `false`.
+    fn subvalue(&mut self, schema: &SchemaOrBool, depth: usize) -> Option<Any> {
+        match schema.is_false() {
+            true => None,
+            false => Some(self.value(schema.schema(), depth)),
+        }
+    }
+
+    fn declared(&mut self, schema: &Schema) -> Option<Any> {
+        // a seeded generator explores the constraints, see `Generator`.
+        if self.rng.is_none() {
+            let declared = schema
+                .example()
+                .cloned()
+                .or_else(|| schema.examples().and_then(|e| e.first().cloned()))
+                .or_else(|| schema.default.clone());
+            if declared.is_some() {
+                return declared;
+            }
+        }
+
+        if let Some(v) = schema.r#const() {
+            return Some(v.clone());
+        }
+
+        match &schema.r#enum {
+            Some(values) if !values.is_empty() => {
+                let i = self.index(values.len());
+                Some(values[i].clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn synthesize(&mut self, schema: &Schema, depth: usize) -> Any {
+        let ty = match self.schema_type(schema) {
+            Some(ty) => ty,
+            None => return Any::Null,
+        };
+
+        match ty {
+            SchemaType::Null => Any::Null,
+            SchemaType::Boolean => {
+                Any::Boolean(self.rng.as_mut().is_none_or(|r| r.next() % 2 == 0))
+            }
+            SchemaType::Integer => Any::Integer(self.integer(schema)),
+            SchemaType::Number => Any::Number(self.number(schema)),
+            SchemaType::String => Any::String(self.string(schema)),
+            SchemaType::Array => self.array(schema, depth),
+            SchemaType::Object => self.object(schema, depth),
+        }
+    }
+
+    fn schema_type(&mut self, schema: &Schema) -> Option<SchemaType> {
+        match &schema.r#type {
+            Some(SchemaTypes::Unit(ty)) => return Some(ty.clone()),
+            Some(SchemaTypes::Array(types)) => {
+                let types = types
+                    .iter()
+                    .filter(|t| **t != SchemaType::Null)
+                    .collect::<Vec<&SchemaType>>();
+                if types.is_empty() {
+                    return Some(SchemaType::Null);
+                }
+                let i = self.index(types.len());
+                return Some(types[i].clone());
+            }
+            None => {}
+        }
+
+        if schema.properties.is_some()
+            || schema.additional_properties.is_some()
+            || schema.required.is_some()
+        {
+            Some(SchemaType::Object)
+        } else if schema.items.is_some() || schema.prefix_items().is_some() {
+            Some(SchemaType::Array)
+        } else if schema.format.is_some()
+            || schema.pattern().is_some()
+            || schema.min_length().is_some()
+            || schema.max_length().is_some()
+        {
+            Some(SchemaType::String)
+        } else if schema.minimum().is_some()
+            || schema.maximum().is_some()
+            || schema.exclusive_minimum().is_some()
+            || schema.exclusive_maximum().is_some()
+            || schema.multiple_of().is_some()
+        {
+            Some(SchemaType::Integer)
+        } else {
+            None
+        }
+    }
+
+    fn integer(&mut self, schema: &Schema) -> i32 {
+        let (lo, hi) = bounds(schema);
+        let step = schema.multiple_of().filter(|m| *m > 0).map(i64::from);
+
+        let value = match self.rng.as_mut() {
+            Some(rng) => {
+                let lo = lo.unwrap_or(hi.map_or(0, |h| h - 100));
+                let hi = hi.unwrap_or(lo + 100).max(lo);
+                lo + rng.below((hi - lo + 1) as u64) as i64
+            }
+            None => lo.unwrap_or(hi.map_or(0, |h| h.min(0))),
+        };
+
+        let value = match step {
+            Some(step) => {
+                let aligned = value.div_euclid(step) * step;
+                if lo.is_some_and(|lo| aligned < lo) {
+                    aligned + step
+                } else {
+                    aligned
+                }
+            }
+            None => value,
+        };
+
+        value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    fn number(&mut self, schema: &Schema) -> f32 {
+        let value = self.integer(schema) as f32;
+        let (lo, hi) = bounds(schema);
+        match self.rng.as_mut() {
+            Some(rng)
+                if schema.multiple_of().is_none() && hi.is_none_or(|h| (value as i64) < h) =>
+            {
+                let fraction = rng.below(100) as f32 / 100.0;
+                if lo.is_none_or(|l| (value as i64) >= l) {
+                    value + fraction
+                } else {
+                    value
+                }
+            }
+            _ => value,
+        }
+    }
+
+    fn string(&mut self, schema: &Schema) -> String {
+        if let Some(pattern) = schema.pattern() {
+            if let Ok(hir) = regex_syntax::parse(pattern) {
+                let mut s = String::new();
+                self.regex(&hir, &mut s);
+                return s;
+            }
+        }
+
+        if let Some(format) = &schema.format {
+            if let Some(s) = self.format(format) {
+                return s;
+            }
+        }
+
+        let min = schema.min_length().unwrap_or(0) as usize;
+        let max = schema
+            .max_length()
+            .map(|m| m as usize)
+            .unwrap_or(min.max(6) + 10);
+
+        match self.rng.as_mut() {
+            Some(rng) => {
+                let len = min + rng.below((max.max(min) - min + 1) as u64) as usize;
+                (0..len)
+                    .map(|_| (b'a' + rng.below(26) as u8) as char)
+                    .collect()
+            }
+            None => {
+                let mut s = "string".to_string();
+                while s.chars().count() < min {
+                    s.push('x');
+                }
+                s.chars().take(max).collect()
+            }
+        }
+    }
+
+    fn format(&mut self, format: &str) -> Option<String> {
+        let rng = match self.rng.as_mut() {
+            Some(rng) => rng,
+            None => return fixed_format(format).map(str::to_string),
+        };
+
+        let s = match format {
+            "date-time" => format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                2000 + rng.below(30),
+                1 + rng.below(12),
+                1 + rng.below(28),
+                rng.below(24),
+                rng.below(60),
+                rng.below(60)
+            ),
+            "date" => format!(
+                "{:04}-{:02}-{:02}",
+                2000 + rng.below(30),
+                1 + rng.below(12),
+                1 + rng.below(28)
+            ),
+            "time" => format!(
+                "{:02}:{:02}:{:02}Z",
+                rng.below(24),
+                rng.below(60),
+                rng.below(60)
+            ),
+            "email" | "idn-email" => format!("user{}@example.com", rng.below(10000)),
+            "hostname" | "idn-hostname" => format!("host{}.example.com", rng.below(10000)),
+            "ipv4" => format!(
+                "{}.{}.{}.{}",
+                rng.below(256),
+                rng.below(256),
+                rng.below(256),
+                rng.below(256)
+            ),
+            "ipv6" => (0..8)
+                .map(|_| format!("{:x}", rng.below(0x10000)))
+                .collect::<Vec<String>>()
+                .join(":"),
+            "uri" | "iri" | "url" => format!("https://example.com/{}", rng.below(10000)),
+            "uuid" => {
+                let hex = |n: usize, rng: &mut Rng| {
+                    (0..n)
+                        .map(|_| format!("{:x}", rng.below(16)))
+                        .collect::<String>()
+                };
+                format!(
+                    "{}-{}-4{}-{:x}{}-{}",
+                    hex(8, rng),
+                    hex(4, rng),
+                    hex(3, rng),
+                    8 + rng.below(4),
+                    hex(3, rng),
+                    hex(12, rng)
+                )
+            }
+            _ => return fixed_format(format).map(str::to_string),
+        };
+        Some(s)
+    }
+
+    fn regex(&mut self, hir: &Hir, out: &mut String) {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Look(_) => {}
+            HirKind::Literal(lit) => out.push_str(&String::from_utf8_lossy(&lit.0)),
+            HirKind::Class(Class::Unicode(class)) => {
+                let ranges = class.ranges();
+                if !ranges.is_empty() {
+                    let range = &ranges[self.index(ranges.len())];
+                    let width = range.end() as u32 - range.start() as u32 + 1;
+                    let offset = match self.rng.as_mut() {
+                        Some(rng) => rng.below(width as u64) as u32,
+                        None => 0,
+                    };
+                    out.push(
+                        char::from_u32(range.start() as u32 + offset).unwrap_or(range.start()),
+                    );
+                }
+            }
+            HirKind::Class(Class::Bytes(class)) => {
+                let ranges = class.ranges();
+                if !ranges.is_empty() {
+                    let range = &ranges[self.index(ranges.len())];
+                    out.push(range.start() as char);
+                }
+            }
+            HirKind::Repetition(rep) => {
+                let count = match self.rng.as_mut() {
+                    Some(rng) => {
+                        let max = rep.max.unwrap_or(rep.min + 3).min(rep.min + 8);
+                        rep.min + rng.below((max - rep.min + 1) as u64) as u32
+                    }
+                    None => rep.min.max(rep.max.map_or(1, |m| m.min(1))),
+                };
+                for _ in 0..count {
+                    self.regex(&rep.sub, out);
+                }
+            }
+            HirKind::Capture(cap) => self.regex(&cap.sub, out),
+            HirKind::Concat(hirs) => {
+                for h in hirs {
+                    self.regex(h, out);
+                }
+            }
+            HirKind::Alternation(hirs) => {
+                let i = self.index(hirs.len());
+                self.regex(&hirs[i], out);
+            }
+        }
+    }
+
+    fn array(&mut self, schema: &Schema, depth: usize) -> Any {
+        if depth >= self.max_depth {
+            return Any::Array(vec![]);
+        }
+
+        let min = schema.min_items().unwrap_or(0) as usize;
+        let max = schema
+            .max_items()
+            .map(|m| m as usize)
+            .unwrap_or(min.max(1) + 2);
+        let mut count = match self.rng.as_mut() {
+            Some(rng) => min + rng.below((max.max(min) - min + 1) as u64) as usize,
+            None => min.max(1).min(max),
+        };
+
+        let mut values = vec![];
+        if let Some(prefix) = schema.prefix_items() {
+            for s in prefix.iter().take(count) {
+                match self.subvalue(s, depth + 1) {
+                    Some(v) => values.push(v),
+                    None => break,
+                }
+            }
+            count = count.max(values.len());
+        }
+
+        let unique = schema.unique_items().unwrap_or(false);
+        while values.len() < count {
+            let value = match &schema.items {
+                Some(items) => match self.subvalue(items, depth + 1) {
+                    Some(v) => v,
+                    None => break,
+                },
+                None => Any::Null,
+            };
+
+            if unique && values.contains(&value) {
+                // retry with another seed to get a distinct value.
+                let seed = values.len() as u64 + self.rng.as_mut().map_or(0, |r| r.next());
+                let mut other = Generator {
+                    resolver: self.resolver,
+                    max_depth: self.max_depth,
+                    rng: Some(Rng::new(seed)),
+                    bases: self.bases.clone(),
+                };
+                let distinct = (0..16)
+                    .map(|_| match &schema.items {
+                        Some(items) => other.value(items.schema(), depth + 1),
+                        None => Any::Null,
+                    })
+                    .find(|v| !values.contains(v));
+                match distinct {
+                    Some(v) => values.push(v),
+                    None => break,
+                }
+            } else {
+                values.push(value);
+            }
+        }
+
+        Any::Array(values)
+    }
+
+    fn object(&mut self, schema: &Schema, depth: usize) -> Any {
+        if depth >= self.max_depth {
+            return Any::Object(HashMap::new());
+        }
+
+        let required = schema.required.clone().unwrap_or_default();
+        let empty = HashMap::new();
+        let properties = schema.properties.as_ref().unwrap_or(&empty);
+        let mut values = HashMap::new();
+
+        let mut names = properties.keys().collect::<Vec<&String>>();
+        names.sort();
+        let mut optional = vec![];
+        for name in names {
+            let include =
+                required.contains(name) || self.rng.as_mut().is_none_or(|r| r.next() % 2 == 0);
+            if !include {
+                optional.push(name);
+            } else if let Some(v) = self.subvalue(&properties[name], depth + 1) {
+                values.insert(name.clone(), v);
+            }
+        }
+
+        // required names without `properties` are additional ones.
+        for name in required {
+            if let Entry::Vacant(e) = values.entry(name) {
+                let v = match properties.contains_key(e.key()) {
+                    true => None,
+                    false => self.additional(schema, e.key(), depth),
+                };
+                e.insert(v.unwrap_or(Any::Null));
+            }
+        }
+
+        // `minProperties` is filled with the optional properties first, then
+        // names of `patternProperties`, then `propertyN` if additional ones
+        // are allowed.
+        let min = schema.min_properties().unwrap_or(0) as usize;
+        for name in optional {
+            if values.len() >= min {
+                break;
+            }
+            if let Some(v) = self.subvalue(&properties[name], depth + 1) {
+                values.insert(name.clone(), v);
+            }
+        }
+
+        let mut patterns = schema
+            .pattern_properties()
+            .into_iter()
+            .flatten()
+            .filter(|(_, s)| !s.is_false())
+            .map(|(p, _)| p)
+            .collect::<Vec<&String>>();
+        patterns.sort();
+        for pattern in patterns {
+            let hir = match regex_syntax::parse(pattern) {
+                Ok(hir) => hir,
+                Err(_) => continue,
+            };
+            // the same name is generated again without a seed.
+            for _ in 0..min {
+                if values.len() >= min {
+                    break;
+                }
+                let mut name = String::new();
+                self.regex(&hir, &mut name);
+                if values.contains_key(&name) || properties.contains_key(&name) {
+                    continue;
+                }
+                if let Some(v) = self.additional(schema, &name, depth) {
+                    values.insert(name, v);
+                }
+            }
+        }
+
+        if !schema
+            .additional_properties
+            .as_deref()
+            .is_some_and(SchemaOrBool::is_false)
+        {
+            let mut i = 0;
+            while values.len() < min && i < min + 100 {
+                let name = format!("property{i}");
+                i += 1;
+                if values.contains_key(&name) || properties.contains_key(&name) {
+                    continue;
+                }
+                if let Some(v) = self.additional(schema, &name, depth) {
+                    values.insert(name, v);
+                }
+            }
+        }
+
+        Any::Object(values)
+    }
+
+    // value of the property `name` not in `properties`, `None` if it is not
+    // allowed.
+    fn additional(&mut self, schema: &Schema, name: &str, depth: usize) -> Option<Any> {
+        let mut patterns = schema
+            .pattern_properties()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        patterns.sort_by(|a, b| a.0.cmp(b.0));
+        for (pattern, s) in patterns {
+            if Regex::new(pattern).is_ok_and(|r| r.is_match(name)) {
+                return self.subvalue(s, depth + 1);
+            }
+        }
+
+        match schema.additional_properties.as_deref() {
+            Some(s) => self.subvalue(s, depth + 1),
+            None => Some(Any::Null),
+        }
+    }
+
+    fn index(&mut self, len: usize) -> usize {
+        match self.rng.as_mut() {
+            Some(rng) => rng.below(len as u64) as usize,
+            None => 0,
+        }
+    }
+}
+
+fn fixed_format(format: &str) -> Option<&'static str> {
+    let s = match format {
+        "date-time" => "2024-01-01T00:00:00Z",
+        "date" => "2024-01-01",
+        "time" => "00:00:00Z",
+        "duration" => "P1D",
+        "email" | "idn-email" => "user@example.com",
+        "hostname" | "idn-hostname" => "example.com",
+        "ipv4" => "192.0.2.1",
+        "ipv6" => "2001:db8::1",
+        "uri" | "iri" | "url" => "https://example.com/",
+        "uri-reference" | "iri-reference" => "/example",
+        "uri-template" => "https://example.com/{id}",
+        "uuid" => "00000000-0000-4000-8000-000000000000",
+        "json-pointer" => "/example",
+        "relative-json-pointer" => "0/example",
+        "regex" => ".*",
+        "byte" => "ZXhhbXBsZQ==",
+        "binary" => "example",
+        "password" => "********",
+        _ => return None,
+    };
+    Some(s)
+}
+
+// inclusive lower and upper bounds.
+fn bounds(schema: &Schema) -> (Option<i64>, Option<i64>) {
+    let lo = match (schema.minimum(), schema.exclusive_minimum()) {
+        (Some(m), Some(e)) => Some((m as i64).max(e as i64 + 1)),
+        (Some(m), None) => Some(m as i64),
+        (None, Some(e)) => Some(e as i64 + 1),
+        (None, None) => None,
+    };
+    let hi = match (schema.maximum(), schema.exclusive_maximum()) {
+        (Some(m), Some(e)) => Some((m as i64).min(e as i64 - 1)),
+        (Some(m), None) => Some(m as i64),
+        (None, Some(e)) => Some(e as i64 - 1),
+        (None, None) => None,
+    };
+    (lo, hi)
+}
+
+// set the discriminator property to the value which selects `branch`.
+fn discriminated(schema: &Schema, branch: &Schema, value: Any) -> Any {
+    let property = schema.discriminator().map(|d| &d.property_name);
+    let selector = branch
+        .r#ref
+        .as_ref()
+        .and_then(|r| schema.discriminator_value(r));
+    match (value, property, selector) {
+        (Any::Object(mut props), Some(property), Some(selector)) => {
+            props.insert(property.clone(), Any::String(selector));
+            Any::Object(props)
+        }
+        (value, _, _) => value,
+    }
+}
+
+fn merge(base: Any, other: Any) -> Any {
+    match (base, other) {
+        (Any::Object(mut a), Any::Object(b)) => {
+            for (k, v) in b {
+                let merged = match a.remove(&k) {
+                    Some(old) => merge(old, v),
+                    None => v,
+                };
+                a.insert(k, merged);
+            }
+            Any::Object(a)
+        }
+        (Any::Null, other) => other,
+        (base, _) => base,
+    }
+}
+
+// SplitMix64
+#[derive(Clone, Debug)]
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 { 0 } else { self.next() % n }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenApi;
+    use crate::validate::Validator;
+    use proptest::prelude::*;
+
+    fn schema(s: &str) -> Schema {
+        serde_json::from_str::<Schema>(s).unwrap()
+    }
+
+    fn json(v: &Any) -> String {
+        serde_json::to_string(v).unwrap()
+    }
+
+    #[test]
+    fn generate_declared() {
+        let mut g = Generator::new(&());
+        assert_eq!(
+            Any::Integer(1),
+            g.generate(&schema(r#"{"example":1,"default":2}"#))
+        );
+        assert_eq!(
+            Any::Integer(2),
+            g.generate(&schema(r#"{"examples":[2],"default":3}"#))
+        );
+        assert_eq!(
+            Any::Integer(3),
+            g.generate(&schema(r#"{"default":3,"const":4}"#))
+        );
+        assert_eq!(
+            Any::Integer(4),
+            g.generate(&schema(r#"{"const":4,"enum":[5]}"#))
+        );
+        assert_eq!(Any::Integer(5), g.generate(&schema(r#"{"enum":[5,6]}"#)));
+    }
+
+    #[test]
+    fn generate_string() {
+        let mut g = Generator::new(&());
+        assert_eq!(
+            Any::String("string".to_string()),
+            g.generate(&schema(r#"{"type":"string"}"#))
+        );
+        assert_eq!(
+            Any::String("str".to_string()),
+            g.generate(&schema(r#"{"type":"string","maxLength":3}"#))
+        );
+        assert_eq!(
+            Any::String("stringxx".to_string()),
+            g.generate(&schema(r#"{"type":"string","minLength":8}"#))
+        );
+        assert_eq!(
+            Any::String("2024-01-01T00:00:00Z".to_string()),
+            g.generate(&schema(r#"{"type":"string","format":"date-time"}"#))
+        );
+        assert_eq!(
+            Any::String("AA-0".to_string()),
+            g.generate(&schema(r#"{"type":"string","pattern":"^[A-Z]{2}-\\d+$"}"#))
+        );
+    }
+
+    #[test]
+    fn generate_integer() {
+        let mut g = Generator::new(&());
+        assert_eq!(
+            Any::Integer(0),
+            g.generate(&schema(r#"{"type":"integer"}"#))
+        );
+        assert_eq!(
+            Any::Integer(6),
+            g.generate(&schema(
+                r#"{"type":"integer","exclusiveMinimum":3,"multipleOf":3}"#
+            ))
+        );
+        assert_eq!(
+            Any::Integer(-5),
+            g.generate(&schema(r#"{"type":"integer","maximum":-5}"#))
+        );
+    }
+
+    #[test]
+    fn generate_object() {
+        let mut g = Generator::new(&());
+        let v = g.generate(&schema(
+            r#"{
+                "type": "object",
+                "required": ["a"],
+                "properties": {
+                    "a": {"type": "boolean"},
+                    "b": {"type": "array", "items": {"type": "integer", "minimum": 1}}
+                }
+            }"#,
+        ));
+        let mut h = HashMap::new();
+        h.insert("a".to_string(), Any::Boolean(true));
+        h.insert("b".to_string(), Any::Array(vec![Any::Integer(1)]));
+        assert_eq!(Any::Object(h), v);
+    }
+
+    #[test]
+    fn generate_object_min_properties() {
+        let mut g = Generator::new(&());
+        let value =
+            |g: &mut Generator<()>, s: &str| serde_json::to_value(g.generate(&schema(s))).unwrap();
+
+        // optional properties first.
+        assert_eq!(
+            serde_json::json!({"a": 1}),
+            value(
+                &mut g,
+                r#"{"minProperties": 1, "properties": {"a": {"const": 1}}, "additionalProperties": false}"#
+            )
+        );
+        assert_eq!(
+            serde_json::json!({"a": 1, "x-a": true}),
+            value(
+                &mut g,
+                r#"{
+                    "minProperties": 3,
+                    "properties": {"a": {"const": 1}},
+                    "patternProperties": {"^x-a$": {"const": true}},
+                    "additionalProperties": false
+                }"#
+            )
+        );
+        assert_eq!(
+            serde_json::json!({"property0": "string", "property1": "string"}),
+            value(
+                &mut g,
+                r#"{"minProperties": 2, "additionalProperties": {"type": "string"}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn generate_object_required() {
+        let mut g = Generator::new(&());
+        let v = g.generate(&schema(
+            r#"{
+                "required": ["a", "x-b"],
+                "patternProperties": {"^x-": {"type": "boolean"}},
+                "additionalProperties": {"type": "integer", "minimum": 3}
+            }"#,
+        ));
+        assert_eq!(
+            serde_json::json!({"a": 3, "x-b": true}),
+            serde_json::to_value(v).unwrap()
+        );
+    }
+
+    #[test]
+    fn generate_composition() {
+        let mut g = Generator::new(&());
+        let v = g.generate(&schema(
+            r#"{
+                "allOf": [
+                    {"properties": {"a": {"const": 1}}},
+                    {"properties": {"b": {"const": 2}}}
+                ]
+            }"#,
+        ));
+        let mut h = HashMap::new();
+        h.insert("a".to_string(), Any::Integer(1));
+        h.insert("b".to_string(), Any::Integer(2));
+        assert_eq!(Any::Object(h), v);
+
+        let v = g.generate(&schema(r#"{"oneOf": [{"const": 1}, {"const": 2}]}"#));
+        assert_eq!(Any::Integer(1), v);
+    }
+
+    #[test]
+    fn generate_boolean_schema() {
+        let mut g = Generator::new(&());
+        let v = g.generate(&schema(
+            r#"{"type":"object","properties":{"a":false,"b":{"const":1}}}"#,
+        ));
+        assert_eq!(r#"{"b":1}"#, json(&v));
+
+        let v = g.generate(&schema(r#"{"type":"array","items":false,"minItems":2}"#));
+        assert_eq!(Any::Array(vec![]), v);
+
+        let v = g.generate(&schema(r#"{"oneOf": [false, {"const": 2}]}"#));
+        assert_eq!(Any::Integer(2), v);
+    }
+
+    #[test]
+    fn generate_recursion() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+                "openapi": "3.1.0",
+                "info": {"title": "", "version": ""},
+                "components": {
+                    "schemas": {
+                        "Node": {
+                            "type": "object",
+                            "properties": {
+                                "child": {"$ref": "#/components/schemas/Node"}
+                            }
+                        }
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+        let mut g = Generator::new(&doc).with_max_depth(3);
+        let v = g.generate(&schema(r##"{"$ref": "#/components/schemas/Node"}"##));
+        assert_eq!(r#"{"child":{}}"#, json(&v));
+    }
+
+    #[test]
+    fn generate_reference_under_id() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+                "openapi": "3.1.0",
+                "info": {"title": "", "version": ""},
+                "components": {
+                    "schemas": {
+                        "A": {
+                            "$id": "https://example.com/schemas/a",
+                            "type": "object",
+                            "properties": {"b": {"$ref": "#bee"}},
+                            "required": ["b"],
+                            "$defs": {"b": {"$anchor": "bee", "const": 1}}
+                        }
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+        let mut g = Generator::new(&doc);
+        let v = g.generate(&schema(r##"{"$ref": "#/components/schemas/A"}"##));
+        assert_eq!(r#"{"b":1}"#, json(&v));
+    }
+
+    #[test]
+    fn generate_seeded() {
+        let s = schema(
+            r#"{
+                "type": "object",
+                "required": ["id", "tags"],
+                "properties": {
+                    "id": {"type": "string", "format": "uuid"},
+                    "tags": {
+                        "type": "array",
+                        "minItems": 2,
+                        "uniqueItems": true,
+                        "items": {"type": "string", "pattern": "^[a-z]{3}$"}
+                    }
+                }
+            }"#,
+        );
+
+        let a = Generator::new(&()).with_seed(1).generate(&s);
+        let b = Generator::new(&()).with_seed(1).generate(&s);
+        assert_eq!(a, b);
+
+        for seed in 0..32 {
+            let v = Generator::new(&()).with_seed(seed).generate(&s);
+            let Any::Object(h) = v else { panic!() };
+            let Some(Any::String(id)) = h.get("id") else {
+                panic!()
+            };
+            assert_eq!(36, id.len());
+            let Some(Any::Array(tags)) = h.get("tags") else {
+                panic!()
+            };
+            assert!(tags.len() >= 2);
+            for tag in tags {
+                let Any::String(tag) = tag else { panic!() };
+                assert_eq!(3, tag.len());
+                assert!(tag.chars().all(|c| c.is_ascii_lowercase()));
+            }
+        }
+    }
+
+    #[test]
+    fn generate_discriminator() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+                "openapi": "3.1.0",
+                "info": {"title": "", "version": ""},
+                "components": {
+                    "schemas": {
+                        "Pet": {
+                            "oneOf": [
+                                {"$ref": "#/components/schemas/Cat"},
+                                {"$ref": "#/components/schemas/Dog"}
+                            ],
+                            "discriminator": {"propertyName": "type", "mapping": {"cat": "Cat"}}
+                        },
+                        "Cat": {"properties": {"type": {"type": "string"}}},
+                        "Dog": {"properties": {"type": {"type": "string"}}}
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+        let pet = schema(r##"{"$ref":"#/components/schemas/Pet"}"##);
+
+        let v = Generator::new(&doc).generate(&pet);
+        assert_eq!(r#"{"type":"cat"}"#, json(&v));
+
+        let v = Validator::new(&doc);
+        for seed in 0..8 {
+            let value = Generator::new(&doc).with_seed(seed).generate(&pet);
+            assert!(v.is_valid(&pet, &value), "{}", json(&value));
+        }
+    }
+
+    // schemas whose constraints are satisfied by some values.
+    fn satisfiable() -> impl Strategy<Value = serde_json::Value> {
+        use serde_json::json;
+
+        let leaf = prop_oneof![
+            Just(json!({"type": "null"})),
+            Just(json!({"type": "boolean"})),
+            (-50i32..50, 1u32..5, 0i32..20).prop_map(|(min, m, w)| {
+                json!({"type": "integer", "minimum": min, "maximum": min + m as i32 + w, "multipleOf": m})
+            }),
+            (-50i32..50, 0i32..20).prop_map(|(min, w)| {
+                json!({"type": "number", "minimum": min, "maximum": min + w})
+            }),
+            (0u32..8, 0u32..8).prop_map(|(min, w)| {
+                json!({"type": "string", "minLength": min, "maxLength": min + w})
+            }),
+            prop::sample::select(vec!["^[a-z]{2,4}$", "^\\d{3}-\\d{4}$", "^(ab|cd)+$"])
+                .prop_map(|p| json!({"type": "string", "pattern": p})),
+            prop::sample::select(vec!["date", "date-time", "email", "ipv4", "uuid"])
+                .prop_map(|f| json!({"type": "string", "format": f})),
+            Just(json!({"enum": [1, "a", null]})),
+            Just(json!({"const": {"a": [1]}})),
+        ];
+
+        leaf.prop_recursive(3, 24, 4, |inner| {
+            prop_oneof![
+                (inner.clone(), 0u32..3, 0u32..3).prop_map(|(items, min, w)| {
+                    json!({"type": "array", "items": items, "minItems": min, "maxItems": min + w})
+                }),
+                (
+                    proptest::collection::btree_map("[a-c]", inner.clone(), 0..3),
+                    proptest::collection::vec(any::<bool>(), 3),
+                    prop_oneof![
+                        Just(None),
+                        Just(Some(json!(false))),
+                        inner.clone().prop_map(Some)
+                    ],
+                    proptest::option::of(inner.clone()),
+                    0usize..5,
+                )
+                    .prop_map(
+                        |(properties, required, additional, pattern, min)| {
+                            let additional_false = additional == Some(json!(false));
+                            let mut names = properties
+                                .keys()
+                                .zip(&required)
+                                .filter(|(_, r)| **r)
+                                .map(|(k, _)| json!(k))
+                                .collect::<Vec<_>>();
+                            // a name only allowed by `additionalProperties`.
+                            if !additional_false {
+                                names.push(json!("r"));
+                            }
+                            // without a seed a pattern generates a single name.
+                            let min = match additional_false {
+                                true => min.min(properties.len() + pattern.is_some() as usize),
+                                false => min,
+                            };
+
+                            let mut s = json!({
+                                "type": "object",
+                                "properties": properties,
+                                "required": names,
+                                "minProperties": min
+                            });
+                            if let Some(a) = additional {
+                                s["additionalProperties"] = a;
+                            }
+                            if let Some(p) = pattern {
+                                s["patternProperties"] = json!({"^x-[a-z]{1,3}$": p});
+                            }
+                            s
+                        }
+                    ),
+                proptest::collection::vec(inner, 1..3).prop_map(|s| json!({"anyOf": s})),
+            ]
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(256))]
+
+        #[test]
+        fn generate_valid(s in satisfiable(), seed in any::<u64>()) {
+            let s = serde_json::from_value::<Schema>(s).unwrap();
+            let v = Validator::new(&());
+            for value in [
+                Generator::new(&()).generate(&s),
+                Generator::new(&()).with_seed(seed).generate(&s),
+            ] {
+                let errors = v.validate(&s, &value);
+                prop_assert!(errors.is_empty(), "{} {:?}", json(&value), errors);
+            }
+        }
+    }
+}
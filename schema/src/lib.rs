@@ -1,11 +1,46 @@
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
+pub mod borrowed;
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod canonical;
+pub mod compat;
+mod de;
+pub mod discriminator;
+pub mod docs;
+pub mod error;
+pub mod example_check;
+pub mod extension;
+pub mod form;
+pub mod generate;
+pub mod lint;
+pub mod load;
+pub mod media_type;
+pub mod merge;
+pub mod mock;
 pub mod model;
+pub mod resolve;
+pub mod runtime;
+pub mod server;
+pub mod strict;
+pub mod validate;
+pub mod version;
+pub mod visit;
+pub mod xml;
 
 // https://spec.openapis.org/oas/v3.0.3
 // https://spec.openapis.org/oas/v3.1.0
+// https://spec.openapis.org/oas/v3.2.0
+//
+// Fields of every version are read whatever `openapi` is, so a 3.0 or 3.1
+// document may have 3.2 fields. `version::newer_features` finds them.
+
+pub use self::error::Error;
 
 use self::model::{Any, Extensions, HttpStatuses, KeyValues};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
@@ -14,17 +49,61 @@ pub enum BooleanOr<T> {
     Boolean(bool),
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+// since v3.1.0
+// https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-4.3.2
+pub type SchemaOrBool = BooleanOr<Schema>;
+
+static ANY_SCHEMA: LazyLock<Schema> = LazyLock::new(Schema::default);
+
+static NO_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    let mut schema = Schema::default();
+    schema.applicators_mut().not = Some(Arc::new(SchemaOrBool::Boolean(true)));
+    schema
+});
+
+impl SchemaOrBool {
+    /// The schema, where `true` is `{}` and `false` is `{"not": true}`.
+    pub fn schema(&self) -> &Schema {
+        match self {
+            BooleanOr::Value(s) => s,
+            BooleanOr::Boolean(true) => &ANY_SCHEMA,
+            BooleanOr::Boolean(false) => &NO_SCHEMA,
+        }
+    }
+
+    pub fn is_false(&self) -> bool {
+        *self == BooleanOr::Boolean(false)
+    }
+}
+
+impl From<Schema> for SchemaOrBool {
+    fn from(value: Schema) -> Self {
+        BooleanOr::Value(value)
+    }
+}
+
+impl From<Schema> for Arc<SchemaOrBool> {
+    fn from(value: Schema) -> Self {
+        Arc::new(BooleanOr::Value(value))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum ReferenceOr<T> {
-    Value(T),
+    // objects with `$ref` are `Ref`, even if they also match `Value`.
     Ref(Reference),
+    Value(T),
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct OpenApi {
     pub openapi: String,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$self")]
+    pub self_uri: Option<String>,
+
     pub info: Info,
 
     // since v3.1.0
@@ -99,7 +178,7 @@ pub struct Contact {
     pub extensions: Extensions,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum License {
     Url(LicenseUrl),
@@ -135,6 +214,10 @@ pub struct Server {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<HashMap<String, ServerVariable>>,
 
@@ -160,7 +243,7 @@ pub struct ServerVariable {
 pub struct Components {
     // or reference until v3.1.0
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub schemas: Option<HashMap<String, Schema>>,
+    pub schemas: Option<HashMap<String, Arc<Schema>>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub responses: Option<HashMap<String, ReferenceOr<Response>>>,
@@ -231,6 +314,17 @@ pub struct PathItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trace: Option<Operation>,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<Operation>,
+
+    // since v3.2.0
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "additionalOperations"
+    )]
+    pub additional_operations: Option<HashMap<String, Operation>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub servers: Option<Vec<Server>>,
 
@@ -241,6 +335,25 @@ pub struct PathItem {
     pub extensions: Extensions,
 }
 
+impl PathItem {
+    pub fn operations(&self) -> Vec<(&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("put", &self.put),
+            ("post", &self.post),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+            ("trace", &self.trace),
+            ("query", &self.query),
+        ]
+        .into_iter()
+        .filter_map(|(method, op)| op.as_ref().map(|op| (method, op)))
+        .collect()
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -321,7 +434,7 @@ pub struct Parameter {
 }
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum ParameterPattern {
     Content(ParameterContent),
@@ -346,13 +459,13 @@ pub struct ParameterStyle {
     allow_reserved: Option<bool>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    schema: Option<Schema>,
+    schema: Option<SchemaOrBool>,
 
     #[serde(flatten)]
     example: Examples,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Examples {
     Values {
@@ -389,6 +502,10 @@ pub enum ParameterIn {
 
     #[serde(rename = "cookie")]
     Cookie,
+
+    // since v3.2.0
+    #[serde(rename = "querystring")]
+    Querystring,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -409,7 +526,11 @@ pub struct RequestBody {
 pub struct MediaType {
     // or reference until v3.1.0
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub schema: Option<Schema>,
+    pub schema: Option<SchemaOrBool>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none", rename = "itemSchema")]
+    pub item_schema: Option<SchemaOrBool>,
 
     #[serde(flatten)]
     example: Examples,
@@ -488,7 +609,7 @@ pub struct Example {
     pub extensions: Extensions,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum ExampleValue {
     Url {
@@ -568,12 +689,24 @@ pub struct Header {
 pub struct Tag {
     pub name: String,
 
+    // since v3.2.0
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub descrption: Option<String>,
+    pub summary: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
     pub external_docs: Option<ExternalDocumentation>,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+
     #[serde(flatten)]
     pub extensions: Extensions,
 }
@@ -592,89 +725,97 @@ pub struct Reference {
     pub description: Option<String>,
 }
 
+/// A JSON Schema.
+///
+/// Keywords which are rarely used are grouped into boxed substructures, like
+/// [`SchemaValidation`], which are `None` when all their keywords are missing.
+/// They are read by the accessors of the same names, like [`Schema::minimum`],
+/// and written by `*_mut`, like [`Schema::validation_mut`].
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Schema {
     // until v3.1.0
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nullable: Option<bool>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub discriminator: Option<Discriminator>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub xml: Option<Xml>,
-
-    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
-    pub external_docs: Option<ExternalDocumentation>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub example: Option<Any>,
-
     // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2.3.1
     #[serde(skip_serializing_if = "Option::is_none", rename = "$ref")]
     pub r#ref: Option<String>,
 
     // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.2.1
     #[serde(skip_serializing_if = "Option::is_none", rename = "allOf")]
-    pub all_of: Option<Vec<Schema>>,
+    pub all_of: Option<Vec<Arc<SchemaOrBool>>>,
 
     #[serde(skip_serializing_if = "Option::is_none", rename = "anyOf")]
-    pub any_of: Option<Vec<Schema>>,
+    pub any_of: Option<Vec<Arc<SchemaOrBool>>>,
 
     #[serde(skip_serializing_if = "Option::is_none", rename = "oneOf")]
-    pub one_of: Option<Vec<Schema>>,
+    pub one_of: Option<Vec<Arc<SchemaOrBool>>>,
 
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.1
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub not: Option<Box<Schema>>,
+    pub items: Option<Arc<SchemaOrBool>>,
 
-    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.2.2
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.2
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub r#if: Option<Box<Schema>>,
+    pub properties: Option<HashMap<String, Arc<SchemaOrBool>>>,
 
+    // bool support until v3.1.0
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "additionalProperties"
+    )]
+    pub additional_properties: Option<Arc<SchemaOrBool>>,
+
+    // unit only until v3.1.0
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.1
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub then: Option<Box<Schema>>,
+    pub r#type: Option<SchemaTypes>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub r#else: Option<Box<Schema>>,
+    pub r#enum: Option<Vec<Any>>,
 
-    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentSchemas")]
-    pub dependent_schemas: Option<Any>,
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.5
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
 
-    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.1
-    #[serde(skip_serializing_if = "Option::is_none", rename = "prefixItems")]
-    pub prefix_items: Option<Vec<Schema>>,
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-7
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
 
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-9
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub items: Option<Box<Schema>>,
+    pub title: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub contains: Option<Box<Schema>>,
+    pub description: Option<String>,
 
-    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.2
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub properties: Option<HashMap<String, Schema>>,
+    pub default: Option<Any>,
 
-    #[serde(skip_serializing_if = "Option::is_none", rename = "patternProperties")]
-    pub pattern_properties: Option<HashMap<String, Schema>>,
+    // groups take their keywords before `extensions`.
+    #[serde(flatten, deserialize_with = "group")]
+    pub validation: Option<Box<SchemaValidation>>,
 
-    // bool support until v3.1.0
-    #[serde(
-        skip_serializing_if = "Option::is_none",
-        rename = "additionalProperties"
-    )]
-    pub additional_properties: Option<Box<BooleanOr<Schema>>>,
+    #[serde(flatten, deserialize_with = "group")]
+    pub applicators: Option<Box<SchemaApplicators>>,
 
-    #[serde(skip_serializing_if = "Option::is_none", rename = "propertyNames")]
-    pub property_names: Option<Box<Schema>>,
+    #[serde(flatten, deserialize_with = "group")]
+    pub metadata: Option<Box<SchemaMetadata>>,
 
-    // unit only until v3.1.0
-    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.1
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub r#type: Option<SchemaTypes>,
+    #[serde(flatten, deserialize_with = "group")]
+    pub content: Option<Box<SchemaContent>>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub r#enum: Option<Vec<Any>>,
+    #[serde(flatten, deserialize_with = "group")]
+    pub oas: Option<Box<SchemaOas>>,
 
+    #[serde(flatten)]
+    pub extensions: KeyValues<Any>,
+}
+
+/// Validation keywords other than `type`, `enum`, `required` and `format`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaValidation {
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-6.1
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r#const: Option<Any>,
 
@@ -727,36 +868,90 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none", rename = "minProperties")]
     pub min_properties: Option<u32>,
 
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentRequired")]
+    pub dependent_required: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Applicators other than `allOf`, `anyOf`, `oneOf`, `items`, `properties`
+/// and `additionalProperties`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaApplicators {
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.2.1
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub required: Option<Vec<String>>,
+    pub not: Option<Arc<SchemaOrBool>>,
 
-    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentRequired")]
-    pub dependent_required: Option<Any>,
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.2.2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#if: Option<Arc<SchemaOrBool>>,
 
-    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-7
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
+    pub then: Option<Arc<SchemaOrBool>>,
 
-    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-8
-    #[serde(skip_serializing_if = "Option::is_none", rename = "contentEncoding")]
-    pub content_encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#else: Option<Arc<SchemaOrBool>>,
 
-    #[serde(skip_serializing_if = "Option::is_none", rename = "contentMediaType")]
-    pub content_media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dependentSchemas")]
+    pub dependent_schemas: Option<HashMap<String, Arc<SchemaOrBool>>>,
 
-    #[serde(skip_serializing_if = "Option::is_none", rename = "contentSchema")]
-    pub content_schema: Option<Box<Schema>>,
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.1
+    #[serde(skip_serializing_if = "Option::is_none", rename = "prefixItems")]
+    pub prefix_items: Option<Vec<Arc<SchemaOrBool>>>,
 
-    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-9
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub title: Option<String>,
+    pub contains: Option<Arc<SchemaOrBool>>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-10.3.2
+    #[serde(skip_serializing_if = "Option::is_none", rename = "patternProperties")]
+    pub pattern_properties: Option<HashMap<String, Arc<SchemaOrBool>>>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<Any>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "propertyNames")]
+    pub property_names: Option<Arc<SchemaOrBool>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-11
+    #[serde(skip_serializing_if = "Option::is_none", rename = "unevaluatedItems")]
+    pub unevaluated_items: Option<Arc<SchemaOrBool>>,
+
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "unevaluatedProperties"
+    )]
+    pub unevaluated_properties: Option<Arc<SchemaOrBool>>,
+}
+
+/// Core keywords other than `$ref`, and annotations other than `title`,
+/// `description` and `default`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaMetadata {
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.1
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$schema")]
+    pub schema: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$vocabulary")]
+    pub vocabulary: Option<HashMap<String, bool>>,
 
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$id")]
+    pub id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$anchor")]
+    pub anchor: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$dynamicAnchor")]
+    pub dynamic_anchor: Option<String>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2.3.2
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$dynamicRef")]
+    pub dynamic_ref: Option<String>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.2.4
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$defs")]
+    pub defs: Option<HashMap<String, Arc<SchemaOrBool>>>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-00#section-8.3
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$comment")]
+    pub comment: Option<String>,
+
+    // https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-9
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<bool>,
 
@@ -768,11 +963,176 @@ pub struct Schema {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub examples: Option<Vec<Any>>,
-    // TODO: add more `JSON Schema` support.
-    #[serde(flatten)]
-    pub extensions: KeyValues<Any>,
 }
 
+// https://datatracker.ietf.org/doc/html/draft-bhutton-json-schema-validation-00#section-8
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaContent {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentEncoding")]
+    pub content_encoding: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentMediaType")]
+    pub content_media_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentSchema")]
+    pub content_schema: Option<Arc<SchemaOrBool>>,
+}
+
+/// Keywords defined by OpenAPI, other than `nullable`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchemaOas {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Xml>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDocumentation>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Any>,
+}
+
+// a group without any keyword is `None`.
+fn group<'de, D, T>(deserializer: D) -> Result<Option<Box<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default + PartialEq,
+{
+    T::deserialize(deserializer).map(boxed)
+}
+
+pub(crate) fn boxed<T: Default + PartialEq>(value: T) -> Option<Box<T>> {
+    (value != T::default()).then(|| Box::new(value))
+}
+
+macro_rules! schema_group {
+    (
+        $group:ident, $group_mut:ident, $ty:ty,
+        copy { $($c:ident: $ct:ty,)* }
+        str { $($s:ident,)* }
+        ref { $($r:ident: $rt:ty,)* }
+    ) => {
+        impl Schema {
+            /// Create the group if it is `None`.
+            pub fn $group_mut(&mut self) -> &mut $ty {
+                self.$group.get_or_insert_default()
+            }
+
+            $(
+                pub fn $c(&self) -> Option<$ct> {
+                    self.$group.as_ref()?.$c
+                }
+            )*
+
+            $(
+                pub fn $s(&self) -> Option<&str> {
+                    self.$group.as_ref()?.$s.as_deref()
+                }
+            )*
+
+            $(
+                pub fn $r(&self) -> Option<&$rt> {
+                    self.$group.as_ref()?.$r.as_ref()
+                }
+            )*
+        }
+    };
+}
+
+schema_group!(
+    validation, validation_mut, SchemaValidation,
+    copy {
+        multiple_of: u32,
+        maximum: i32,
+        exclusive_maximum: i32,
+        minimum: i32,
+        exclusive_minimum: i32,
+        max_length: u32,
+        min_length: u32,
+        max_items: u32,
+        min_items: u32,
+        unique_items: bool,
+        max_contains: u32,
+        min_contains: u32,
+        max_properties: u32,
+        min_properties: u32,
+    }
+    str {
+        pattern,
+    }
+    ref {
+        r#const: Any,
+        dependent_required: HashMap<String, Vec<String>>,
+    }
+);
+
+schema_group!(
+    applicators, applicators_mut, SchemaApplicators,
+    copy {}
+    str {}
+    ref {
+        not: Arc<SchemaOrBool>,
+        r#if: Arc<SchemaOrBool>,
+        then: Arc<SchemaOrBool>,
+        r#else: Arc<SchemaOrBool>,
+        dependent_schemas: HashMap<String, Arc<SchemaOrBool>>,
+        prefix_items: Vec<Arc<SchemaOrBool>>,
+        contains: Arc<SchemaOrBool>,
+        pattern_properties: HashMap<String, Arc<SchemaOrBool>>,
+        property_names: Arc<SchemaOrBool>,
+        unevaluated_items: Arc<SchemaOrBool>,
+        unevaluated_properties: Arc<SchemaOrBool>,
+    }
+);
+
+schema_group!(
+    metadata, metadata_mut, SchemaMetadata,
+    copy {
+        deprecated: bool,
+        read_only: bool,
+        write_only: bool,
+    }
+    str {
+        schema,
+        id,
+        anchor,
+        dynamic_anchor,
+        dynamic_ref,
+        comment,
+    }
+    ref {
+        vocabulary: HashMap<String, bool>,
+        defs: HashMap<String, Arc<SchemaOrBool>>,
+        examples: Vec<Any>,
+    }
+);
+
+schema_group!(
+    content, content_mut, SchemaContent,
+    copy {}
+    str {
+        content_encoding,
+        content_media_type,
+    }
+    ref {
+        content_schema: Arc<SchemaOrBool>,
+    }
+);
+
+schema_group!(
+    oas, oas_mut, SchemaOas,
+    copy {}
+    str {}
+    ref {
+        discriminator: Discriminator,
+        xml: Xml,
+        external_docs: ExternalDocumentation,
+        example: Any,
+    }
+);
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub enum SchemaType {
     #[default]
@@ -798,7 +1158,7 @@ pub enum SchemaType {
     Integer,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum SchemaTypes {
     Unit(SchemaType),
@@ -868,6 +1228,10 @@ pub struct SecuritySchemeApiKey {
 
     pub r#in: SecuritySchemeIn,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     #[serde(flatten)]
     pub extensions: Extensions,
 }
@@ -882,6 +1246,10 @@ pub struct SecuritySchemeHttp {
     #[serde(skip_serializing_if = "Option::is_none", rename = "bearerFormat")]
     pub bearer_format: Option<String>,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     #[serde(flatten)]
     pub extensions: Extensions,
 }
@@ -891,6 +1259,10 @@ pub struct SecuritySchemeMutualTls {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Any>,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     #[serde(flatten)]
     pub extensions: Extensions,
 }
@@ -902,6 +1274,10 @@ pub struct SecuritySchemeOauth2 {
 
     pub flows: OAuthFlows,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     #[serde(flatten)]
     pub extensions: Extensions,
 }
@@ -914,6 +1290,10 @@ pub struct SecuritySchemeOpenIdConnect {
     #[serde(rename = "openIdConnectUrl")]
     pub open_id_connect_url: String,
 
+    // since v3.2.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     #[serde(flatten)]
     pub extensions: Extensions,
 }
@@ -945,6 +1325,13 @@ pub struct OAuthFlows {
     #[serde(skip_serializing_if = "Option::is_none", rename = "authorizationCode")]
     pub authorization_code: Option<OAuthFlowAuthorizationCode>,
 
+    // since v3.2.0
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "deviceAuthorization"
+    )]
+    pub device_authorization: Option<OAuthFlowDeviceAuthorization>,
+
     #[serde(flatten)]
     pub extensions: Extensions,
 }
@@ -1008,6 +1395,24 @@ pub struct OAuthFlowAuthorizationCode {
     pub extensions: Extensions,
 }
 
+// since v3.2.0
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OAuthFlowDeviceAuthorization {
+    #[serde(rename = "deviceAuthorizationUrl")]
+    pub device_authorization_url: String,
+
+    #[serde(rename = "tokenUrl")]
+    pub token_url: String,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "refreshUrl")]
+    pub refresh_url: Option<String>,
+
+    pub scopes: HashMap<String, String>,
+
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
 type SecurityRequirement = HashMap<String, Vec<String>>;
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -1157,6 +1562,20 @@ mod tests {
         assert_eq!(v, r);
     }
 
+    #[test]
+    fn serde_license_error() {
+        let r = serde_json::from_str::<License>("{\"url\":\"a\"}");
+        assert!(r.unwrap_err().to_string().contains("missing field `name`"));
+
+        let s = "{\"name\":\"a\",\"url\":\"b\",\"identifier\":\"c\"}";
+        let r = serde_json::from_str::<License>(s);
+        assert!(
+            r.unwrap_err()
+                .to_string()
+                .contains("both `url` and `identifier`")
+        );
+    }
+
     #[test]
     fn serde_server() {
         let v = Server::default();
@@ -1272,11 +1691,14 @@ mod tests {
     fn serde_parameter_schema() {
         let v = Parameter {
             pattern: ParameterPattern::Style(ParameterStyle {
-                schema: Some(Schema {
-                    r#type: Some(SchemaTypes::Unit(SchemaType::Integer)),
-                    r#enum: Some(vec![Any::Integer(1)]),
-                    ..Default::default()
-                }),
+                schema: Some(
+                    Schema {
+                        r#type: Some(SchemaTypes::Unit(SchemaType::Integer)),
+                        r#enum: Some(vec![Any::Integer(1)]),
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
                 ..Default::default()
             }),
             ..Default::default()
@@ -1361,6 +1783,21 @@ mod tests {
         assert_eq!(v, r);
     }
 
+    #[test]
+    fn serde_parameter_error() {
+        let s = "{\"name\":\"a\",\"in\":\"query\",\"content\":[]}";
+        let r = serde_json::from_str::<Parameter>(s);
+        assert!(r.unwrap_err().to_string().contains("expected a map"));
+
+        let s = "{\"name\":\"a\",\"in\":\"query\",\"examples\":1}";
+        let r = serde_json::from_str::<Parameter>(s);
+        assert!(
+            r.unwrap_err()
+                .to_string()
+                .contains("invalid type: integer `1`")
+        );
+    }
+
     #[test]
     fn serde_request_body() {
         let v = RequestBody::default();
@@ -1581,6 +2018,66 @@ mod tests {
         assert_eq!(v, r);
     }
 
+    #[test]
+    fn serde_example_ref() {
+        let v = ReferenceOr::<Example>::Ref(Reference {
+            r#ref: "a".to_string(),
+            ..Default::default()
+        });
+        let s = serde_json::to_string(&v).unwrap();
+        assert_eq!("{\"$ref\":\"a\"}", s);
+        let r = serde_json::from_str::<ReferenceOr<Example>>(&s).unwrap();
+        assert_eq!(v, r);
+    }
+
+    #[test]
+    fn serde_reference_or_siblings() {
+        let s = "{\"$ref\":\"a\",\"description\":\"b\",\"in\":\"query\"}";
+        let r = serde_json::from_str::<ReferenceOr<Parameter>>(s).unwrap();
+        let v = ReferenceOr::Ref(Reference {
+            r#ref: "a".to_string(),
+            description: Some("b".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(v, r);
+
+        let s = "{\"summary\":\"a\",\"value\":1}";
+        let r = serde_json::from_str::<ReferenceOr<Example>>(s).unwrap();
+        let v = ReferenceOr::Value(Example {
+            summary: Some("a".to_string()),
+            value: ExampleValue::Literal {
+                value: Some(Any::Integer(1)),
+            },
+            ..Default::default()
+        });
+        assert_eq!(v, r);
+    }
+
+    #[test]
+    fn serde_reference_or_path_item() {
+        let s = "{\"$ref\":\"#/a\",\"get\":{\"responses\":{}}}";
+        let r = serde_json::from_str::<ReferenceOr<PathItem>>(s).unwrap();
+        match &r {
+            ReferenceOr::Value(v) => {
+                assert_eq!(Some("#/a"), v.r#ref.as_deref());
+                assert!(v.get.is_some());
+            }
+            ReferenceOr::Ref(_) => panic!("{r:?}"),
+        }
+        assert_eq!(s, serde_json::to_string(&r).unwrap());
+
+        let s = "{\"$ref\":\"#/a\",\"summary\":\"b\"}";
+        let r = serde_json::from_str::<ReferenceOr<PathItem>>(s).unwrap();
+        assert!(matches!(r, ReferenceOr::Ref(_)));
+        assert_eq!(s, serde_json::to_string(&r).unwrap());
+    }
+
+    #[test]
+    fn serde_reference_or_error() {
+        let r = serde_json::from_str::<ReferenceOr<Parameter>>("{\"in\":\"query\"}");
+        assert!(r.unwrap_err().to_string().contains("missing field `name`"));
+    }
+
     #[test]
     fn serde_link() {
         let v = Link::default();
@@ -1632,6 +2129,19 @@ mod tests {
         assert_eq!(v, r);
     }
 
+    #[test]
+    fn serde_tag_description() {
+        let v = Tag {
+            name: "a".to_string(),
+            description: Some("b".to_string()),
+            ..Default::default()
+        };
+        let s = serde_json::to_string(&v).unwrap();
+        assert_eq!("{\"name\":\"a\",\"description\":\"b\"}", s);
+        let r = serde_json::from_str::<Tag>(&s).unwrap();
+        assert_eq!(v, r);
+    }
+
     #[test]
     fn serde_reference() {
         let v = Reference::default();
@@ -1653,7 +2163,7 @@ mod tests {
     #[test]
     fn serde_schema_additional_properties_value() {
         let v = Schema {
-            additional_properties: Some(Box::new(BooleanOr::Value(Schema::default()))),
+            additional_properties: Some(Arc::new(BooleanOr::Value(Schema::default()))),
             ..Default::default()
         };
         let s = serde_json::to_string(&v).unwrap();
@@ -1665,7 +2175,7 @@ mod tests {
     #[test]
     fn serde_schema_additional_properties_bool() {
         let v = Schema {
-            additional_properties: Some(Box::new(BooleanOr::Boolean(true))),
+            additional_properties: Some(Arc::new(BooleanOr::Boolean(true))),
             ..Default::default()
         };
         let s = serde_json::to_string(&v).unwrap();
@@ -1696,6 +2206,76 @@ mod tests {
         assert_eq!(v, r);
     }
 
+    #[test]
+    fn serde_schema_core_keywords() {
+        let s = r##"{"$schema":"https://json-schema.org/draft/2020-12/schema","$id":"https://example.com/a","$anchor":"a","$dynamicRef":"#node","$defs":{"b":{}},"$comment":"","dependentRequired":{"c":["d"]},"unevaluatedProperties":false}"##;
+        let v = serde_json::from_str::<Schema>(s).unwrap();
+        assert_eq!(Some("https://example.com/a"), v.id());
+        assert_eq!(Some("#node"), v.dynamic_ref());
+        assert_eq!(
+            Some(&vec!["d".to_string()]),
+            v.dependent_required().unwrap().get("c")
+        );
+        assert!(v.extensions.values.is_empty());
+        let r = serde_json::from_str::<Schema>(&serde_json::to_string(&v).unwrap()).unwrap();
+        assert_eq!(v, r);
+    }
+
+    #[test]
+    fn serde_schema_type_error() {
+        let r = serde_json::from_str::<Schema>("{\"type\":\"a\"}");
+        assert!(r.unwrap_err().to_string().contains("unknown variant `a`"));
+
+        let r = serde_json::from_str::<Schema>("{\"type\":[\"string\",\"b\"]}");
+        assert!(r.unwrap_err().to_string().contains("unknown variant `b`"));
+    }
+
+    #[test]
+    fn serde_schema_groups() {
+        let s = r#"{"type":"string","minLength":1,"x-a":0,"unknown":1}"#;
+        let mut v = serde_json::from_str::<Schema>(s).unwrap();
+        assert_eq!(Some(1), v.min_length());
+        assert!(v.applicators.is_none() && v.metadata.is_none() && v.oas.is_none());
+        assert_eq!(
+            vec!["unknown"],
+            v.extensions.values.keys().collect::<Vec<_>>()
+        );
+
+        v.oas_mut().example = Some(Any::String("a".to_string()));
+        v.items = Some(Arc::new(Schema::default().into()));
+        let copied = v.clone();
+        assert!(Arc::ptr_eq(
+            v.items.as_ref().unwrap(),
+            copied.items.as_ref().unwrap()
+        ));
+        let r = serde_json::from_str::<Schema>(&serde_json::to_string(&v).unwrap()).unwrap();
+        assert_eq!(Some(&Any::String("a".to_string())), r.example());
+        assert_eq!(v, r);
+    }
+
+    #[test]
+    fn serde_schema_boolean() {
+        let s = r#"{"items":false,"properties":{"a":true,"b":{"type":"string"}},"not":false}"#;
+        let v = serde_json::from_str::<Schema>(s).unwrap();
+        assert!(v.items.as_ref().unwrap().is_false());
+        let properties = v.properties.as_ref().unwrap();
+        assert_eq!(BooleanOr::Boolean(true), *properties["a"]);
+        let copied = v.clone();
+        assert!(Arc::ptr_eq(
+            &properties["b"],
+            &copied.properties.as_ref().unwrap()["b"]
+        ));
+        assert_eq!(
+            Some(SchemaTypes::Unit(SchemaType::String)),
+            properties["b"].schema().r#type
+        );
+        let r = serde_json::from_str::<Schema>(&serde_json::to_string(&v).unwrap()).unwrap();
+        assert_eq!(v, r);
+
+        let v = serde_json::from_str::<MediaType>(r#"{"schema":false}"#).unwrap();
+        assert!(v.schema.unwrap().is_false());
+    }
+
     #[test]
     fn serde_discriminator() {
         let v = Discriminator::default();
@@ -1815,4 +2395,37 @@ mod tests {
         let r = serde_json::from_str::<PartOpenApi>(&s).unwrap();
         assert_eq!(v, r);
     }
+
+    // https://github.com/OAI/OpenAPI-Specification/tree/main/examples
+    #[test]
+    fn conformance_oas_examples() {
+        for (name, s) in [
+            (
+                "v3.0/petstore.yaml",
+                include_str!("../testdata/oas/v3.0/petstore.yaml"),
+            ),
+            (
+                "v3.0/callback-example.yaml",
+                include_str!("../testdata/oas/v3.0/callback-example.yaml"),
+            ),
+            (
+                "v3.0/link-example.yaml",
+                include_str!("../testdata/oas/v3.0/link-example.yaml"),
+            ),
+            (
+                "v3.1/webhook-example.yaml",
+                include_str!("../testdata/oas/v3.1/webhook-example.yaml"),
+            ),
+            (
+                "v3.1/non-oauth-scopes.yaml",
+                include_str!("../testdata/oas/v3.1/non-oauth-scopes.yaml"),
+            ),
+        ] {
+            let expected = serde_yaml::from_str::<serde_json::Value>(s).unwrap();
+            let v = serde_yaml::from_str::<OpenApi>(s).unwrap();
+            assert_eq!(expected, serde_json::to_value(&v).unwrap(), "{name}");
+            let r = serde_json::from_value::<OpenApi>(expected).unwrap();
+            assert_eq!(v, r, "{name}");
+        }
+    }
 }
@@ -0,0 +1,635 @@
+use super::model::Any;
+use super::version::newer_features;
+use super::visit::{Visitor, pointer, walk};
+use super::{OpenApi, Operation};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Severity {
+    #[serde(rename = "off")]
+    Off,
+
+    #[serde(rename = "info")]
+    Info,
+
+    #[serde(rename = "warning")]
+    Warning,
+
+    #[serde(rename = "error")]
+    Error,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LintConfig {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rules: HashMap<String, Severity>,
+
+    // without `x-` prefix is also accepted.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowedExtensions")]
+    pub allowed_extensions: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub pointer: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    pub pointer: String,
+    pub message: String,
+}
+
+/// A lint rule. Rules collect findings while the document is walked and
+/// hand them over in [`Rule::finish`].
+pub trait Rule: Visitor {
+    fn name(&self) -> &str;
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn finish(&mut self, doc: &OpenApi) -> Vec<Finding>;
+}
+
+pub struct Linter {
+    config: LintConfig,
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Linter {
+    pub fn new(config: LintConfig) -> Self {
+        let rules = builtin_rules(&config);
+        Linter { config, rules }
+    }
+
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rules.push(rule);
+    }
+
+    pub fn rule_names(&self) -> Vec<&str> {
+        self.rules.iter().map(|r| r.name()).collect()
+    }
+
+    pub fn lint(&mut self, doc: &OpenApi) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        for rule in &mut self.rules {
+            let severity = self
+                .config
+                .rules
+                .get(rule.name())
+                .copied()
+                .unwrap_or(rule.default_severity());
+            if severity == Severity::Off {
+                continue;
+            }
+
+            walk(doc, rule.as_mut());
+            for finding in rule.finish(doc) {
+                diagnostics.push(Diagnostic {
+                    rule: rule.name().to_string(),
+                    severity,
+                    pointer: finding.pointer,
+                    message: finding.message,
+                });
+            }
+        }
+
+        diagnostics.sort_by(|a, b| (&a.pointer, &a.rule).cmp(&(&b.pointer, &b.rule)));
+        diagnostics
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Linter::new(LintConfig::default())
+    }
+}
+
+fn builtin_rules(config: &LintConfig) -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(OperationField::new(
+            "operation-operation-id",
+            "operationId",
+            |op| op.operation_id.is_some(),
+        )),
+        Box::new(OperationField::new("operation-summary", "summary", |op| {
+            op.summary.is_some()
+        })),
+        Box::new(OperationField::new("operation-tags", "tags", |op| {
+            op.tags.as_ref().is_some_and(|t| !t.is_empty())
+        })),
+        Box::new(OperationTagDefined::default()),
+        Box::new(PathKebabCase::default()),
+        Box::new(Response4xx::default()),
+        Box::new(SchemaDescription::default()),
+        Box::new(ComponentsUnused::default()),
+        Box::new(ExtensionAllowlist::new(
+            config.allowed_extensions.as_deref(),
+        )),
+        Box::new(SpecVersionFeature),
+    ]
+}
+
+// ---------------------------------------------------------------------------
+
+struct OperationField {
+    name: &'static str,
+    field: &'static str,
+    exists: fn(&Operation) -> bool,
+    findings: Vec<Finding>,
+}
+
+impl OperationField {
+    fn new(name: &'static str, field: &'static str, exists: fn(&Operation) -> bool) -> Self {
+        OperationField {
+            name,
+            field,
+            exists,
+            findings: vec![],
+        }
+    }
+}
+
+impl Visitor for OperationField {
+    fn visit_operation(&mut self, ptr: &str, path: &str, method: &str, value: &Operation) {
+        if !(self.exists)(value) {
+            self.findings.push(Finding {
+                pointer: ptr.to_string(),
+                message: format!(
+                    "Operation `{} {path}` is missing `{}`.",
+                    method.to_uppercase(),
+                    self.field
+                ),
+            });
+        }
+    }
+}
+
+impl Rule for OperationField {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn finish(&mut self, _doc: &OpenApi) -> Vec<Finding> {
+        std::mem::take(&mut self.findings)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct OperationTagDefined {
+    used: Vec<(String, String)>,
+}
+
+impl Visitor for OperationTagDefined {
+    fn visit_operation(&mut self, ptr: &str, _path: &str, _method: &str, value: &Operation) {
+        if let Some(tags) = &value.tags {
+            for (i, tag) in tags.iter().enumerate() {
+                let ptr = pointer(&pointer(ptr, "tags"), &i.to_string());
+                self.used.push((ptr, tag.clone()));
+            }
+        }
+    }
+}
+
+impl Rule for OperationTagDefined {
+    fn name(&self) -> &str {
+        "operation-tag-defined"
+    }
+
+    fn finish(&mut self, doc: &OpenApi) -> Vec<Finding> {
+        let declared = doc
+            .tags
+            .iter()
+            .flatten()
+            .map(|t| t.name.as_str())
+            .collect::<HashSet<&str>>();
+
+        std::mem::take(&mut self.used)
+            .into_iter()
+            .filter(|(_, tag)| !declared.contains(tag.as_str()))
+            .map(|(pointer, tag)| Finding {
+                pointer,
+                message: format!("Tag `{tag}` is not declared in `tags`."),
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct PathKebabCase {
+    findings: Vec<Finding>,
+}
+
+impl Visitor for PathKebabCase {
+    fn visit_openapi(&mut self, ptr: &str, value: &OpenApi) {
+        let paths = match &value.paths {
+            Some(paths) => paths,
+            None => return,
+        };
+
+        for path in paths.values.keys() {
+            let invalid = path
+                .split('/')
+                .filter(|s| !(s.starts_with('{') && s.ends_with('}')))
+                .any(|s| !kebab_case(s));
+            if invalid {
+                self.findings.push(Finding {
+                    pointer: pointer(&pointer(ptr, "paths"), path),
+                    message: format!("Path `{path}` is not kebab-case."),
+                });
+            }
+        }
+    }
+}
+
+impl Rule for PathKebabCase {
+    fn name(&self) -> &str {
+        "path-kebab-case"
+    }
+
+    fn finish(&mut self, _doc: &OpenApi) -> Vec<Finding> {
+        std::mem::take(&mut self.findings)
+    }
+}
+
+fn kebab_case(segment: &str) -> bool {
+    !segment.starts_with('-')
+        && !segment.ends_with('-')
+        && !segment.contains("--")
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.')
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct Response4xx {
+    findings: Vec<Finding>,
+}
+
+impl Visitor for Response4xx {
+    fn visit_operation(&mut self, ptr: &str, path: &str, method: &str, value: &Operation) {
+        let exists = value
+            .responses
+            .as_ref()
+            .is_some_and(|r| r.statuses.values.keys().any(|s| (400..500).contains(s)));
+        if !exists {
+            self.findings.push(Finding {
+                pointer: ptr.to_string(),
+                message: format!(
+                    "Operation `{} {path}` has no 4xx response.",
+                    method.to_uppercase()
+                ),
+            });
+        }
+    }
+}
+
+impl Rule for Response4xx {
+    fn name(&self) -> &str {
+        "response-4xx"
+    }
+
+    fn finish(&mut self, _doc: &OpenApi) -> Vec<Finding> {
+        std::mem::take(&mut self.findings)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct SchemaDescription {
+    findings: Vec<Finding>,
+}
+
+impl Visitor for SchemaDescription {
+    fn visit_openapi(&mut self, ptr: &str, value: &OpenApi) {
+        let schemas = match value.components.as_ref().and_then(|c| c.schemas.as_ref()) {
+            Some(schemas) => schemas,
+            None => return,
+        };
+
+        let ptr = pointer(&pointer(ptr, "components"), "schemas");
+        for (name, schema) in schemas {
+            if schema.r#ref.is_none() && schema.description.is_none() {
+                self.findings.push(Finding {
+                    pointer: pointer(&ptr, name),
+                    message: format!("Schema `{name}` is missing `description`."),
+                });
+            }
+        }
+    }
+}
+
+impl Rule for SchemaDescription {
+    fn name(&self) -> &str {
+        "schema-description"
+    }
+
+    fn finish(&mut self, _doc: &OpenApi) -> Vec<Finding> {
+        std::mem::take(&mut self.findings)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct ComponentsUnused {
+    references: HashSet<String>,
+}
+
+impl Visitor for ComponentsUnused {
+    fn visit_reference(&mut self, _ptr: &str, reference: &str) {
+        if let Some((_, fragment)) = reference.split_once('#') {
+            self.references.insert(fragment.to_string());
+        }
+    }
+}
+
+impl Rule for ComponentsUnused {
+    fn name(&self) -> &str {
+        "components-unused"
+    }
+
+    fn finish(&mut self, doc: &OpenApi) -> Vec<Finding> {
+        let components = match &doc.components {
+            Some(components) => components,
+            None => return vec![],
+        };
+
+        let mut defined = vec![];
+        defined.extend(names("schemas", &components.schemas));
+        defined.extend(names("responses", &components.responses));
+        defined.extend(names("parameters", &components.parameters));
+        defined.extend(names("examples", &components.examples));
+        defined.extend(names("requestBodies", &components.request_bodies));
+        defined.extend(names("headers", &components.headers));
+        defined.extend(names("links", &components.links));
+        defined.extend(names("callbacks", &components.callbacks));
+        defined.extend(names("pathItems", &components.path_items));
+
+        let references = std::mem::take(&mut self.references);
+        let mut findings = vec![];
+        for (kind, name) in defined {
+            let ptr = pointer(&pointer("/components", kind), name);
+            if !references.contains(&ptr) {
+                findings.push(Finding {
+                    message: format!("Component `{ptr}` is not referenced."),
+                    pointer: ptr,
+                });
+            }
+        }
+        findings
+    }
+}
+
+fn names<'a, T>(
+    kind: &'static str,
+    values: &'a Option<HashMap<String, T>>,
+) -> impl Iterator<Item = (&'static str, &'a str)> {
+    values
+        .iter()
+        .flat_map(|v| v.keys())
+        .map(move |k| (kind, k.as_str()))
+}
+
+// ---------------------------------------------------------------------------
+
+struct ExtensionAllowlist {
+    allowed: Option<HashSet<String>>,
+    findings: Vec<Finding>,
+}
+
+impl ExtensionAllowlist {
+    fn new(allowed: Option<&[String]>) -> Self {
+        let allowed = allowed.map(|a| {
+            a.iter()
+                .map(|e| e.strip_prefix("x-").unwrap_or(e).to_string())
+                .collect()
+        });
+        ExtensionAllowlist {
+            allowed,
+            findings: vec![],
+        }
+    }
+}
+
+impl Visitor for ExtensionAllowlist {
+    fn visit_extensions(&mut self, ptr: &str, values: &HashMap<String, Any>) {
+        let allowed = match &self.allowed {
+            Some(allowed) => allowed,
+            None => return,
+        };
+
+        for key in values.keys() {
+            if !allowed.contains(key) {
+                self.findings.push(Finding {
+                    pointer: pointer(ptr, &format!("x-{key}")),
+                    message: format!("Extension `x-{key}` is not allowed."),
+                });
+            }
+        }
+    }
+}
+
+impl Rule for ExtensionAllowlist {
+    fn name(&self) -> &str {
+        "extension-allowlist"
+    }
+
+    fn finish(&mut self, _doc: &OpenApi) -> Vec<Finding> {
+        std::mem::take(&mut self.findings)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+// features of a later version than `openapi`, which tools of that version ignore.
+struct SpecVersionFeature;
+
+impl Visitor for SpecVersionFeature {}
+
+impl Rule for SpecVersionFeature {
+    fn name(&self) -> &str {
+        "spec-version-feature"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn finish(&mut self, doc: &OpenApi) -> Vec<Finding> {
+        newer_features(doc)
+            .into_iter()
+            .map(|f| Finding {
+                message: format!("`{}` requires {} or later.", f.name, f.since),
+                pointer: f.pointer,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(s: &str) -> OpenApi {
+        serde_json::from_str::<OpenApi>(s).unwrap()
+    }
+
+    fn rules(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.rule.as_str()).collect()
+    }
+
+    const DOC: &str = r##"{
+        "openapi": "3.1.0",
+        "info": {"title": "", "version": ""},
+        "tags": [{"name": "a"}],
+        "paths": {
+            "/fooBar/{id}": {
+                "get": {
+                    "tags": ["b"],
+                    "x-internal": true,
+                    "responses": {
+                        "200": {
+                            "description": "",
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "#/components/schemas/A"}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "A": {"description": "a"},
+                "B": {}
+            }
+        }
+    }"##;
+
+    #[test]
+    fn lint_default() {
+        let mut linter = Linter::default();
+        let r = linter.lint(&doc(DOC));
+        assert_eq!(
+            vec![
+                "components-unused",
+                "schema-description",
+                "path-kebab-case",
+                "operation-operation-id",
+                "operation-summary",
+                "response-4xx",
+                "operation-tag-defined",
+            ],
+            rules(&r)
+        );
+        assert_eq!("/components/schemas/B", r[0].pointer);
+        assert_eq!("/paths/~1fooBar~1{id}/get/tags/0", r[6].pointer);
+    }
+
+    #[test]
+    fn lint_config() {
+        let config = serde_json::from_str::<LintConfig>(
+            r#"{
+                "rules": {
+                    "components-unused": "off",
+                    "schema-description": "off",
+                    "path-kebab-case": "error",
+                    "operation-operation-id": "off",
+                    "operation-summary": "off",
+                    "operation-tag-defined": "off",
+                    "response-4xx": "info"
+                },
+                "allowedExtensions": ["x-public"]
+            }"#,
+        )
+        .unwrap();
+        let mut linter = Linter::new(config);
+        let r = linter.lint(&doc(DOC));
+        assert_eq!(
+            vec!["path-kebab-case", "response-4xx", "extension-allowlist"],
+            rules(&r)
+        );
+        assert_eq!(Severity::Error, r[0].severity);
+        assert_eq!(Severity::Info, r[1].severity);
+        assert_eq!("/paths/~1fooBar~1{id}/get/x-internal", r[2].pointer);
+    }
+
+    #[test]
+    fn lint_custom_rule() {
+        #[derive(Default)]
+        struct NoDelete {
+            findings: Vec<Finding>,
+        }
+
+        impl Visitor for NoDelete {
+            fn visit_operation(&mut self, ptr: &str, _path: &str, method: &str, _: &Operation) {
+                if method == "delete" {
+                    self.findings.push(Finding {
+                        pointer: ptr.to_string(),
+                        message: "".to_string(),
+                    });
+                }
+            }
+        }
+
+        impl Rule for NoDelete {
+            fn name(&self) -> &str {
+                "no-delete"
+            }
+
+            fn default_severity(&self) -> Severity {
+                Severity::Error
+            }
+
+            fn finish(&mut self, _doc: &OpenApi) -> Vec<Finding> {
+                std::mem::take(&mut self.findings)
+            }
+        }
+
+        let mut config = LintConfig::default();
+        for name in Linter::default().rule_names() {
+            config.rules.insert(name.to_string(), Severity::Off);
+        }
+        let mut linter = Linter::new(config);
+        linter.add_rule(Box::new(NoDelete::default()));
+
+        let r = linter.lint(&doc(
+            r#"{"openapi":"","info":{"title":"","version":""},"paths":{"/a":{"delete":{}}}}"#,
+        ));
+        assert_eq!(vec!["no-delete"], rules(&r));
+        assert_eq!(Severity::Error, r[0].severity);
+    }
+
+    #[test]
+    fn lint_spec_version_feature() {
+        let s = r#"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "tags": [{"name": "a", "kind": "nav"}]
+        }"#;
+        let r = Linter::default().lint(&doc(s));
+        assert_eq!(vec!["spec-version-feature"], rules(&r));
+        assert_eq!(Severity::Error, r[0].severity);
+        assert_eq!("/tags/0/kind", r[0].pointer);
+        assert_eq!("`kind` requires v3.2 or later.", r[0].message);
+
+        let r = Linter::default().lint(&doc(&s.replace("3.1.0", "3.2.0")));
+        assert!(r.is_empty());
+    }
+}
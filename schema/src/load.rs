@@ -0,0 +1,74 @@
+#[cfg(feature = "cache")]
+use super::cache::Cache;
+use super::error::{Error, ErrorKind};
+#[cfg(feature = "cache")]
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::Path;
+
+/// Read a JSON or YAML document, chosen by the extension of `path`.
+///
+/// Errors carry `path` as the URI. With the feature `cache`, the document is
+/// cached in the directory of [`CACHE_DIR_ENV`](crate::cache::CACHE_DIR_ENV)
+/// if it is set.
+#[cfg(feature = "cache")]
+pub fn from_path<T: DeserializeOwned + Serialize>(path: &Path) -> Result<T, Error> {
+    if let Some(cache) = Cache::from_env() {
+        return cache.load(path);
+    }
+    read(path)
+}
+
+/// Read a JSON or YAML document, chosen by the extension of `path`.
+///
+/// Errors carry `path` as the URI.
+#[cfg(not(feature = "cache"))]
+pub fn from_path<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    read(path)
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    let uri = path.display().to_string();
+    let content = fs::read_to_string(path).map_err(|e| Error::from(e).with_uri(&uri))?;
+    from_content(path, &content).map_err(|e| e.with_uri(uri))
+}
+
+pub(crate) fn from_content<T: DeserializeOwned>(path: &Path, content: &str) -> Result<T, Error> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => from_json(content),
+        Some("yaml" | "yml") => from_yaml(content),
+        _ => Err(Error::new(ErrorKind::Parse, "Not supported format.")),
+    }
+}
+
+pub fn from_json<T: DeserializeOwned>(content: &str) -> Result<T, Error> {
+    Ok(serde_json::from_str(content)?)
+}
+
+pub fn from_yaml<T: DeserializeOwned>(content: &str) -> Result<T, Error> {
+    Ok(serde_yaml::from_str(content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpenApi, PartOpenApi};
+
+    #[test]
+    fn load_from_path() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/oas/v3.0/petstore.yaml");
+        let doc = from_path::<OpenApi>(&path).unwrap();
+        assert_eq!("Swagger Petstore", doc.info.title);
+        let part = from_path::<PartOpenApi>(&path).unwrap();
+        assert!(part.paths.is_some());
+
+        let e = from_path::<OpenApi>(&path.with_extension("json")).unwrap_err();
+        assert_eq!(ErrorKind::Io, e.kind());
+        assert!(e.uri().unwrap().ends_with("petstore.json"));
+
+        let e = from_json::<OpenApi>(r#"{"openapi": "3.1.0"}"#).unwrap_err();
+        assert_eq!(ErrorKind::Parse, e.kind());
+        assert_eq!(None, e.uri());
+    }
+}
@@ -0,0 +1,352 @@
+use super::MediaType;
+use std::collections::HashMap;
+use std::fmt;
+
+// https://datatracker.ietf.org/doc/html/rfc9110#section-8.3.1
+// https://spec.openapis.org/oas/v3.1.0#media-types
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MediaTypeError {
+    // not `type/subtype`.
+    InvalidType(String),
+    InvalidParameter(String),
+}
+
+impl fmt::Display for MediaTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MediaTypeError::InvalidType(s) => write!(f, "Invalid media type `{s}`."),
+            MediaTypeError::InvalidParameter(s) => {
+                write!(f, "Invalid media type parameter `{s}`.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MediaTypeError {}
+
+/// A media type, or a range with `*`, like `text/*; charset=utf-8`.
+///
+/// Type, subtype and parameter names are lowercased.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaRange {
+    pub r#type: String,
+    pub subtype: String,
+    pub parameters: Vec<(String, String)>,
+}
+
+impl MediaRange {
+    pub fn parse(s: &str) -> Result<Self, MediaTypeError> {
+        let mut parts = split_unquoted(s, ';').into_iter();
+        let essence = parts.next().unwrap_or_default().trim().to_lowercase();
+        let (r#type, subtype) = match essence.split_once('/') {
+            Some((t, s)) => (t.to_string(), s.to_string()),
+            // sent by some clients for `*/*`.
+            None if essence == "*" => ("*".to_string(), "*".to_string()),
+            None => return Err(MediaTypeError::InvalidType(s.to_string())),
+        };
+        if !is_token(&r#type) || !is_token(&subtype) || (r#type == "*" && subtype != "*") {
+            return Err(MediaTypeError::InvalidType(s.to_string()));
+        }
+
+        let mut parameters = vec![];
+        for p in parts {
+            let p = p.trim();
+            if p.is_empty() {
+                continue;
+            }
+            let (name, value) = p
+                .split_once('=')
+                .ok_or_else(|| MediaTypeError::InvalidParameter(p.to_string()))?;
+            let name = name.trim().to_lowercase();
+            let value = value.trim();
+            let value = match value.strip_prefix('"') {
+                Some(quoted) => unquote(quoted)
+                    .ok_or_else(|| MediaTypeError::InvalidParameter(p.to_string()))?,
+                None => value.to_string(),
+            };
+            if !is_token(&name) {
+                return Err(MediaTypeError::InvalidParameter(p.to_string()));
+            }
+            parameters.push((name, value));
+        }
+
+        Ok(MediaRange {
+            r#type,
+            subtype,
+            parameters,
+        })
+    }
+
+    /// `type/subtype` without parameters.
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.r#type, self.subtype)
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.essence() == "application/json" || self.subtype.ends_with("+json")
+    }
+
+    pub fn is_xml(&self) -> bool {
+        matches!(self.essence().as_str(), "application/xml" | "text/xml")
+            || self.subtype.ends_with("+xml")
+    }
+
+    pub fn is_range(&self) -> bool {
+        self.r#type == "*" || self.subtype == "*"
+    }
+
+    /// Whether `media_type` is in this range, with the same values of the parameters of the range.
+    pub fn contains(&self, media_type: &MediaRange) -> bool {
+        let r#type = self.r#type == "*" || self.r#type == media_type.r#type;
+        let subtype = self.subtype == "*" || self.subtype == media_type.subtype;
+        r#type
+            && subtype
+            && self
+                .parameters
+                .iter()
+                .filter(|(n, _)| n != "q")
+                .all(|(n, v)| {
+                    // values are case-insensitive only for `charset`.
+                    media_type.parameter(n).is_some_and(|w| match n.as_str() {
+                        "charset" => w.eq_ignore_ascii_case(v),
+                        _ => w == v,
+                    })
+                })
+    }
+
+    /// `*/*` < `type/*` < `type/subtype` < `type/subtype` with parameters.
+    pub fn specificity(&self) -> u8 {
+        match (self.r#type.as_str(), self.subtype.as_str()) {
+            ("*", _) => 1,
+            (_, "*") => 2,
+            _ if self.parameters.iter().any(|(n, _)| n != "q") => 4,
+            _ => 3,
+        }
+    }
+
+    // the weight of `q`, where a missing or invalid value is 1.
+    fn quality(&self) -> f32 {
+        self.parameter("q")
+            .and_then(|q| q.parse::<f32>().ok())
+            .filter(|q| (0.0..=1.0).contains(q))
+            .unwrap_or(1.0)
+    }
+}
+
+impl fmt::Display for MediaRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.r#type, self.subtype)?;
+        for (name, value) in &self.parameters {
+            if is_token(value) {
+                write!(f, "; {name}={value}")?;
+            } else {
+                let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+                write!(f, "; {name}=\"{value}\"")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The entry of `content` for `content_type`, preferring the most specific key.
+///
+/// Keys are media type ranges, so `text/plain; charset=utf-8` overrides
+/// `text/plain`, which overrides `text/*`, which overrides `*/*`.
+pub fn select_media_type<'a>(
+    content: &'a HashMap<String, MediaType>,
+    content_type: &str,
+) -> Option<(&'a str, &'a MediaType)> {
+    let expected = MediaRange::parse(content_type).ok()?;
+
+    let mut best: Option<(u8, &str, &MediaType)> = None;
+    for (key, media) in content {
+        let range = match MediaRange::parse(key) {
+            Ok(range) if range.contains(&expected) => range,
+            _ => continue,
+        };
+        let rank = range.specificity();
+        if best.is_none_or(|(r, k, _)| rank > r || (rank == r && key.as_str() < k)) {
+            best = Some((rank, key, media));
+        }
+    }
+    best.map(|(_, k, m)| (k, m))
+}
+
+/// The entry of `content` which is the most acceptable by the `Accept` header.
+///
+/// Each key takes `q` of the most specific range in `accept` which matches it,
+/// and ranges with `q=0` reject it. Ties are broken by the order in `accept`,
+/// then JSON is preferred.
+pub fn negotiate<'a>(
+    content: &'a HashMap<String, MediaType>,
+    accept: &str,
+) -> Option<(&'a str, &'a MediaType)> {
+    let ranges = split_unquoted(accept, ',')
+        .into_iter()
+        .filter(|r| !r.trim().is_empty())
+        .filter_map(|r| MediaRange::parse(r).ok())
+        .collect::<Vec<_>>();
+
+    let mut keys = content.keys().collect::<Vec<&String>>();
+    keys.sort();
+
+    // (q, order in `accept`, not JSON)
+    let mut best: Option<((f32, usize, bool), &str)> = None;
+    for key in keys {
+        let media_type = match MediaRange::parse(key) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+        let matched = ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.contains(&media_type) || media_type.contains(r))
+            .max_by_key(|(i, r)| (r.specificity(), std::cmp::Reverse(*i)));
+        let (i, range) = match matched {
+            Some(m) => m,
+            None => continue,
+        };
+        let q = range.quality();
+        if q <= 0.0 {
+            continue;
+        }
+        let score = (q, i, !media_type.is_json());
+        let better = best.is_none_or(|((bq, bi, bj), _)| {
+            q > bq || (q == bq && (i < bi || (i == bi && !score.2 && bj)))
+        });
+        if better {
+            best = Some((score, key));
+        }
+    }
+    best.map(|(_, k)| (k, &content[k]))
+}
+
+// `,` or `;` outside of quoted strings.
+pub(crate) fn split_unquoted(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+// the rest of a quoted string after the opening quote.
+pub(crate) fn unquote(s: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?),
+            '"' => return chars.as_str().trim().is_empty().then_some(value),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+// https://datatracker.ietf.org/doc/html/rfc9110#section-5.6.2
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(keys: &[&str]) -> HashMap<String, MediaType> {
+        keys.iter()
+            .map(|k| (k.to_string(), MediaType::default()))
+            .collect()
+    }
+
+    #[test]
+    fn media_type_parse() {
+        let m = MediaRange::parse(r#"Text/HTML; Charset="utf-8"; q=0.5; a="b\"; c""#).unwrap();
+        assert_eq!("text/html", m.essence());
+        assert_eq!(Some("utf-8"), m.parameter("charset"));
+        assert_eq!(Some("b\"; c"), m.parameter("a"));
+        assert_eq!(0.5, m.quality());
+        assert_eq!(
+            r#"text/html; charset=utf-8; q=0.5; a="b\"; c""#,
+            m.to_string()
+        );
+
+        assert_eq!("*/*", MediaRange::parse("*").unwrap().essence());
+        assert!(
+            MediaRange::parse("application/vnd.api+json")
+                .unwrap()
+                .is_json()
+        );
+        assert!(MediaRange::parse("application/atom+xml").unwrap().is_xml());
+        assert!(MediaRange::parse("json").is_err());
+        assert!(MediaRange::parse("*/json").is_err());
+        assert!(MediaRange::parse("text/plain; charset").is_err());
+    }
+
+    #[test]
+    fn media_type_select() {
+        let c = content(&["*/*", "text/*", "text/plain", "text/plain; charset=utf-8"]);
+        let select = |t| select_media_type(&c, t).map(|(k, _)| k);
+        assert_eq!(
+            Some("text/plain; charset=utf-8"),
+            select("text/plain; charset=UTF-8")
+        );
+        assert_eq!(Some("text/plain"), select("text/plain; charset=ascii"));
+        assert_eq!(Some("text/*"), select("text/html"));
+        assert_eq!(Some("*/*"), select("image/png"));
+        assert_eq!(None, select("invalid"));
+
+        let c = content(&["application/json"]);
+        assert_eq!(None, select_media_type(&c, "application/xml"));
+    }
+
+    #[test]
+    fn media_type_negotiate() {
+        let c = content(&["application/json", "application/xml", "text/plain"]);
+        let select = |a| negotiate(&c, a).map(|(k, _)| k);
+        assert_eq!(Some("application/json"), select("*/*"));
+        assert_eq!(
+            Some("application/xml"),
+            select("application/xml, application/json")
+        );
+        assert_eq!(
+            Some("application/json"),
+            select("application/xml;q=0.5, application/json")
+        );
+        assert_eq!(Some("text/plain"), select("text/*, application/*;q=0.1"));
+        assert_eq!(
+            Some("application/xml"),
+            select("application/*, application/json;q=0")
+        );
+        assert_eq!(None, select("image/*"));
+
+        let c = content(&["application/*"]);
+        assert_eq!(
+            Some("application/*"),
+            negotiate(&c, "application/json").map(|(k, _)| k)
+        );
+    }
+}
@@ -0,0 +1,647 @@
+use super::model::Any;
+use super::resolve::Resolver;
+use super::validate::{equal, is_type};
+use super::visit::pointer;
+use super::{
+    BooleanOr, Schema, SchemaApplicators, SchemaContent, SchemaMetadata, SchemaOas, SchemaOrBool,
+    SchemaType, SchemaTypes, SchemaValidation, boxed,
+};
+use std::fmt;
+use std::sync::Arc;
+
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeError {
+    // subschemas of `allOf` which no instance can satisfy together.
+    Conflict { pointer: String, keyword: String },
+    NotFoundReference(String),
+    // nested too deeply, or a cycle of `$ref`.
+    TooDeep,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MergeError::Conflict { pointer, keyword } => {
+                let ptr = if pointer.is_empty() { "/" } else { pointer };
+                write!(f, "Conflicting `{keyword}` in `allOf` at {ptr}.")
+            }
+            MergeError::NotFoundReference(r) => write!(f, "Not found reference `{r}`."),
+            MergeError::TooDeep => write!(f, "`allOf` is nested too deeply."),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+impl Schema {
+    /// Merge the subschemas of `allOf` into one equivalent schema.
+    ///
+    /// References in `allOf` are resolved by `resolver`. Keywords which can
+    /// not be combined into one value, like two different `pattern`s, are
+    /// kept in `allOf` of the result.
+    pub fn merge_all_of<R: Resolver + ?Sized>(&self, resolver: &R) -> Result<Schema, MergeError> {
+        flatten(self, resolver, 0)
+    }
+}
+
+fn flatten<R: Resolver + ?Sized>(
+    schema: &Schema,
+    resolver: &R,
+    depth: usize,
+) -> Result<Schema, MergeError> {
+    if depth > MAX_DEPTH {
+        return Err(MergeError::TooDeep);
+    }
+
+    let mut merged = schema.clone();
+    for s in merged.all_of.take().into_iter().flatten() {
+        let s = match s.as_ref() {
+            BooleanOr::Value(s) => inline(s, resolver, depth + 1)?,
+            BooleanOr::Boolean(true) => continue,
+            BooleanOr::Boolean(false) => {
+                return Err(MergeError::Conflict {
+                    pointer: String::new(),
+                    keyword: "allOf".to_string(),
+                });
+            }
+        };
+        merged = merge(merged, s, "")?;
+    }
+    Ok(merged)
+}
+
+// a subschema of `allOf` with its `$ref` replaced by the target.
+fn inline<R: Resolver + ?Sized>(
+    schema: &Schema,
+    resolver: &R,
+    depth: usize,
+) -> Result<Schema, MergeError> {
+    let mut s = flatten(schema, resolver, depth)?;
+    if let Some(r) = s.r#ref.take() {
+        let target = resolver
+            .resolve_schema(None, &r)
+            .ok_or(MergeError::NotFoundReference(r))?;
+        let target = inline(target, resolver, depth + 1)?;
+        s = merge(s, target, "")?;
+    }
+    Ok(s)
+}
+
+fn merge(mut a: Schema, mut b: Schema, ptr: &str) -> Result<Schema, MergeError> {
+    if a == b {
+        return Ok(a);
+    }
+    // constraints beside `$ref` are ignored until v3.1.0, so keep both as they are.
+    if a.r#ref.is_some() || b.r#ref.is_some() {
+        return Ok(Schema {
+            all_of: Some(vec![a.into(), b.into()]),
+            ..Default::default()
+        });
+    }
+
+    let conflict = |keyword: &str| MergeError::Conflict {
+        pointer: ptr.to_string(),
+        keyword: keyword.to_string(),
+    };
+
+    let (av, bv) = (ungroup(a.validation.take()), ungroup(b.validation.take()));
+    let (aa, ba) = (ungroup(a.applicators.take()), ungroup(b.applicators.take()));
+    let (am, bm) = (ungroup(a.metadata.take()), ungroup(b.metadata.take()));
+    let (ac, bc) = (ungroup(a.content.take()), ungroup(b.content.take()));
+    let (ao, bo) = (ungroup(a.oas.take()), ungroup(b.oas.take()));
+
+    // keywords of `b` which can not be merged.
+    let mut rest = Schema::default();
+    let mut rest_validation = SchemaValidation::default();
+    let mut rest_applicators = SchemaApplicators::default();
+    let mut rest_metadata = SchemaMetadata::default();
+    let mut rest_content = SchemaContent::default();
+
+    let r#type = match (types(&a), types(&b)) {
+        (Some(x), Some(y)) => {
+            let mut types = vec![];
+            for t in &x {
+                for u in &y {
+                    let common = match (t, u) {
+                        (SchemaType::Number, SchemaType::Integer)
+                        | (SchemaType::Integer, SchemaType::Number) => SchemaType::Integer,
+                        (t, u) if t == u => t.clone(),
+                        _ => continue,
+                    };
+                    if !types.contains(&common) {
+                        types.push(common);
+                    }
+                }
+            }
+            match types.len() {
+                0 => return Err(conflict("type")),
+                1 => Some(SchemaTypes::Unit(types.remove(0))),
+                _ => Some(SchemaTypes::Array(types)),
+            }
+        }
+        _ => a.r#type.clone().or(b.r#type.clone()),
+    };
+
+    // a schema without `type` allows `null`.
+    let nullable =
+        (a.nullable.is_some() || b.nullable.is_some()).then(|| nullable(&a) && nullable(&b));
+
+    let mut r#enum = match (a.r#enum, b.r#enum) {
+        (Some(x), Some(y)) => Some(
+            x.into_iter()
+                .filter(|v| y.iter().any(|w| equal(v, w)))
+                .collect::<Vec<_>>(),
+        ),
+        (x, y) => x.or(y),
+    };
+    if let (Some(values), Some(types)) = (&mut r#enum, &r#type) {
+        let types = match types {
+            SchemaTypes::Unit(t) => vec![t.clone()],
+            SchemaTypes::Array(v) => v.clone(),
+        };
+        values.retain(|v| {
+            types.iter().any(|t| is_type(v, t)) || (*v == Any::Null && nullable == Some(true))
+        });
+    }
+    if r#enum.as_ref().is_some_and(|v| v.is_empty()) {
+        return Err(conflict("enum"));
+    }
+
+    let r#const = match (av.r#const, bv.r#const) {
+        (Some(x), Some(y)) if !equal(&x, &y) => return Err(conflict("const")),
+        (x, y) => x.or(y),
+    };
+
+    let multiple_of = match (av.multiple_of, bv.multiple_of) {
+        (Some(x), Some(y)) => Some(lcm(x, y)),
+        (x, y) => x.or(y),
+    };
+
+    // properties which are not declared in the other side must satisfy its `additionalProperties`.
+    let mut properties = a.properties.clone();
+    for (name, schema) in b.properties.iter().flatten() {
+        let ptr = pointer(&pointer(ptr, "properties"), name);
+        let merged = match (
+            a.properties.as_ref().and_then(|p| p.get(name)),
+            &a.additional_properties,
+        ) {
+            (Some(other), _) => merge_subschema(other.clone(), schema.clone(), &ptr)?,
+            (None, Some(additional)) => additional_property(schema, additional, &ptr)?,
+            (None, None) => schema.clone(),
+        };
+        properties
+            .get_or_insert_default()
+            .insert(name.clone(), merged);
+    }
+    for (name, schema) in a.properties.iter().flatten() {
+        if b.properties.as_ref().is_some_and(|p| p.contains_key(name)) {
+            continue;
+        }
+        if let Some(additional) = &b.additional_properties {
+            let ptr = pointer(&pointer(ptr, "properties"), name);
+            let merged = additional_property(schema, additional, &ptr)?;
+            properties
+                .get_or_insert_default()
+                .insert(name.clone(), merged);
+        }
+    }
+
+    let mut pattern_properties = aa.pattern_properties;
+    for (pattern, schema) in ba.pattern_properties.into_iter().flatten() {
+        let ptr = pointer(&pointer(ptr, "patternProperties"), &pattern);
+        let p = pattern_properties.get_or_insert_default();
+        let merged = match p.remove(&pattern) {
+            Some(other) => merge_subschema(other, schema, &ptr)?,
+            None => schema,
+        };
+        p.insert(pattern, merged);
+    }
+
+    let additional_properties = match (a.additional_properties, b.additional_properties) {
+        (Some(x), Some(y)) => Some(merge_subschema(
+            x,
+            y,
+            &pointer(ptr, "additionalProperties"),
+        )?),
+        (x, y) => x.or(y),
+    };
+
+    let items = match (a.items, b.items) {
+        (Some(x), Some(y)) => Some(merge_subschema(x, y, &pointer(ptr, "items"))?),
+        (x, y) => x.or(y),
+    };
+
+    let mut required = a.required;
+    for name in b.required.into_iter().flatten() {
+        let r = required.get_or_insert_default();
+        if !r.contains(&name) {
+            r.push(name);
+        }
+    }
+
+    let mut dependent_schemas = aa.dependent_schemas;
+    for (name, schema) in ba.dependent_schemas.into_iter().flatten() {
+        let ptr = pointer(&pointer(ptr, "dependentSchemas"), &name);
+        let d = dependent_schemas.get_or_insert_default();
+        let merged = match d.remove(&name) {
+            Some(other) => merge_subschema(other, schema, &ptr)?,
+            None => schema,
+        };
+        d.insert(name, merged);
+    }
+
+    let mut dependent_required = av.dependent_required;
+    for (name, names) in bv.dependent_required.into_iter().flatten() {
+        let d = dependent_required
+            .get_or_insert_default()
+            .entry(name)
+            .or_default();
+        for n in names {
+            if !d.contains(&n) {
+                d.push(n);
+            }
+        }
+    }
+
+    // `$defs` are only referenced, so the both are kept.
+    let mut defs = bm.defs;
+    if let Some(a) = am.defs {
+        defs.get_or_insert_default().extend(a);
+    }
+
+    let mut extensions = b.extensions;
+    extensions.values.extend(a.extensions.values);
+    extensions.extensions.extend(a.extensions.extensions);
+
+    let mut all_of = a.all_of;
+    all_of
+        .get_or_insert_default()
+        .extend(b.all_of.into_iter().flatten());
+
+    let validation = SchemaValidation {
+        r#const,
+        multiple_of,
+        maximum: upper(av.maximum, bv.maximum),
+        exclusive_maximum: upper(av.exclusive_maximum, bv.exclusive_maximum),
+        minimum: lower(av.minimum, bv.minimum),
+        exclusive_minimum: lower(av.exclusive_minimum, bv.exclusive_minimum),
+        max_length: upper(av.max_length, bv.max_length),
+        min_length: lower(av.min_length, bv.min_length),
+        pattern: keep(av.pattern, bv.pattern, &mut rest_validation.pattern),
+        max_items: upper(av.max_items, bv.max_items),
+        min_items: lower(av.min_items, bv.min_items),
+        unique_items: or(av.unique_items, bv.unique_items),
+        max_contains: upper(av.max_contains, bv.max_contains),
+        min_contains: lower(av.min_contains, bv.min_contains),
+        max_properties: upper(av.max_properties, bv.max_properties),
+        min_properties: lower(av.min_properties, bv.min_properties),
+        dependent_required,
+    };
+    let r = &mut rest_applicators;
+    let applicators = SchemaApplicators {
+        not: keep(aa.not, ba.not, &mut r.not),
+        r#if: keep(aa.r#if, ba.r#if, &mut r.r#if),
+        then: keep(aa.then, ba.then, &mut r.then),
+        r#else: keep(aa.r#else, ba.r#else, &mut r.r#else),
+        dependent_schemas,
+        prefix_items: keep(aa.prefix_items, ba.prefix_items, &mut r.prefix_items),
+        contains: keep(aa.contains, ba.contains, &mut r.contains),
+        pattern_properties,
+        property_names: keep(aa.property_names, ba.property_names, &mut r.property_names),
+        unevaluated_items: keep(
+            aa.unevaluated_items,
+            ba.unevaluated_items,
+            &mut r.unevaluated_items,
+        ),
+        unevaluated_properties: keep(
+            aa.unevaluated_properties,
+            ba.unevaluated_properties,
+            &mut r.unevaluated_properties,
+        ),
+    };
+    let metadata = SchemaMetadata {
+        schema: am.schema.or(bm.schema),
+        vocabulary: am.vocabulary.or(bm.vocabulary),
+        // the identifiers of `b` would change the base URI of `a`.
+        id: am.id,
+        anchor: am.anchor,
+        dynamic_anchor: am.dynamic_anchor,
+        dynamic_ref: keep(
+            am.dynamic_ref,
+            bm.dynamic_ref,
+            &mut rest_metadata.dynamic_ref,
+        ),
+        defs,
+        comment: am.comment.or(bm.comment),
+        deprecated: or(am.deprecated, bm.deprecated),
+        read_only: or(am.read_only, bm.read_only),
+        write_only: or(am.write_only, bm.write_only),
+        examples: am.examples.or(bm.examples),
+    };
+    let r = &mut rest_content;
+    let content = SchemaContent {
+        content_encoding: keep(
+            ac.content_encoding,
+            bc.content_encoding,
+            &mut r.content_encoding,
+        ),
+        content_media_type: keep(
+            ac.content_media_type,
+            bc.content_media_type,
+            &mut r.content_media_type,
+        ),
+        content_schema: keep(ac.content_schema, bc.content_schema, &mut r.content_schema),
+    };
+    let oas = SchemaOas {
+        discriminator: ao.discriminator.or(bo.discriminator),
+        xml: ao.xml.or(bo.xml),
+        external_docs: ao.external_docs.or(bo.external_docs),
+        example: ao.example.or(bo.example),
+    };
+
+    let merged = Schema {
+        nullable,
+        r#ref: None,
+        any_of: keep(a.any_of, b.any_of, &mut rest.any_of),
+        one_of: keep(a.one_of, b.one_of, &mut rest.one_of),
+        items,
+        properties,
+        additional_properties,
+        r#type,
+        r#enum,
+        required,
+        format: keep(a.format, b.format, &mut rest.format),
+        title: a.title.or(b.title),
+        description: a.description.or(b.description),
+        default: a.default.or(b.default),
+        validation: boxed(validation),
+        applicators: boxed(applicators),
+        metadata: boxed(metadata),
+        content: boxed(content),
+        oas: boxed(oas),
+        all_of,
+        extensions,
+    };
+    let mut merged = check_bounds(merged).map_err(conflict)?;
+
+    rest.validation = boxed(rest_validation);
+    rest.applicators = boxed(rest_applicators);
+    rest.metadata = boxed(rest_metadata);
+    rest.content = boxed(rest_content);
+    if rest != Schema::default() {
+        merged.all_of.get_or_insert_default().push(rest.into());
+    }
+    if merged.all_of.as_ref().is_some_and(|v| v.is_empty()) {
+        merged.all_of = None;
+    }
+    Ok(merged)
+}
+
+// `false` on either side rejects all values, and `true` adds nothing.
+fn merge_subschema(
+    a: Arc<SchemaOrBool>,
+    b: Arc<SchemaOrBool>,
+    ptr: &str,
+) -> Result<Arc<SchemaOrBool>, MergeError> {
+    match (a.as_ref(), b.as_ref()) {
+        (BooleanOr::Boolean(false), _) | (_, BooleanOr::Boolean(true)) => Ok(a),
+        (_, BooleanOr::Boolean(false)) | (BooleanOr::Boolean(true), _) => Ok(b),
+        (BooleanOr::Value(x), BooleanOr::Value(y)) => Ok(merge(x.clone(), y.clone(), ptr)?.into()),
+    }
+}
+
+// a property declared only in one side, with `additionalProperties` of the other.
+fn additional_property(
+    schema: &Arc<SchemaOrBool>,
+    additional: &Arc<SchemaOrBool>,
+    ptr: &str,
+) -> Result<Arc<SchemaOrBool>, MergeError> {
+    match additional.as_ref() {
+        BooleanOr::Boolean(false) if !schema.is_false() => Err(MergeError::Conflict {
+            pointer: ptr.to_string(),
+            keyword: "additionalProperties".to_string(),
+        }),
+        _ => merge_subschema(schema.clone(), additional.clone(), ptr),
+    }
+}
+
+fn check_bounds(s: Schema) -> Result<Schema, &'static str> {
+    fn over<T: PartialOrd>(min: Option<T>, max: Option<T>) -> bool {
+        matches!((min, max), (Some(min), Some(max)) if min > max)
+    }
+    fn touch<T: PartialOrd>(min: Option<T>, max: Option<T>) -> bool {
+        matches!((min, max), (Some(min), Some(max)) if min >= max)
+    }
+
+    if over(s.minimum(), s.maximum())
+        || touch(s.exclusive_minimum(), s.maximum())
+        || touch(s.minimum(), s.exclusive_maximum())
+        || touch(s.exclusive_minimum(), s.exclusive_maximum())
+    {
+        return Err("minimum");
+    }
+    if over(s.min_length(), s.max_length()) {
+        return Err("minLength");
+    }
+    if over(s.min_items(), s.max_items()) {
+        return Err("minItems");
+    }
+    if over(s.min_contains(), s.max_contains()) {
+        return Err("minContains");
+    }
+    if over(s.min_properties(), s.max_properties()) {
+        return Err("minProperties");
+    }
+    Ok(s)
+}
+
+fn types(s: &Schema) -> Option<Vec<SchemaType>> {
+    match s.r#type.as_ref()? {
+        SchemaTypes::Unit(t) => Some(vec![t.clone()]),
+        SchemaTypes::Array(v) => Some(v.clone()),
+    }
+}
+
+fn nullable(s: &Schema) -> bool {
+    match types(s) {
+        Some(types) => types.contains(&SchemaType::Null) || s.nullable == Some(true),
+        None => true,
+    }
+}
+
+fn ungroup<T: Default>(group: Option<Box<T>>) -> T {
+    group.map(|g| *g).unwrap_or_default()
+}
+
+// keep `a`, and move `b` to `rest` if both are given and different.
+fn keep<T: PartialEq>(a: Option<T>, b: Option<T>, rest: &mut Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            if a != b {
+                *rest = Some(b);
+            }
+            Some(a)
+        }
+        (a, b) => a.or(b),
+    }
+}
+
+fn lower<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn upper<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn or(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a || b),
+        (a, b) => a.or(b),
+    }
+}
+
+fn lcm(a: u32, b: u32) -> u32 {
+    fn gcd(a: u32, b: u32) -> u32 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+    match gcd(a, b) {
+        0 => 0,
+        g => a / g * b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenApi;
+
+    fn schema(s: &str) -> Schema {
+        serde_json::from_str(s).unwrap()
+    }
+
+    fn doc() -> OpenApi {
+        serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "schemas": {
+                    "Named": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {"name": {"type": "string", "maxLength": 10}}
+                    },
+                    "Loop": {"allOf": [{"$ref": "#/components/schemas/Loop"}]}
+                }
+            }
+        }"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn merge_all_of() {
+        let s = schema(
+            r##"{
+            "description": "pet",
+            "allOf": [
+                {"$ref": "#/components/schemas/Named"},
+                {
+                    "type": "object",
+                    "required": ["id", "name"],
+                    "properties": {
+                        "id": {"type": "number", "minimum": 1, "multipleOf": 2},
+                        "name": {"minLength": 1, "maxLength": 20, "enum": ["a", "b", 1]}
+                    }
+                },
+                {"properties": {"id": {"type": "integer", "minimum": 3, "multipleOf": 3}}}
+            ]
+        }"##,
+        );
+        let merged = s.merge_all_of(&doc()).unwrap();
+        assert_eq!(
+            schema(
+                r#"{
+                "description": "pet",
+                "type": "object",
+                "required": ["name", "id"],
+                "properties": {
+                    "id": {"type": "integer", "minimum": 3, "multipleOf": 6},
+                    "name": {"type": "string", "minLength": 1, "maxLength": 10, "enum": ["a", "b"]}
+                }
+            }"#
+            ),
+            merged
+        );
+    }
+
+    #[test]
+    fn merge_all_of_rest() {
+        let s = schema(r#"{"allOf": [{"pattern": "^a"}, {"pattern": "b$"}, {"pattern": "^a"}]}"#);
+        assert_eq!(
+            schema(r#"{"pattern": "^a", "allOf": [{"pattern": "b$"}]}"#),
+            s.merge_all_of(&()).unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_all_of_conflict() {
+        let s = schema(r#"{"allOf": [{"type": "string"}, {"type": "integer"}]}"#);
+        assert_eq!(
+            Err(MergeError::Conflict {
+                pointer: "".to_string(),
+                keyword: "type".to_string()
+            }),
+            s.merge_all_of(&())
+        );
+
+        let s = schema(
+            r#"{"allOf": [
+                {"properties": {"a": {"minimum": 5}}},
+                {"properties": {"a": {"maximum": 3}}}
+            ]}"#,
+        );
+        let e = s.merge_all_of(&()).unwrap_err();
+        assert_eq!(
+            "Conflicting `minimum` in `allOf` at /properties/a.",
+            e.to_string()
+        );
+
+        let s = schema(
+            r#"{"allOf": [
+                {"properties": {"a": {}}, "additionalProperties": false},
+                {"properties": {"b": {}}}
+            ]}"#,
+        );
+        assert_eq!(
+            Err(MergeError::Conflict {
+                pointer: "/properties/b".to_string(),
+                keyword: "additionalProperties".to_string()
+            }),
+            s.merge_all_of(&())
+        );
+
+        let s = schema(r#"{"allOf": [{"enum": [1, 2]}, {"enum": [3]}]}"#);
+        assert!(s.merge_all_of(&()).is_err());
+
+        let s = schema(r##"{"allOf": [{"$ref": "#/components/schemas/Loop"}]}"##);
+        assert_eq!(Err(MergeError::TooDeep), s.merge_all_of(&doc()));
+
+        let s = schema(r##"{"allOf": [{"$ref": "#/components/schemas/None"}]}"##);
+        assert_eq!(
+            Err(MergeError::NotFoundReference(
+                "#/components/schemas/None".to_string()
+            )),
+            s.merge_all_of(&doc())
+        );
+    }
+}
@@ -0,0 +1,847 @@
+use super::form::{FormCodec, FormError};
+use super::generate::Generator;
+use super::media_type::{MediaRange, negotiate, select_media_type};
+use super::model::Any;
+use super::resolve::SchemaIndex;
+use super::server::percent_decode;
+use super::validate::{ValidationError, Validator};
+use super::xml::XmlCodec;
+use super::{
+    Examples, MediaType, OpenApi, Operation, Parameter, ParameterIn, ParameterPattern, PathItem,
+    Response, Schema, SchemaType, SchemaTypes, Server,
+};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MockRequest {
+    pub method: String,
+    // path with query string.
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl MockResponse {
+    fn json(status: u16, body: &Any) -> Self {
+        MockResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: serde_json::to_vec(body).unwrap_or_default(),
+        }
+    }
+
+    fn problem(status: u16, title: &str, errors: &[String]) -> Self {
+        let mut body = HashMap::new();
+        body.insert("title".to_string(), Any::String(title.to_string()));
+        body.insert("status".to_string(), Any::Integer(status as i32));
+        if !errors.is_empty() {
+            let errors = errors.iter().map(|e| Any::String(e.clone())).collect();
+            body.insert("errors".to_string(), Any::Array(errors));
+        }
+        MockResponse::json(status, &Any::Object(body))
+    }
+}
+
+/// Serves responses described by a document.
+///
+/// Requests are routed by the path templates under the base paths of
+/// `servers`, and validated against parameters and request bodies. The
+/// response is the `Prefer: code=...` one if requested, otherwise the first
+/// successful one.
+pub struct MockServer<'a> {
+    doc: &'a OpenApi,
+    base_paths: Vec<String>,
+    index: SchemaIndex<'a>,
+}
+
+struct Route<'a> {
+    item: &'a PathItem,
+    op: &'a Operation,
+    params: HashMap<String, String>,
+}
+
+impl<'a> MockServer<'a> {
+    pub fn new(doc: &'a OpenApi) -> Self {
+        let mut base_paths = doc
+            .servers
+            .iter()
+            .flatten()
+            .map(base_path)
+            .collect::<Vec<String>>();
+        if base_paths.is_empty() {
+            base_paths.push("".to_string());
+        }
+        // longest base path first.
+        base_paths.sort_by_key(|b| std::cmp::Reverse(b.len()));
+        MockServer {
+            doc,
+            base_paths,
+            index: SchemaIndex::new(doc.components.as_ref()),
+        }
+    }
+
+    pub fn handle(&self, req: &MockRequest) -> MockResponse {
+        let (path, query) = match req.target.split_once('?') {
+            Some((p, q)) => (p, q),
+            None => (req.target.as_str(), ""),
+        };
+
+        let path = match self
+            .base_paths
+            .iter()
+            .find_map(|b| strip_base_path(path, b))
+        {
+            Some(path) => path,
+            None => return MockResponse::problem(404, "Not Found", &[]),
+        };
+
+        let route = match self.route(&path, &req.method) {
+            Ok(route) => route,
+            Err(status) => {
+                let title = if status == 405 {
+                    "Method Not Allowed"
+                } else {
+                    "Not Found"
+                };
+                return MockResponse::problem(status, title, &[]);
+            }
+        };
+
+        let errors = self.validate(req, &route, query);
+        if !errors.is_empty() {
+            return MockResponse::problem(400, "Bad Request", &errors);
+        }
+
+        self.respond(req, route.op)
+    }
+
+    fn route(&self, path: &str, method: &str) -> Result<Route<'a>, u16> {
+        let paths = match &self.doc.paths {
+            Some(paths) => paths,
+            None => return Err(404),
+        };
+
+        let mut candidates = paths
+            .values
+            .iter()
+            .filter_map(|(template, item)| {
+                match_template(template, path).map(|(params, literals)| (literals, item, params))
+            })
+            .collect::<Vec<_>>();
+        // prefer concrete path to templated path.
+        candidates.sort_by_key(|(literals, _, _)| std::cmp::Reverse(*literals));
+
+        if candidates.is_empty() {
+            return Err(404);
+        }
+
+        let lower = method.to_lowercase();
+        for (_, item, params) in candidates {
+            let op = item
+                .operations()
+                .into_iter()
+                .find(|(m, _)| *m == lower)
+                .map(|(_, op)| op)
+                // the names of additional operations are case-sensitive.
+                .or_else(|| item.additional_operations.as_ref()?.get(method));
+            if let Some(op) = op {
+                return Ok(Route { item, op, params });
+            }
+        }
+        Err(405)
+    }
+
+    fn validate(&self, req: &MockRequest, route: &Route<'a>, query: &str) -> Vec<String> {
+        let validator = Validator::new(&self.index);
+        let mut errors = vec![];
+
+        let querystring = query;
+        let query = parse_query(query);
+        let cookies = req
+            .header("cookie")
+            .map(|c| {
+                c.split(';')
+                    .filter_map(|c| c.trim().split_once('='))
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<HashMap<String, String>>()
+            })
+            .unwrap_or_default();
+
+        for parameter in self.parameters(route) {
+            let values: Vec<String> = match parameter.r#in {
+                ParameterIn::Path => route
+                    .params
+                    .get(&parameter.name)
+                    .cloned()
+                    .into_iter()
+                    .collect(),
+                ParameterIn::Query => query
+                    .iter()
+                    .filter(|(k, _)| *k == parameter.name)
+                    .map(|(_, v)| v.clone())
+                    .collect(),
+                ParameterIn::Header => req
+                    .headers
+                    .iter()
+                    .filter(|(k, _)| k.eq_ignore_ascii_case(&parameter.name))
+                    .map(|(_, v)| v.clone())
+                    .collect(),
+                ParameterIn::Cookie => cookies.get(&parameter.name).cloned().into_iter().collect(),
+                // the whole query string is one value.
+                ParameterIn::Querystring => Some(querystring.to_string())
+                    .filter(|q| !q.is_empty())
+                    .into_iter()
+                    .collect(),
+            };
+
+            let required = parameter
+                .required
+                .unwrap_or(parameter.r#in == ParameterIn::Path);
+            if values.is_empty() {
+                if required {
+                    errors.push(format!(
+                        "Missing required {} parameter `{}`.",
+                        in_name(&parameter.r#in),
+                        parameter.name
+                    ));
+                }
+                continue;
+            }
+
+            if let ParameterPattern::Style(style) = &parameter.pattern {
+                if let Some(schema) = &style.schema {
+                    let schema = schema.schema();
+                    let value = coerce(self.doc, schema, &values);
+                    for e in validator.validate(schema, &value) {
+                        errors.push(describe(&format!("parameter `{}`", parameter.name), &e));
+                    }
+                }
+            }
+        }
+
+        if let Some(body) = route
+            .op
+            .request_body
+            .as_ref()
+            .and_then(|b| self.doc.resolve(b))
+        {
+            if req.body.is_empty() {
+                if body.required == Some(true) {
+                    errors.push("Missing required request body.".to_string());
+                }
+                return errors;
+            }
+
+            let content_type = req.header("content-type").unwrap_or("application/json");
+            let media = match select_media_type(&body.content, content_type) {
+                Some((_, media)) => media,
+                None => {
+                    errors.push(format!("Unsupported content type `{content_type}`."));
+                    return errors;
+                }
+            };
+
+            if let Some(schema) = &media.schema {
+                let schema = schema.schema();
+                if is_json(content_type) {
+                    match serde_json::from_slice::<Any>(&req.body) {
+                        Ok(value) => {
+                            for e in validator.validate(schema, &value) {
+                                errors.push(describe("request body", &e));
+                            }
+                        }
+                        Err(e) => errors.push(format!("Invalid JSON request body: {e}")),
+                    }
+                } else if is_xml(content_type) {
+                    let xml = String::from_utf8_lossy(&req.body);
+                    match XmlCodec::new(self.doc).decode(schema, &xml) {
+                        Ok(value) => {
+                            for e in validator.validate(schema, &value) {
+                                errors.push(describe("request body", &e));
+                            }
+                        }
+                        Err(e) => errors.push(format!("Invalid XML request body: {e}")),
+                    }
+                } else if let Some(form) = decode_form(self.doc, media, content_type, &req.body) {
+                    match form {
+                        Ok(value) => {
+                            for e in validator.validate(schema, &value) {
+                                errors.push(describe("request body", &e));
+                            }
+                        }
+                        Err(e) => errors.push(format!("Invalid form request body: {e}")),
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+
+    // path level parameters overridden by operation level parameters.
+    fn parameters(&self, route: &Route<'a>) -> Vec<&'a Parameter> {
+        let mut parameters: Vec<&Parameter> = vec![];
+        let declared = route
+            .item
+            .parameters
+            .iter()
+            .flatten()
+            .chain(route.op.parameters.iter().flatten());
+        for p in declared {
+            if let Some(p) = self.doc.resolve(p) {
+                parameters.retain(|q| !(q.name == p.name && q.r#in == p.r#in));
+                parameters.push(p);
+            }
+        }
+        parameters
+    }
+
+    fn respond(&self, req: &MockRequest, op: &Operation) -> MockResponse {
+        let prefer = req.header("prefer").map(parse_prefer).unwrap_or_default();
+
+        let (status, response) = match select_response(self.doc, op, prefer.get("code")) {
+            Some(v) => v,
+            None => return MockResponse::problem(501, "Not Implemented", &[]),
+        };
+
+        let content = match &response.content {
+            Some(content) if !content.is_empty() => content,
+            _ => {
+                return MockResponse {
+                    status,
+                    ..Default::default()
+                };
+            }
+        };
+
+        let accept = req.header("accept").unwrap_or("*/*");
+        let (content_type, media) = match select_accept(content, accept) {
+            Some(v) => v,
+            None => return MockResponse::problem(406, "Not Acceptable", &[]),
+        };
+
+        let value = self.example(media, prefer.get("example").map(|e| e.as_str()));
+        let body = match (&value, &media.schema) {
+            (value, Some(schema)) if is_xml(content_type) => XmlCodec::new(self.doc)
+                .encode(schema.schema(), value)
+                .map(String::into_bytes)
+                .unwrap_or_default(),
+            (Any::String(s), _) if !is_json(content_type) => s.as_bytes().to_vec(),
+            (value, _) => serde_json::to_vec(value).unwrap_or_default(),
+        };
+
+        MockResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body,
+        }
+    }
+
+    fn example(&self, media: &MediaType, name: Option<&str>) -> Any {
+        match &media.example {
+            Examples::Value { example: Some(v) } => return v.clone(),
+            Examples::Values { examples } => {
+                let mut names = examples.keys().collect::<Vec<&String>>();
+                names.sort();
+                let name = name
+                    .and_then(|n| names.iter().find(|k| k.as_str() == n))
+                    .or(names.first());
+                let example = name
+                    .and_then(|n| self.doc.resolve(&examples[*n]))
+                    .and_then(|e| match &e.value {
+                        super::ExampleValue::Literal { value } => value.clone(),
+                        super::ExampleValue::Url { .. } => None,
+                    });
+                if let Some(v) = example {
+                    return v;
+                }
+            }
+            _ => {}
+        }
+
+        match &media.schema {
+            Some(schema) => Generator::new(&self.index).generate(schema.schema()),
+            None => Any::Null,
+        }
+    }
+}
+
+fn select_response<'a>(
+    doc: &'a OpenApi,
+    op: &'a Operation,
+    code: Option<&String>,
+) -> Option<(u16, &'a Response)> {
+    let responses = op.responses.as_ref()?;
+
+    if let Some(code) = code.and_then(|c| c.parse::<u16>().ok()) {
+        if let Some(r) = responses.statuses.values.get(&code) {
+            return Some((code, doc.resolve(r)?));
+        }
+        if let Some(r) = &responses.r#default {
+            return Some((code, doc.resolve(r)?));
+        }
+    }
+
+    let mut statuses = responses
+        .statuses
+        .values
+        .keys()
+        .copied()
+        .collect::<Vec<u16>>();
+    statuses.sort_unstable();
+    let status = statuses
+        .iter()
+        .find(|s| (200..300).contains(*s))
+        .or(statuses.first());
+
+    match status {
+        Some(s) => Some((*s, doc.resolve(&responses.statuses.values[s])?)),
+        None => Some((200, doc.resolve(responses.r#default.as_ref()?)?)),
+    }
+}
+
+// a range as the key is answered as an arbitrary binary.
+fn select_accept<'a>(
+    content: &'a HashMap<String, MediaType>,
+    accept: &str,
+) -> Option<(&'a str, &'a MediaType)> {
+    let (key, media) = negotiate(content, accept)?;
+    match MediaRange::parse(key) {
+        Ok(range) if range.is_range() => Some(("application/octet-stream", media)),
+        _ => Some((key, media)),
+    }
+}
+
+// `None` if `content_type` is not a form.
+fn decode_form(
+    doc: &OpenApi,
+    media: &MediaType,
+    content_type: &str,
+    body: &[u8],
+) -> Option<Result<Any, FormError>> {
+    let codec = FormCodec::new(doc);
+    match MediaRange::parse(content_type).ok()?.essence().as_str() {
+        "application/x-www-form-urlencoded" => {
+            Some(codec.decode_urlencoded(media, &String::from_utf8_lossy(body)))
+        }
+        "multipart/form-data" => Some(codec.decode_multipart(media, content_type, body)),
+        _ => None,
+    }
+}
+
+fn is_json(media_type: &str) -> bool {
+    MediaRange::parse(media_type).is_ok_and(|m| m.is_json())
+}
+
+fn is_xml(media_type: &str) -> bool {
+    MediaRange::parse(media_type).is_ok_and(|m| m.is_xml())
+}
+
+fn parse_prefer(value: &str) -> HashMap<String, String> {
+    value
+        .split([',', ';'])
+        .filter_map(|p| p.trim().split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().to_lowercase(),
+                v.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect()
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| match p.split_once('=') {
+            Some((k, v)) => (percent_decode(k), percent_decode(v)),
+            None => (percent_decode(p), "".to_string()),
+        })
+        .collect()
+}
+
+// match the path template and return path parameters and count of literal segments.
+fn match_template(template: &str, path: &str) -> Option<(HashMap<String, String>, usize)> {
+    let template = template
+        .trim_end_matches('/')
+        .split('/')
+        .collect::<Vec<&str>>();
+    let path = path.trim_end_matches('/').split('/').collect::<Vec<&str>>();
+    if template.len() != path.len() {
+        return None;
+    }
+
+    let mut params = HashMap::new();
+    let mut literals = 0;
+    for (t, p) in template.iter().zip(&path) {
+        if t.starts_with('{') && t.ends_with('}') {
+            if p.is_empty() {
+                return None;
+            }
+            params.insert(t[1..t.len() - 1].to_string(), percent_decode(p));
+        } else if t == p {
+            literals += 1;
+        } else {
+            return None;
+        }
+    }
+    Some((params, literals))
+}
+
+fn base_path(server: &Server) -> String {
+    let url = server.expand(&HashMap::new()).unwrap_or(server.url.clone());
+
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |i| &rest[i..]),
+        None => url.as_str(),
+    };
+    path.trim_end_matches('/').to_string()
+}
+
+fn strip_base_path(path: &str, base: &str) -> Option<String> {
+    let rest = path.strip_prefix(base)?;
+    if rest.is_empty() {
+        Some("/".to_string())
+    } else if rest.starts_with('/') {
+        Some(rest.to_string())
+    } else {
+        None
+    }
+}
+
+// convert the raw parameter values to the type of the schema.
+fn coerce(doc: &OpenApi, schema: &Schema, values: &[String]) -> Any {
+    let schema = match &schema.r#ref {
+        Some(r) => super::resolve::Resolver::resolve_schema(doc, None, r).unwrap_or(schema),
+        None => schema,
+    };
+
+    match schema_type(schema) {
+        Some(SchemaType::Array) => {
+            let items = values
+                .iter()
+                .flat_map(|v| v.split(','))
+                .map(|v| match &schema.items {
+                    Some(items) => coerce(doc, items.schema(), &[v.to_string()]),
+                    None => Any::String(v.to_string()),
+                })
+                .collect();
+            Any::Array(items)
+        }
+        ty => {
+            let value = &values[0];
+            match ty {
+                Some(SchemaType::Integer) => value
+                    .parse::<i32>()
+                    .map(Any::Integer)
+                    .unwrap_or(Any::String(value.clone())),
+                Some(SchemaType::Number) => value
+                    .parse::<f32>()
+                    .map(Any::Number)
+                    .unwrap_or(Any::String(value.clone())),
+                Some(SchemaType::Boolean) => value
+                    .parse::<bool>()
+                    .map(Any::Boolean)
+                    .unwrap_or(Any::String(value.clone())),
+                _ => Any::String(value.clone()),
+            }
+        }
+    }
+}
+
+fn schema_type(schema: &Schema) -> Option<SchemaType> {
+    match &schema.r#type {
+        Some(SchemaTypes::Unit(t)) => Some(t.clone()),
+        Some(SchemaTypes::Array(t)) => t.iter().find(|t| **t != SchemaType::Null).cloned(),
+        None => None,
+    }
+}
+
+fn in_name(r#in: &ParameterIn) -> &'static str {
+    match r#in {
+        ParameterIn::Query => "query",
+        ParameterIn::Header => "header",
+        ParameterIn::Path => "path",
+        ParameterIn::Cookie => "cookie",
+        ParameterIn::Querystring => "querystring",
+    }
+}
+
+fn describe(target: &str, e: &ValidationError) -> String {
+    if e.instance.is_empty() {
+        format!("{target}: {}", e.message)
+    } else {
+        format!("{target} at {}: {}", e.instance, e.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> OpenApi {
+        serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "servers": [{"url": "https://{host}/v1", "variables": {"host": {"default": "example.com"}}}],
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [
+                        {"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}}
+                    ],
+                    "get": {
+                        "parameters": [
+                            {"name": "fields", "in": "query", "schema": {"type": "array", "items": {"type": "string"}}}
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/Pet"},
+                                        "examples": {
+                                            "cat": {"value": {"id": 1, "name": "cat"}},
+                                            "dog": {"$ref": "#/components/examples/Dog"}
+                                        }
+                                    }
+                                }
+                            },
+                            "404": {"description": ""}
+                        }
+                    }
+                },
+                "/pets/mine": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "",
+                                "content": {
+                                    "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}},
+                                    "application/xml": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                                }
+                            }
+                        }
+                    }
+                },
+                "/pets": {
+                    "post": {
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}},
+                                "application/xml": {"schema": {"$ref": "#/components/schemas/Pet"}},
+                                "application/x-www-form-urlencoded": {"schema": {"$ref": "#/components/schemas/Pet"}}
+                            }
+                        },
+                        "responses": {"201": {"description": ""}}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["id", "name"],
+                        "properties": {"id": {"type": "integer"}, "name": {"type": "string", "example": "mine"}}
+                    }
+                },
+                "examples": {
+                    "Dog": {"value": {"id": 2, "name": "dog"}}
+                }
+            }
+        }"##,
+        )
+        .unwrap()
+    }
+
+    fn request(method: &str, target: &str, headers: &[(&str, &str)], body: &str) -> MockRequest {
+        MockRequest {
+            method: method.to_string(),
+            target: target.to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn body(r: &MockResponse) -> serde_json::Value {
+        serde_json::from_slice(&r.body).unwrap()
+    }
+
+    #[test]
+    fn mock_route() {
+        let v = doc();
+        let s = MockServer::new(&v);
+
+        let r = s.handle(&request("GET", "/v1/pets/1?fields=a,b", &[], ""));
+        assert_eq!(200, r.status);
+        assert_eq!(serde_json::json!({"id": 1, "name": "cat"}), body(&r));
+
+        let r = s.handle(&request("GET", "/v1/pets/mine", &[], ""));
+        assert_eq!(200, r.status);
+        assert_eq!(serde_json::json!({"id": 0, "name": "mine"}), body(&r));
+
+        assert_eq!(404, s.handle(&request("GET", "/pets/1", &[], "")).status);
+        assert_eq!(404, s.handle(&request("GET", "/v1/cats", &[], "")).status);
+        assert_eq!(
+            405,
+            s.handle(&request("DELETE", "/v1/pets/1", &[], "")).status
+        );
+    }
+
+    #[test]
+    fn mock_prefer() {
+        let v = doc();
+        let s = MockServer::new(&v);
+
+        let r = s.handle(&request(
+            "GET",
+            "/v1/pets/1",
+            &[("Prefer", "example=dog")],
+            "",
+        ));
+        assert_eq!(serde_json::json!({"id": 2, "name": "dog"}), body(&r));
+
+        let r = s.handle(&request("GET", "/v1/pets/1", &[("Prefer", "code=404")], ""));
+        assert_eq!(404, r.status);
+        assert!(r.body.is_empty());
+
+        let r = s.handle(&request(
+            "GET",
+            "/v1/pets/1",
+            &[("Accept", "text/html")],
+            "",
+        ));
+        assert_eq!(406, r.status);
+    }
+
+    #[test]
+    fn mock_validate() {
+        let v = doc();
+        let s = MockServer::new(&v);
+
+        let r = s.handle(&request("GET", "/v1/pets/a", &[], ""));
+        assert_eq!(400, r.status);
+
+        let r = s.handle(&request("POST", "/v1/pets", &[], ""));
+        assert_eq!(400, r.status);
+        assert_eq!(
+            serde_json::json!(["Missing required request body."]),
+            body(&r)["errors"]
+        );
+
+        let json = [("Content-Type", "application/json")];
+        let r = s.handle(&request("POST", "/v1/pets", &json, r#"{"id": "1"}"#));
+        assert_eq!(400, r.status);
+        assert_eq!(2, body(&r)["errors"].as_array().unwrap().len());
+
+        let r = s.handle(&request(
+            "POST",
+            "/v1/pets",
+            &json,
+            r#"{"id": 1, "name": "a"}"#,
+        ));
+        assert_eq!(201, r.status);
+
+        let xml = [("Content-Type", "application/xml")];
+        let r = s.handle(&request("POST", "/v1/pets", &xml, "<pet/>"));
+        assert_eq!(400, r.status);
+
+        let r = s.handle(&request(
+            "POST",
+            "/v1/pets",
+            &xml,
+            "<Pet><id>1</id><name>a</name></Pet>",
+        ));
+        assert_eq!(201, r.status);
+
+        let r = s.handle(&request("POST", "/v1/pets", &xml, "<Pet><id>"));
+        assert_eq!(400, r.status);
+
+        let form = [("Content-Type", "application/x-www-form-urlencoded")];
+        let r = s.handle(&request("POST", "/v1/pets", &form, "id=1&name=a"));
+        assert_eq!(201, r.status);
+        let r = s.handle(&request("POST", "/v1/pets", &form, "id=a&name=a"));
+        assert_eq!(400, r.status);
+    }
+
+    #[test]
+    fn mock_query_method() {
+        let v = serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.2.0",
+            "info": {"title": "", "version": ""},
+            "paths": {
+                "/search": {
+                    "query": {
+                        "parameters": [
+                            {
+                                "name": "q",
+                                "in": "querystring",
+                                "required": true,
+                                "content": {"application/x-www-form-urlencoded": {}}
+                            }
+                        ],
+                        "responses": {"200": {"description": ""}}
+                    },
+                    "additionalOperations": {
+                        "COPY": {"responses": {"204": {"description": ""}}}
+                    }
+                }
+            }
+        }"##,
+        )
+        .unwrap();
+        let s = MockServer::new(&v);
+
+        assert_eq!(
+            200,
+            s.handle(&request("QUERY", "/search?a=1", &[], "")).status
+        );
+        let r = s.handle(&request("QUERY", "/search", &[], ""));
+        assert_eq!(400, r.status);
+        assert_eq!(
+            serde_json::json!(["Missing required querystring parameter `q`."]),
+            body(&r)["errors"]
+        );
+        assert_eq!(204, s.handle(&request("COPY", "/search", &[], "")).status);
+        assert_eq!(405, s.handle(&request("copy", "/search", &[], "")).status);
+    }
+
+    #[test]
+    fn mock_xml() {
+        let v = doc();
+        let s = MockServer::new(&v);
+        let r = s.handle(&request(
+            "GET",
+            "/v1/pets/mine",
+            &[("Accept", "application/xml")],
+            "",
+        ));
+        assert_eq!(200, r.status);
+        assert_eq!(
+            "<Pet><id>0</id><name>mine</name></Pet>",
+            String::from_utf8_lossy(&r.body)
+        );
+    }
+}
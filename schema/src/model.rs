@@ -1,3 +1,4 @@
+use serde::de::DeserializeOwned;
 use serde::de::{MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -27,6 +28,22 @@ pub struct Extensions {
     pub values: HashMap<String, Any>,
 }
 
+impl Extensions {
+    /// The value of the extension `name`, with or without `x-`, as `T`.
+    pub fn get_as<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, serde_json::Error> {
+        get_as(&self.values, name)
+    }
+
+    /// Set the extension `name`, with or without `x-`, to `value`.
+    pub fn set_from<T: Serialize>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> Result<(), serde_json::Error> {
+        set_from(&mut self.values, name, value)
+    }
+}
+
 #[derive(Default)]
 struct ExtensionsVisitor;
 
@@ -172,6 +189,22 @@ impl<T> Default for KeyValues<T> {
     }
 }
 
+impl<T> KeyValues<T> {
+    /// The value of the extension `name`, with or without `x-`, as `U`.
+    pub fn get_as<U: DeserializeOwned>(&self, name: &str) -> Result<Option<U>, serde_json::Error> {
+        get_as(&self.extensions, name)
+    }
+
+    /// Set the extension `name`, with or without `x-`, to `value`.
+    pub fn set_from<U: Serialize>(
+        &mut self,
+        name: &str,
+        value: &U,
+    ) -> Result<(), serde_json::Error> {
+        set_from(&mut self.extensions, name, value)
+    }
+}
+
 struct KeyValuesVisitor<T> {
     marker: PhantomData<KeyValues<T>>,
 }
@@ -243,3 +276,30 @@ where
         deserializer.deserialize_map(KeyValuesVisitor::default())
     }
 }
+
+// ---------------------------------------------------------------------------
+
+// extensions are stored without `x-`.
+fn get_as<T: DeserializeOwned>(
+    extensions: &HashMap<String, Any>,
+    name: &str,
+) -> Result<Option<T>, serde_json::Error> {
+    let name = name.strip_prefix("x-").unwrap_or(name);
+    match extensions.get(name) {
+        Some(v) => serde_json::to_value(v)
+            .and_then(serde_json::from_value)
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+fn set_from<T: Serialize>(
+    extensions: &mut HashMap<String, Any>,
+    name: &str,
+    value: &T,
+) -> Result<(), serde_json::Error> {
+    let name = name.strip_prefix("x-").unwrap_or(name);
+    let value = serde_json::to_value(value).and_then(serde_json::from_value)?;
+    extensions.insert(name.to_string(), value);
+    Ok(())
+}
@@ -0,0 +1,541 @@
+use super::{
+    Callback, Components, Example, Header, Link, OpenApi, Parameter, PartOpenApi, PathItem,
+    ReferenceOr, RequestBody, Response, Schema, SecurityScheme,
+};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const MAX_DEPTH: usize = 32;
+
+/// Looks up the schema that a `$ref` points to.
+pub trait Resolver {
+    /// `base` is the URI of the enclosing `$id`, which `reference` is
+    /// relative to.
+    fn resolve_schema(&self, base: Option<&str>, reference: &str) -> Option<&Schema>;
+}
+
+/// Indexes `$id` and `$anchor` in each lookup. Use [`SchemaIndex`] for many
+/// lookups.
+impl Resolver for Components {
+    fn resolve_schema(&self, base: Option<&str>, reference: &str) -> Option<&Schema> {
+        SchemaIndex::new(Some(self)).get(base, reference)
+    }
+}
+
+impl Resolver for OpenApi {
+    fn resolve_schema(&self, base: Option<&str>, reference: &str) -> Option<&Schema> {
+        self.components.as_ref()?.resolve_schema(base, reference)
+    }
+}
+
+impl Resolver for PartOpenApi {
+    fn resolve_schema(&self, base: Option<&str>, reference: &str) -> Option<&Schema> {
+        self.components.as_ref()?.resolve_schema(base, reference)
+    }
+}
+
+impl Resolver for () {
+    fn resolve_schema(&self, _base: Option<&str>, _reference: &str) -> Option<&Schema> {
+        None
+    }
+}
+
+/// The schemas of `components`, whose `$id` and `$anchor` are indexed once
+/// on the first lookup by them.
+pub struct SchemaIndex<'a> {
+    schemas: Option<&'a HashMap<String, Arc<Schema>>>,
+    // by the URI and `#` with the anchor, if any.
+    resources: OnceCell<HashMap<String, &'a Schema>>,
+}
+
+impl<'a> SchemaIndex<'a> {
+    pub fn new(components: Option<&'a Components>) -> Self {
+        SchemaIndex {
+            schemas: components.and_then(|c| c.schemas.as_ref()),
+            resources: OnceCell::new(),
+        }
+    }
+
+    fn get(&self, base: Option<&str>, reference: &str) -> Option<&'a Schema> {
+        let schemas = self.schemas?;
+        if let Some(name) = component_name(reference, "schemas") {
+            return schemas.get(&name).map(Arc::as_ref);
+        }
+        // pointers into `components` are not relative to `$id`.
+        if let Some(fragment) = reference.strip_prefix("#/components/schemas/") {
+            let tokens = fragment.split('/').map(unescape).collect::<Vec<_>>();
+            return subschema(schemas.get(tokens.first()?)?, &tokens[1..]);
+        }
+
+        let reference = match base {
+            Some(base) => join(base, reference),
+            None => reference.to_string(),
+        };
+        let (uri, fragment) = reference.split_once('#').unwrap_or((&reference, ""));
+        let key = match fragment {
+            "" => uri.to_string(),
+            f if f.starts_with('/') => uri.to_string(),
+            f => format!("{uri}#{f}"),
+        };
+        let schema = *self.resources().get(&key)?;
+        match fragment.strip_prefix('/') {
+            Some(p) => subschema(schema, &p.split('/').map(unescape).collect::<Vec<_>>()),
+            None => Some(schema),
+        }
+    }
+
+    fn resources(&self) -> &HashMap<String, &'a Schema> {
+        self.resources.get_or_init(|| {
+            let mut resources = HashMap::new();
+            if let Some(schemas) = self.schemas {
+                let mut names = schemas.keys().collect::<Vec<_>>();
+                names.sort();
+                for name in names {
+                    index("", &schemas[name], &mut resources);
+                }
+            }
+            resources
+        })
+    }
+}
+
+impl Resolver for SchemaIndex<'_> {
+    fn resolve_schema(&self, base: Option<&str>, reference: &str) -> Option<&Schema> {
+        self.get(base, reference)
+    }
+}
+
+/// The base URI of the schema which `reference` relative to `base` points to.
+///
+/// Schemas in `components` have no base URI but their own `$id`.
+pub fn ref_base(base: Option<&str>, reference: &str) -> Option<String> {
+    if reference.starts_with("#/components/") || component_name(reference, "schemas").is_some() {
+        return None;
+    }
+    let reference = match base {
+        Some(base) => join(base, reference),
+        None => reference.to_string(),
+    };
+    let uri = reference
+        .split_once('#')
+        .map_or(reference.as_str(), |(u, _)| u);
+    (!uri.is_empty()).then(|| uri.to_string())
+}
+
+/// Objects which can be defined in `components` and referenced by `$ref`.
+pub trait Component: Sized {
+    const KIND: &'static str;
+
+    fn components(components: &Components) -> Option<&HashMap<String, ReferenceOr<Self>>>;
+}
+
+macro_rules! component {
+    ($ty:ty, $kind:literal, $field:ident) => {
+        impl Component for $ty {
+            const KIND: &'static str = $kind;
+
+            fn components(components: &Components) -> Option<&HashMap<String, ReferenceOr<Self>>> {
+                components.$field.as_ref()
+            }
+        }
+    };
+}
+
+component!(Response, "responses", responses);
+component!(Parameter, "parameters", parameters);
+component!(Example, "examples", examples);
+component!(RequestBody, "requestBodies", request_bodies);
+component!(Header, "headers", headers);
+component!(SecurityScheme, "securitySchemes", security_schemes);
+component!(Link, "links", links);
+component!(Callback, "callbacks", callbacks);
+component!(PathItem, "pathItems", path_items);
+
+impl Components {
+    /// Follow `$ref` to the component object.
+    pub fn resolve<'a, T: Component>(&'a self, value: &'a ReferenceOr<T>) -> Option<&'a T> {
+        let mut value = value;
+        for _ in 0..MAX_DEPTH {
+            match value {
+                ReferenceOr::Value(v) => return Some(v),
+                ReferenceOr::Ref(r) => {
+                    let name = component_name(&r.r#ref, T::KIND)?;
+                    value = T::components(self)?.get(&name)?;
+                }
+            }
+        }
+        None
+    }
+}
+
+impl OpenApi {
+    pub fn resolve<'a, T: Component>(&'a self, value: &'a ReferenceOr<T>) -> Option<&'a T> {
+        match (value, &self.components) {
+            (ReferenceOr::Value(v), _) => Some(v),
+            (_, Some(components)) => components.resolve(value),
+            (_, None) => None,
+        }
+    }
+}
+
+/// Get the component name from a local reference like `#/components/schemas/Pet`.
+pub fn component_name(reference: &str, kind: &str) -> Option<String> {
+    let (_, fragment) = reference.split_once('#')?;
+    let mut tokens = fragment.strip_prefix('/')?.split('/').map(unescape);
+    if tokens.next()? != "components" || tokens.next()? != kind {
+        return None;
+    }
+
+    let name = tokens.next()?;
+    if tokens.next().is_some() {
+        return None;
+    }
+
+    Some(name)
+}
+
+/// Resolve `reference` against the base URI `base` (RFC 3986, section 5.2).
+pub fn join(base: &str, reference: &str) -> String {
+    let base = base.split_once('#').map_or(base, |(b, _)| b);
+    if reference.is_empty() {
+        return base.to_string();
+    }
+    if reference.starts_with('#') {
+        return format!("{base}{reference}");
+    }
+    if has_scheme(reference) {
+        return reference.to_string();
+    }
+
+    let (scheme, rest) = match base.split_once(':') {
+        Some((scheme, rest)) if has_scheme(base) => (&base[..=scheme.len()], rest),
+        _ => ("", base),
+    };
+    if reference.starts_with("//") {
+        return format!("{scheme}{reference}");
+    }
+    let (authority, path) = match rest.strip_prefix("//") {
+        Some(r) => {
+            let i = r.find('/').unwrap_or(r.len());
+            (&rest[..i + 2], &r[i..])
+        }
+        None => ("", rest),
+    };
+    let path = path.split_once('?').map_or(path, |(p, _)| p);
+
+    let i = reference.find(['?', '#']).unwrap_or(reference.len());
+    let (relative, suffix) = reference.split_at(i);
+    let merged = if relative.starts_with('/') {
+        relative.to_string()
+    } else {
+        match path.rfind('/') {
+            Some(i) => format!("{}{relative}", &path[..=i]),
+            None if !authority.is_empty() => format!("/{relative}"),
+            None => relative.to_string(),
+        }
+    };
+    format!("{scheme}{authority}{}{suffix}", remove_dots(&merged))
+}
+
+fn has_scheme(uri: &str) -> bool {
+    match uri.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+fn remove_dots(path: &str) -> String {
+    let (root, body) = match path.strip_prefix('/') {
+        Some(body) => ("/", body),
+        None => ("", path),
+    };
+    let mut segments = vec![];
+    let mut last = "";
+    for segment in body.split('/') {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+        last = segment;
+    }
+    if last == "." || last == ".." {
+        segments.push("");
+    }
+    format!("{root}{}", segments.join("/"))
+}
+
+// collect the resources in `schema`, with the base URIs changed by `$id`.
+// the first one wins for the same URI.
+fn index<'a>(base: &str, schema: &'a Schema, resources: &mut HashMap<String, &'a Schema>) {
+    let base = match schema.id() {
+        Some(id) => {
+            let uri = join(base, id);
+            resources.entry(uri.clone()).or_insert(schema);
+            uri
+        }
+        None => base.to_string(),
+    };
+    for anchor in [schema.anchor(), schema.dynamic_anchor()]
+        .into_iter()
+        .flatten()
+    {
+        resources
+            .entry(format!("{base}#{anchor}"))
+            .or_insert(schema);
+    }
+    for s in subschemas(schema) {
+        index(&base, s, resources);
+    }
+}
+
+fn subschemas(s: &Schema) -> Vec<&Schema> {
+    let mut schemas = vec![];
+    for v in [
+        s.all_of.as_ref(),
+        s.any_of.as_ref(),
+        s.one_of.as_ref(),
+        s.prefix_items(),
+    ] {
+        schemas.extend(v.into_iter().flatten());
+    }
+    for v in [
+        s.not(),
+        s.r#if(),
+        s.then(),
+        s.r#else(),
+        s.items.as_ref(),
+        s.contains(),
+        s.property_names(),
+        s.content_schema(),
+        s.additional_properties.as_ref(),
+        s.unevaluated_items(),
+        s.unevaluated_properties(),
+    ] {
+        schemas.extend(v);
+    }
+    for v in [
+        s.properties.as_ref(),
+        s.pattern_properties(),
+        s.dependent_schemas(),
+        s.defs(),
+    ] {
+        let mut names = v.iter().flat_map(|m| m.keys()).collect::<Vec<_>>();
+        names.sort();
+        schemas.extend(names.into_iter().filter_map(|n| v?.get(n)));
+    }
+    schemas.into_iter().map(|s| s.schema()).collect()
+}
+
+// follow JSON pointer tokens from `schema`.
+fn subschema<'a>(schema: &'a Schema, tokens: &[String]) -> Option<&'a Schema> {
+    let (s, rest) = match tokens {
+        [] => return Some(schema),
+        [key, name, rest @ ..] if key == "properties" => {
+            (schema.properties.as_ref()?.get(name)?, rest)
+        }
+        [key, name, rest @ ..] if key == "patternProperties" => {
+            (schema.pattern_properties()?.get(name)?, rest)
+        }
+        [key, name, rest @ ..] if key == "dependentSchemas" => {
+            (schema.dependent_schemas()?.get(name)?, rest)
+        }
+        [key, name, rest @ ..] if key == "$defs" => (schema.defs()?.get(name)?, rest),
+        [key, i, rest @ ..]
+            if ["allOf", "anyOf", "oneOf", "prefixItems"].contains(&key.as_str()) =>
+        {
+            let schemas = match key.as_str() {
+                "allOf" => schema.all_of.as_ref(),
+                "anyOf" => schema.any_of.as_ref(),
+                "oneOf" => schema.one_of.as_ref(),
+                _ => schema.prefix_items(),
+            };
+            (schemas?.get(i.parse::<usize>().ok()?)?, rest)
+        }
+        [key, rest @ ..] => {
+            let s = match key.as_str() {
+                "not" => schema.not(),
+                "if" => schema.r#if(),
+                "then" => schema.then(),
+                "else" => schema.r#else(),
+                "items" => schema.items.as_ref(),
+                "contains" => schema.contains(),
+                "propertyNames" => schema.property_names(),
+                "contentSchema" => schema.content_schema(),
+                "additionalProperties" => schema.additional_properties.as_ref(),
+                "unevaluatedItems" => schema.unevaluated_items(),
+                "unevaluatedProperties" => schema.unevaluated_properties(),
+                _ => return None,
+            };
+            (s?, rest)
+        }
+    };
+    subschema(s.schema(), rest)
+}
+
+/// Decode a JSON pointer reference token.
+pub fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Reference, SchemaType, SchemaTypes};
+
+    #[test]
+    fn resolve_component_name() {
+        assert_eq!(
+            Some("a/b".to_string()),
+            component_name("#/components/schemas/a~1b", "schemas")
+        );
+        assert_eq!(
+            Some("A".to_string()),
+            component_name("openapi.yaml#/components/schemas/A", "schemas")
+        );
+        assert_eq!(None, component_name("#/components/responses/A", "schemas"));
+        assert_eq!(None, component_name("#/components/schemas/A/b", "schemas"));
+        assert_eq!(None, component_name("A.yaml", "schemas"));
+    }
+
+    #[test]
+    fn resolve_component() {
+        let mut responses = HashMap::new();
+        responses.insert(
+            "A".to_string(),
+            ReferenceOr::Ref(Reference {
+                r#ref: "#/components/responses/B".to_string(),
+                ..Default::default()
+            }),
+        );
+        responses.insert(
+            "B".to_string(),
+            ReferenceOr::Value(Response {
+                description: "b".to_string(),
+                ..Default::default()
+            }),
+        );
+        let v = OpenApi {
+            components: Some(Components {
+                responses: Some(responses),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let r = ReferenceOr::<Response>::Ref(Reference {
+            r#ref: "#/components/responses/A".to_string(),
+            ..Default::default()
+        });
+        assert_eq!("b", v.resolve(&r).unwrap().description);
+
+        let r = ReferenceOr::<Response>::Ref(Reference {
+            r#ref: "#/components/responses/C".to_string(),
+            ..Default::default()
+        });
+        assert!(v.resolve(&r).is_none());
+    }
+
+    #[test]
+    fn resolve_join() {
+        let base = "https://example.com/a/b.json";
+        assert_eq!("https://example.com/c.json", join(base, "../c.json"));
+        assert_eq!("https://example.com/a/c.json#d", join(base, "./c.json#d"));
+        assert_eq!("https://example.com/e", join(base, "/e"));
+        assert_eq!("https://other.com/f", join(base, "//other.com/f"));
+        assert_eq!(
+            "https://example.com/a/b.json#g",
+            join("https://example.com/a/b.json#h", "#g")
+        );
+        assert_eq!("urn:uuid:1", join(base, "urn:uuid:1"));
+        assert_eq!("c.json", join("", "c.json"));
+        assert_eq!("https://example.com/x", join("https://example.com", "x"));
+    }
+
+    #[test]
+    fn resolve_id_and_anchor() {
+        let v = serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "schemas": {
+                    "A": {
+                        "$id": "https://example.com/schemas/a",
+                        "$defs": {"b": {"$anchor": "bee", "type": "string"}},
+                        "properties": {"c": {"$id": "c.json", "type": "integer"}}
+                    },
+                    "D": {"$anchor": "top", "type": "boolean"}
+                }
+            }
+        }"##,
+        )
+        .unwrap();
+
+        let id = |r: &str| v.resolve_schema(None, r).and_then(|s| s.r#type.clone());
+        assert!(
+            v.resolve_schema(None, "https://example.com/schemas/a")
+                .is_some()
+        );
+        let string = Some(SchemaTypes::Unit(SchemaType::String));
+        assert_eq!(string, id("https://example.com/schemas/a#bee"));
+        assert_eq!(string, id("https://example.com/schemas/a#/$defs/b"));
+        assert_eq!(string, id("#/components/schemas/A/$defs/b"));
+        assert_eq!(
+            Some(SchemaTypes::Unit(SchemaType::Integer)),
+            id("https://example.com/schemas/c.json")
+        );
+        assert_eq!(Some(SchemaTypes::Unit(SchemaType::Boolean)), id("#top"));
+        assert!(v.resolve_schema(None, "#bee").is_none());
+        assert!(
+            v.resolve_schema(None, "https://example.com/schemas/b")
+                .is_none()
+        );
+
+        // relative to the enclosing `$id`.
+        let index = SchemaIndex::new(v.components.as_ref());
+        let base = Some("https://example.com/schemas/a");
+        let relative = |r: &str| index.resolve_schema(base, r).and_then(|s| s.r#type.clone());
+        assert_eq!(string, relative("#bee"));
+        assert_eq!(string, relative("#/$defs/b"));
+        assert_eq!(string, relative("a#bee"));
+        assert_eq!(
+            Some(SchemaTypes::Unit(SchemaType::Integer)),
+            relative("c.json")
+        );
+        assert_eq!(
+            Some(SchemaTypes::Unit(SchemaType::Boolean)),
+            relative("#/components/schemas/D")
+        );
+        assert!(index.resolve_schema(base, "#top").is_none());
+        assert_eq!(
+            Some("https://example.com/schemas/c.json".to_string()),
+            ref_base(base, "c.json#/type")
+        );
+        assert_eq!(None, ref_base(base, "#/components/schemas/D"));
+        assert_eq!(None, ref_base(None, "#top"));
+    }
+
+    #[test]
+    fn resolve_openapi() {
+        let mut schemas = HashMap::new();
+        schemas.insert("A".to_string(), Arc::new(Schema::default()));
+        let v = OpenApi {
+            components: Some(Components {
+                schemas: Some(schemas),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(v.resolve_schema(None, "#/components/schemas/A").is_some());
+        assert!(v.resolve_schema(None, "#/components/schemas/B").is_none());
+    }
+}
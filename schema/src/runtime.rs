@@ -0,0 +1,495 @@
+use super::model::{Any, KeyValues};
+use super::resolve::unescape;
+use super::server::percent_decode;
+use super::{Link, LinkOperation, OpenApi, Operation, PathItem, ReferenceOr};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+// https://spec.openapis.org/oas/v3.1.0#runtime-expressions
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Url,
+    Method,
+    StatusCode,
+    Request(Source),
+    Response(Source),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Header(String),
+    Query(String),
+    Path(String),
+    // JSON pointer in the body.
+    Body(Option<String>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionError {
+    Invalid(String),
+    // the value is not in the exchange.
+    Unresolved(String),
+    NotFoundOperation(String),
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpressionError::Invalid(e) => write!(f, "Invalid runtime expression `{e}`."),
+            ExpressionError::Unresolved(e) => write!(f, "Failed to evaluate `{e}`."),
+            ExpressionError::NotFoundOperation(o) => write!(f, "Not found operation `{o}`."),
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ExpressionError::Invalid(s.to_string());
+        match s {
+            "$url" => return Ok(Expression::Url),
+            "$method" => return Ok(Expression::Method),
+            "$statusCode" => return Ok(Expression::StatusCode),
+            _ => {}
+        }
+
+        let (source, f): (&str, fn(Source) -> Expression) =
+            if let Some(source) = s.strip_prefix("$request.") {
+                (source, Expression::Request)
+            } else if let Some(source) = s.strip_prefix("$response.") {
+                (source, Expression::Response)
+            } else {
+                return Err(invalid());
+            };
+
+        let source = if let Some(token) = source.strip_prefix("header.") {
+            if token.is_empty() || !token.chars().all(is_tchar) {
+                return Err(invalid());
+            }
+            Source::Header(token.to_string())
+        } else if let Some(name) = source.strip_prefix("query.") {
+            Source::Query(name.to_string())
+        } else if let Some(name) = source.strip_prefix("path.") {
+            Source::Path(name.to_string())
+        } else if source == "body" {
+            Source::Body(None)
+        } else if let Some(pointer) = source.strip_prefix("body#") {
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                return Err(invalid());
+            }
+            Source::Body(Some(pointer.to_string()))
+        } else {
+            return Err(invalid());
+        };
+        Ok(f(source))
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (prefix, source) = match self {
+            Expression::Url => return f.write_str("$url"),
+            Expression::Method => return f.write_str("$method"),
+            Expression::StatusCode => return f.write_str("$statusCode"),
+            Expression::Request(source) => ("$request", source),
+            Expression::Response(source) => ("$response", source),
+        };
+        match source {
+            Source::Header(t) => write!(f, "{prefix}.header.{t}"),
+            Source::Query(n) => write!(f, "{prefix}.query.{n}"),
+            Source::Path(n) => write!(f, "{prefix}.path.{n}"),
+            Source::Body(None) => write!(f, "{prefix}.body"),
+            Source::Body(Some(p)) => write!(f, "{prefix}.body#{p}"),
+        }
+    }
+}
+
+// https://www.rfc-editor.org/rfc/rfc7230#section-3.2.6
+fn is_tchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HttpRequest {
+    // URL with query string.
+    pub url: String,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+    pub path: HashMap<String, String>,
+    pub body: Option<Any>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Any>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exchange<'a> {
+    pub request: &'a HttpRequest,
+    // not available for callbacks.
+    pub response: Option<&'a HttpResponse>,
+}
+
+impl Expression {
+    pub fn evaluate(&self, exchange: &Exchange) -> Option<Any> {
+        let request = exchange.request;
+        let (headers, body, source) = match self {
+            Expression::Url => return Some(Any::String(request.url.clone())),
+            Expression::Method => return Some(Any::String(request.method.to_uppercase())),
+            Expression::StatusCode => {
+                return exchange.response.map(|r| Any::Integer(r.status as i32));
+            }
+            Expression::Request(source) => (&request.headers, &request.body, source),
+            Expression::Response(source) => {
+                let response = exchange.response?;
+                (&response.headers, &response.body, source)
+            }
+        };
+
+        match source {
+            Source::Header(name) => headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| Any::String(v.clone())),
+            Source::Query(name) => {
+                if !matches!(self, Expression::Request(_)) {
+                    return None;
+                }
+                let (_, query) = request.url.split_once('?')?;
+                let query = query.split('#').next().unwrap_or("");
+                query
+                    .split('&')
+                    .map(|p| p.split_once('=').unwrap_or((p, "")))
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| Any::String(percent_decode(v)))
+            }
+            Source::Path(name) => {
+                if !matches!(self, Expression::Request(_)) {
+                    return None;
+                }
+                request.path.get(name).map(|v| Any::String(v.clone()))
+            }
+            Source::Body(pointer) => {
+                let body = body.as_ref()?;
+                match pointer {
+                    Some(p) => json_pointer(body, p).cloned(),
+                    None => Some(body.clone()),
+                }
+            }
+        }
+    }
+}
+
+fn json_pointer<'a>(value: &'a Any, pointer: &str) -> Option<&'a Any> {
+    if pointer.is_empty() {
+        return Some(value);
+    }
+
+    let mut value = value;
+    for token in pointer.strip_prefix('/')?.split('/').map(unescape) {
+        value = match value {
+            Any::Object(map) => map.get(&token)?,
+            Any::Array(items) => items.get(token.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplatePart {
+    Literal(String),
+    Expression(Expression),
+}
+
+/// Parse a string with embedded `{expression}`s, like callback keys.
+pub fn parse_template(s: &str) -> Result<Vec<TemplatePart>, ExpressionError> {
+    let mut parts = vec![];
+    let mut rest = s;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| ExpressionError::Invalid(s.to_string()))?;
+        if start > 0 {
+            parts.push(TemplatePart::Literal(rest[..start].to_string()));
+        }
+        let expression = rest[start + 1..start + end].parse::<Expression>()?;
+        parts.push(TemplatePart::Expression(expression));
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest.to_string()));
+    }
+    Ok(parts)
+}
+
+/// Evaluate a string with embedded `{expression}`s.
+pub fn evaluate_template(s: &str, exchange: &Exchange) -> Result<String, ExpressionError> {
+    let mut out = String::new();
+    for part in parse_template(s)? {
+        match part {
+            TemplatePart::Literal(l) => out.push_str(&l),
+            TemplatePart::Expression(e) => match e.evaluate(exchange) {
+                Some(Any::String(v)) => out.push_str(&v),
+                Some(v) => out.push_str(&serde_json::to_string(&v).unwrap_or_default()),
+                None => return Err(ExpressionError::Unresolved(e.to_string())),
+            },
+        }
+    }
+    Ok(out)
+}
+
+// a value of `Link` is a constant, an expression or a string with embedded expressions.
+fn evaluate_value(value: &Any, exchange: &Exchange) -> Result<Any, ExpressionError> {
+    match value {
+        Any::String(s) if s.starts_with('$') => {
+            let e = s.parse::<Expression>()?;
+            e.evaluate(exchange)
+                .ok_or_else(|| ExpressionError::Unresolved(s.clone()))
+        }
+        Any::String(s) if s.contains('{') => Ok(Any::String(evaluate_template(s, exchange)?)),
+        value => Ok(value.clone()),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkedRequest<'a> {
+    pub path: &'a str,
+    pub method: &'static str,
+    pub operation: &'a Operation,
+    // names may be qualified by the location like `path.id`.
+    pub parameters: HashMap<String, Any>,
+    pub body: Option<Any>,
+}
+
+impl Link {
+    /// Evaluate `parameters` and `requestBody` over the exchange.
+    pub fn evaluate(
+        &self,
+        exchange: &Exchange,
+    ) -> Result<(HashMap<String, Any>, Option<Any>), ExpressionError> {
+        let mut parameters = HashMap::new();
+        for (name, value) in self.parameters.iter().flatten() {
+            parameters.insert(name.clone(), evaluate_value(value, exchange)?);
+        }
+        let body = match &self.request_body {
+            Some(v) => Some(evaluate_value(v, exchange)?),
+            None => None,
+        };
+        Ok((parameters, body))
+    }
+}
+
+impl OpenApi {
+    /// Find the operation which `link` points to, by `operationId` or `operationRef`.
+    pub fn link_operation(&self, link: &Link) -> Option<(&str, &'static str, &Operation)> {
+        let paths = &self.paths.as_ref()?.values;
+        match &link.operation {
+            LinkOperation::Id(id) => paths.iter().find_map(|(path, item)| {
+                item.operations()
+                    .into_iter()
+                    .find(|(_, op)| op.operation_id.as_ref() == Some(id))
+                    .map(|(method, op)| (path.as_str(), method, op))
+            }),
+            LinkOperation::Ref(r) => {
+                // only local references like `#/paths/~1pets~1{id}/get`.
+                let (_, fragment) = r.split_once('#')?;
+                let mut tokens = fragment.strip_prefix("/paths/")?.split('/');
+                let path = unescape(&percent_decode(tokens.next()?));
+                let method = tokens.next()?;
+                if tokens.next().is_some() {
+                    return None;
+                }
+                let (path, item) = paths.get_key_value(&path)?;
+                let (method, op) = item.operations().into_iter().find(|(m, _)| *m == method)?;
+                Some((path.as_str(), method, op))
+            }
+        }
+    }
+
+    /// Build the request which `link` describes from the exchange.
+    pub fn follow_link(
+        &self,
+        link: &Link,
+        exchange: &Exchange,
+    ) -> Result<LinkedRequest<'_>, ExpressionError> {
+        let (path, method, operation) = self.link_operation(link).ok_or_else(|| {
+            ExpressionError::NotFoundOperation(match &link.operation {
+                LinkOperation::Id(id) => id.clone(),
+                LinkOperation::Ref(r) => r.clone(),
+            })
+        })?;
+        let (parameters, body) = link.evaluate(exchange)?;
+        Ok(LinkedRequest {
+            path,
+            method,
+            operation,
+            parameters,
+            body,
+        })
+    }
+}
+
+/// Evaluate the keys of a callback to URLs.
+pub fn callback_urls<'a>(
+    callback: &'a KeyValues<ReferenceOr<PathItem>>,
+    exchange: &Exchange,
+) -> Result<Vec<(String, &'a ReferenceOr<PathItem>)>, ExpressionError> {
+    let mut urls = callback
+        .values
+        .iter()
+        .map(|(key, item)| Ok((evaluate_template(key, exchange)?, item)))
+        .collect::<Result<Vec<_>, ExpressionError>>()?;
+    urls.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange() -> (HttpRequest, HttpResponse) {
+        let request = HttpRequest {
+            url: "https://example.com/pets/1?q=a%20b&url=https%3A%2F%2Fc.example.com".to_string(),
+            method: "post".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            path: [("id".to_string(), "1".to_string())].into_iter().collect(),
+            body: Some(
+                serde_json::from_str(r#"{"a/b": [{"c": 1}], "url": "https://d.example.com"}"#)
+                    .unwrap(),
+            ),
+        };
+        let response = HttpResponse {
+            status: 201,
+            headers: vec![("Location".to_string(), "/pets/2".to_string())],
+            body: Some(serde_json::from_str(r#"{"id": 2}"#).unwrap()),
+        };
+        (request, response)
+    }
+
+    #[test]
+    fn expression_parse() {
+        for s in [
+            "$url",
+            "$method",
+            "$statusCode",
+            "$request.header.accept",
+            "$request.query.queryUrl",
+            "$request.path.id",
+            "$request.body",
+            "$request.body#/user/uuid",
+            "$response.header.Server",
+            "$response.body#/status",
+        ] {
+            assert_eq!(s, s.parse::<Expression>().unwrap().to_string());
+        }
+        for s in [
+            "url",
+            "$request",
+            "$request.header.a b",
+            "$request.body#a",
+            "$response.cookie.a",
+        ] {
+            assert!(s.parse::<Expression>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn expression_evaluate() {
+        let (request, response) = exchange();
+        let e = Exchange {
+            request: &request,
+            response: Some(&response),
+        };
+        let eval = |s: &str| s.parse::<Expression>().unwrap().evaluate(&e);
+
+        assert_eq!(Some(Any::String("POST".to_string())), eval("$method"));
+        assert_eq!(Some(Any::Integer(201)), eval("$statusCode"));
+        assert_eq!(
+            Some(Any::String("a b".to_string())),
+            eval("$request.query.q")
+        );
+        assert_eq!(Some(Any::String("1".to_string())), eval("$request.path.id"));
+        assert_eq!(
+            Some(Any::String("application/json".to_string())),
+            eval("$request.header.content-type")
+        );
+        assert_eq!(Some(Any::Integer(1)), eval("$request.body#/a~1b/0/c"));
+        assert_eq!(
+            Some(Any::String("/pets/2".to_string())),
+            eval("$response.header.Location")
+        );
+        assert_eq!(None, eval("$response.body#/name"));
+        assert_eq!(None, eval("$response.path.id"));
+
+        assert_eq!(
+            Ok("https://d.example.com/events?id=2".to_string()),
+            evaluate_template("{$request.body#/url}/events?id={$response.body#/id}", &e)
+        );
+        assert_eq!(
+            Err(ExpressionError::Unresolved("$request.header.X".to_string())),
+            evaluate_template("{$request.header.X}", &e)
+        );
+    }
+
+    #[test]
+    fn follow_link() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "paths": {
+                "/pets/{id}": {"get": {"operationId": "getPet"}}
+            }
+        }"##,
+        )
+        .unwrap();
+        let (request, response) = exchange();
+        let e = Exchange {
+            request: &request,
+            response: Some(&response),
+        };
+
+        let link = serde_json::from_str::<Link>(
+            r##"{"operationRef": "#/paths/~1pets~1%7Bid%7D/get", "parameters": {"id": "$response.body#/id", "v": 1}}"##,
+        )
+        .unwrap();
+        let r = doc.follow_link(&link, &e).unwrap();
+        assert_eq!(("/pets/{id}", "get"), (r.path, r.method));
+        assert_eq!(Some(&Any::Integer(2)), r.parameters.get("id"));
+        assert_eq!(Some(&Any::Integer(1)), r.parameters.get("v"));
+
+        let link = serde_json::from_str::<Link>(
+            r#"{"operationId": "getPet", "requestBody": "{$request.path.id}"}"#,
+        )
+        .unwrap();
+        let r = doc.follow_link(&link, &e).unwrap();
+        assert_eq!(Some(Any::String("1".to_string())), r.body);
+
+        let link = serde_json::from_str::<Link>(r#"{"operationId": "a"}"#).unwrap();
+        assert!(doc.follow_link(&link, &e).is_err());
+    }
+
+    #[test]
+    fn callback_url() {
+        let callback = serde_json::from_str::<KeyValues<ReferenceOr<PathItem>>>(
+            r#"{"{$request.query.url}/data": {}, "x-a": 1}"#,
+        )
+        .unwrap();
+        let (request, _) = exchange();
+        let e = Exchange {
+            request: &request,
+            response: None,
+        };
+        let urls = callback_urls(&callback, &e).unwrap();
+        assert_eq!(1, urls.len());
+        assert_eq!("https://c.example.com/data", urls[0].0);
+    }
+}
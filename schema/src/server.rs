@@ -0,0 +1,338 @@
+use super::Server;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ServerError {
+    // `{` without `}`, or nested braces.
+    InvalidTemplate(String),
+    // placeholder in `url` which is not in `variables`.
+    UndeclaredVariable(String),
+    // variable in `variables` which is not in `url`.
+    UnusedVariable(String),
+    NotInEnum { name: String, value: String },
+    EmptyEnum(String),
+    MissingPathParameter(String),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerError::InvalidTemplate(t) => write!(f, "Invalid URL template `{t}`."),
+            ServerError::UndeclaredVariable(n) => {
+                write!(f, "Server variable `{n}` is not declared.")
+            }
+            ServerError::UnusedVariable(n) => {
+                write!(f, "Server variable `{n}` is not used in the URL.")
+            }
+            ServerError::NotInEnum { name, value } => {
+                write!(f, "`{value}` is not allowed for server variable `{name}`.")
+            }
+            ServerError::EmptyEnum(n) => write!(f, "Enum of server variable `{n}` is empty."),
+            ServerError::MissingPathParameter(n) => write!(f, "Path parameter `{n}` is missing."),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    Variable(&'a str),
+}
+
+fn parse_template(template: &str) -> Result<Vec<Segment<'_>>, ServerError> {
+    let mut segments = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(ServerError::InvalidTemplate(template.to_string()));
+        }
+        let end = match rest[start + 1..].find(['{', '}']) {
+            Some(i) if rest[start + 1 + i..].starts_with('}') => start + 1 + i,
+            _ => return Err(ServerError::InvalidTemplate(template.to_string())),
+        };
+        if start > 0 {
+            segments.push(Segment::Literal(&rest[..start]));
+        }
+        segments.push(Segment::Variable(&rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    Ok(segments)
+}
+
+impl Server {
+    /// Names of the `{var}` placeholders in `url`, in order of appearance.
+    pub fn variables_in_url(&self) -> Result<Vec<String>, ServerError> {
+        let mut names: Vec<String> = vec![];
+        for segment in parse_template(&self.url)? {
+            if let Segment::Variable(name) = segment {
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    /// Check the placeholders in `url` against `variables`.
+    pub fn validate(&self) -> Vec<ServerError> {
+        let names = match self.variables_in_url() {
+            Ok(names) => names,
+            Err(e) => return vec![e],
+        };
+
+        let mut errors = vec![];
+        for name in &names {
+            if !self.variables.iter().flatten().any(|(n, _)| n == name) {
+                errors.push(ServerError::UndeclaredVariable(name.clone()));
+            }
+        }
+
+        let mut declared = self.variables.iter().flatten().collect::<Vec<_>>();
+        declared.sort_by_key(|(name, _)| *name);
+        for (name, var) in declared {
+            if !names.contains(name) {
+                errors.push(ServerError::UnusedVariable(name.clone()));
+            }
+            match &var.r#enum {
+                Some(values) if values.is_empty() => {
+                    errors.push(ServerError::EmptyEnum(name.clone()))
+                }
+                Some(values) if !values.contains(&var.default) => {
+                    errors.push(ServerError::NotInEnum {
+                        name: name.clone(),
+                        value: var.default.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+        errors
+    }
+
+    /// Substitute the placeholders in `url` by `values`, or `default`s of the variables.
+    pub fn expand(&self, values: &HashMap<String, String>) -> Result<String, ServerError> {
+        let mut url = String::new();
+        for segment in parse_template(&self.url)? {
+            let name = match segment {
+                Segment::Literal(s) => {
+                    url.push_str(s);
+                    continue;
+                }
+                Segment::Variable(name) => name,
+            };
+
+            let var = self
+                .variables
+                .as_ref()
+                .and_then(|v| v.get(name))
+                .ok_or_else(|| ServerError::UndeclaredVariable(name.to_string()))?;
+            let value = values.get(name).unwrap_or(&var.default);
+            if let Some(values) = &var.r#enum {
+                if !values.contains(value) {
+                    return Err(ServerError::NotInEnum {
+                        name: name.to_string(),
+                        value: value.clone(),
+                    });
+                }
+            }
+            url.push_str(value);
+        }
+        Ok(url)
+    }
+
+    /// Join the expanded server URL and the path template with `parameters`.
+    ///
+    /// Path parameters are percent-encoded.
+    pub fn url_for(
+        &self,
+        values: &HashMap<String, String>,
+        path: &str,
+        parameters: &HashMap<String, String>,
+    ) -> Result<String, ServerError> {
+        let base = self.expand(values)?;
+
+        let mut expanded = String::new();
+        for segment in parse_template(path)? {
+            match segment {
+                Segment::Literal(s) => expanded.push_str(s),
+                Segment::Variable(name) => {
+                    let value = parameters
+                        .get(name)
+                        .ok_or_else(|| ServerError::MissingPathParameter(name.to_string()))?;
+                    expanded.push_str(&percent_encode(value));
+                }
+            }
+        }
+
+        let base = base.trim_end_matches('/');
+        if expanded.is_empty() {
+            return Ok(base.to_string());
+        }
+        match expanded.strip_prefix('/') {
+            Some(p) => Ok(format!("{base}/{p}")),
+            None => Ok(format!("{base}/{expanded}")),
+        }
+    }
+}
+
+// encode all characters except unreserved ones.
+pub(crate) fn percent_encode(s: &str) -> String {
+    let mut encoded = String::new();
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(b as char)
+            }
+            b => encoded.push_str(&format!("%{b:02X}")),
+        }
+    }
+    encoded
+}
+
+// `+` is decoded to a space as in query strings.
+pub(crate) fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => match hex(bytes[i + 1], bytes[i + 2]) {
+                Some(b) => {
+                    out.push(b);
+                    i += 2;
+                }
+                None => out.push(b'%'),
+            },
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn hex(hi: u8, lo: u8) -> Option<u8> {
+    let hi = (hi as char).to_digit(16)?;
+    let lo = (lo as char).to_digit(16)?;
+    Some((hi * 16 + lo) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServerVariable;
+
+    fn server(url: &str, variables: &[(&str, &str, Option<&[&str]>)]) -> Server {
+        let variables = variables
+            .iter()
+            .map(|(name, default, values)| {
+                let var = ServerVariable {
+                    default: default.to_string(),
+                    r#enum: values.map(|v| v.iter().map(|s| s.to_string()).collect()),
+                    ..Default::default()
+                };
+                (name.to_string(), var)
+            })
+            .collect();
+        Server {
+            url: url.to_string(),
+            variables: Some(variables),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn server_expand() {
+        let s = server(
+            "https://{env}.example.com:{port}/v1",
+            &[
+                ("env", "api", Some(&["api", "staging"])),
+                ("port", "443", None),
+            ],
+        );
+        assert_eq!(
+            Ok("https://api.example.com:443/v1".to_string()),
+            s.expand(&HashMap::new())
+        );
+
+        let mut values = HashMap::new();
+        values.insert("port".to_string(), "8443".to_string());
+        values.insert("env".to_string(), "staging".to_string());
+        assert_eq!(
+            Ok("https://staging.example.com:8443/v1".to_string()),
+            s.expand(&values)
+        );
+
+        values.insert("env".to_string(), "prod".to_string());
+        assert_eq!(
+            Err(ServerError::NotInEnum {
+                name: "env".to_string(),
+                value: "prod".to_string()
+            }),
+            s.expand(&values)
+        );
+
+        let s = server("https://{host/v1", &[]);
+        assert!(matches!(
+            s.expand(&HashMap::new()),
+            Err(ServerError::InvalidTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn server_validate() {
+        let s = server(
+            "https://{host}/{base}/{host}",
+            &[("base", "v1", Some(&["v2"])), ("port", "443", None)],
+        );
+        assert_eq!(
+            Ok(vec!["host".to_string(), "base".to_string()]),
+            s.variables_in_url()
+        );
+        assert_eq!(
+            vec![
+                ServerError::UndeclaredVariable("host".to_string()),
+                ServerError::NotInEnum {
+                    name: "base".to_string(),
+                    value: "v1".to_string()
+                },
+                ServerError::UnusedVariable("port".to_string()),
+            ],
+            s.validate()
+        );
+    }
+
+    #[test]
+    fn server_url_for() {
+        let s = server("https://example.com/{base}/", &[("base", "v1", None)]);
+        let mut parameters = HashMap::new();
+        parameters.insert("id".to_string(), "a b/c".to_string());
+        assert_eq!(
+            Ok("https://example.com/v1/pets/a%20b%2Fc".to_string()),
+            s.url_for(&HashMap::new(), "/pets/{id}", &parameters)
+        );
+        assert_eq!(
+            Err(ServerError::MissingPathParameter("name".to_string())),
+            s.url_for(&HashMap::new(), "/pets/{name}", &parameters)
+        );
+
+        let s = server("/", &[]);
+        assert_eq!(
+            Ok("/pets".to_string()),
+            s.url_for(&HashMap::new(), "/pets", &parameters)
+        );
+    }
+
+    #[test]
+    fn server_percent_codec() {
+        assert_eq!("a%20b%2Fc~", percent_encode("a b/c~"));
+        assert_eq!("a b/c%", percent_decode("a+b%2Fc%"));
+        assert_eq!("%zz", percent_decode("%zz"));
+    }
+}
@@ -0,0 +1,221 @@
+use super::OpenApi;
+use super::Schema;
+use super::version::newer_features;
+use super::visit::{Visitor, pointer, walk};
+use serde::de::Error as _;
+use serde_json::Value;
+use std::fmt;
+
+// keywords of earlier drafts which are valid but not modeled by `Schema`.
+const SCHEMA_KEYWORDS: [&str; 2] = ["definitions", "dependencies"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownField {
+    // JSON pointer of the object which has the field.
+    pub pointer: String,
+    pub key: String,
+}
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ptr = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "unknown field `{}` at {}", self.key, ptr)
+    }
+}
+
+/// Deserialize JSON and report fields which are ignored by the model.
+pub fn from_json_strict(s: &str) -> Result<(OpenApi, Vec<UnknownField>), serde_json::Error> {
+    let input = serde_json::from_str::<Value>(s)?;
+    let doc = serde_json::from_value::<OpenApi>(input.clone())?;
+    let fields = unknown_fields(&input, &doc);
+    Ok((doc, fields))
+}
+
+/// Deserialize YAML and report fields which are ignored by the model.
+pub fn from_yaml_strict(s: &str) -> Result<(OpenApi, Vec<UnknownField>), serde_yaml::Error> {
+    // `from_value` can not read integer keys as strings, so parse twice.
+    let doc = serde_yaml::from_str::<OpenApi>(s)?;
+    let input = serde_yaml::from_str::<serde_yaml::Value>(s)?;
+    let input = serde_json::to_value(&input).map_err(serde_yaml::Error::custom)?;
+    let fields = unknown_fields(&input, &doc);
+    Ok((doc, fields))
+}
+
+/// Collect the fields of `input` which are not kept in `doc`.
+///
+/// `doc` must be deserialized from `input`. Keys are found by comparing
+/// `input` with re-serialized `doc`, and unknown keywords in schemas are
+/// found from `Schema::extensions`. Fields added by a later version than the
+/// one of `doc` are also unknown.
+pub fn unknown_fields(input: &Value, doc: &OpenApi) -> Vec<UnknownField> {
+    let mut fields = vec![];
+    if let Ok(output) = serde_json::to_value(doc) {
+        diff("", input, &output, &mut fields);
+    }
+
+    let mut keywords = SchemaKeywords { fields: vec![] };
+    walk(doc, &mut keywords);
+    fields.extend(keywords.fields);
+
+    for feature in newer_features(doc) {
+        // values like `querystring` are not fields.
+        if let Some(ptr) = feature
+            .pointer
+            .strip_suffix(&feature.name)
+            .and_then(|p| p.strip_suffix('/'))
+        {
+            fields.push(UnknownField {
+                pointer: ptr.to_string(),
+                key: feature.name,
+            });
+        }
+    }
+
+    fields.sort_by(|a, b| (&a.pointer, &a.key).cmp(&(&b.pointer, &b.key)));
+    fields.dedup();
+    fields
+}
+
+fn diff(ptr: &str, input: &Value, output: &Value, fields: &mut Vec<UnknownField>) {
+    match (input, output) {
+        (Value::Object(input), Value::Object(output)) => {
+            for (key, value) in input {
+                match output.get(key) {
+                    Some(v) => diff(&pointer(ptr, key), value, v, fields),
+                    // `null` is same as missing.
+                    None if value.is_null() => {}
+                    None => fields.push(UnknownField {
+                        pointer: ptr.to_string(),
+                        key: key.clone(),
+                    }),
+                }
+            }
+        }
+        (Value::Array(input), Value::Array(output)) => {
+            for (i, (a, b)) in input.iter().zip(output).enumerate() {
+                diff(&pointer(ptr, &i.to_string()), a, b, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+// schemas keep unknown keywords in `extensions`, so they are not found by `diff`.
+struct SchemaKeywords {
+    fields: Vec<UnknownField>,
+}
+
+impl Visitor for SchemaKeywords {
+    fn visit_schema(&mut self, ptr: &str, value: &Schema) {
+        for key in value.extensions.values.keys() {
+            if !SCHEMA_KEYWORDS.contains(&key.as_str()) {
+                self.fields.push(UnknownField {
+                    pointer: ptr.to_string(),
+                    key: key.clone(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(fields: &[UnknownField]) -> Vec<(&str, &str)> {
+        fields
+            .iter()
+            .map(|f| (f.pointer.as_str(), f.key.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn strict_json() {
+        let (doc, fields) = from_json_strict(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": "", "descripton": ""},
+            "tags": [{"name": "a", "description": "b"}],
+            "paths": {
+                "/a": {
+                    "get": {
+                        "operationID": "a",
+                        "responses": {"200": {"description": "", "x-a": {"b": 1}}}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "A": {
+                        "type": "object",
+                        "requried": ["a"],
+                        "$comment": "",
+                        "properties": {"a": {"type": "string", "example": {"c": null}}}
+                    }
+                }
+            }
+        }"##,
+        )
+        .unwrap();
+
+        assert_eq!(Some("b".to_string()), doc.tags.unwrap()[0].description);
+        assert_eq!(
+            vec![
+                ("/components/schemas/A", "requried"),
+                ("/info", "descripton"),
+                ("/paths/~1a/get", "operationID"),
+            ],
+            keys(&fields)
+        );
+        assert_eq!("unknown field `descripton` at /info", fields[1].to_string());
+    }
+
+    #[test]
+    fn strict_yaml() {
+        let (_, fields) = from_yaml_strict(
+            r#"
+openapi: 3.1.0
+info:
+  title: ""
+  version: ""
+paths:
+  /a:
+    get:
+      responses:
+        200:
+          description: ""
+          contents: {}
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![("/paths/~1a/get/responses/200", "contents")],
+            keys(&fields)
+        );
+    }
+
+    #[test]
+    fn strict_newer_version() {
+        let s = r#"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "tags": [{"name": "a", "kind": "nav"}],
+            "paths": {
+                "/a": {
+                    "get": {
+                        "parameters": [{"name": "a", "in": "querystring", "content": {}}]
+                    }
+                }
+            }
+        }"#;
+        let (_, fields) = from_json_strict(s).unwrap();
+        assert_eq!(vec![("/tags/0", "kind")], keys(&fields));
+
+        let (_, fields) = from_json_strict(&s.replace("3.1.0", "3.2.0")).unwrap();
+        assert!(fields.is_empty());
+    }
+}
@@ -0,0 +1,1029 @@
+use super::model::Any;
+use super::resolve::{Resolver, join, ref_base};
+use super::visit::pointer;
+use super::{BooleanOr, Schema, SchemaOrBool, SchemaType, SchemaTypes};
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    // JSON pointer in the instance.
+    pub instance: String,
+    // JSON pointer of the failed keyword.
+    pub schema: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ptr = if self.instance.is_empty() {
+            "/"
+        } else {
+            &self.instance
+        };
+        write!(f, "{} (at {ptr})", self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Validates values against schemas.
+pub struct Validator<'a, R: Resolver + ?Sized> {
+    resolver: &'a R,
+    regexes: RefCell<HashMap<String, Option<Regex>>>,
+    // base URIs changed by `$id` and `$ref`, the innermost last.
+    bases: RefCell<Vec<Option<String>>>,
+}
+
+impl<'a, R: Resolver + ?Sized> Validator<'a, R> {
+    pub fn new(resolver: &'a R) -> Self {
+        Validator {
+            resolver,
+            regexes: RefCell::new(HashMap::new()),
+            bases: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn is_valid(&self, schema: &Schema, instance: &Any) -> bool {
+        self.validate(schema, instance).is_empty()
+    }
+
+    pub fn validate(&self, schema: &Schema, instance: &Any) -> Vec<ValidationError> {
+        self.validate_at(schema, "", instance)
+    }
+
+    /// Validate `instance` against `schema` located at `schema_ptr`.
+    pub fn validate_at(
+        &self,
+        schema: &Schema,
+        schema_ptr: &str,
+        instance: &Any,
+    ) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        self.schema(schema, schema_ptr, instance, "", 0, &mut errors);
+        errors
+    }
+
+    fn schema(
+        &self,
+        schema: &Schema,
+        sp: &str,
+        instance: &Any,
+        ip: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        match schema.id() {
+            Some(id) => {
+                let base = join(self.base().as_deref().unwrap_or(""), id);
+                self.with_base(Some(base), || {
+                    self.keywords(schema, sp, instance, ip, depth, errors)
+                });
+            }
+            None => self.keywords(schema, sp, instance, ip, depth, errors),
+        }
+    }
+
+    fn base(&self) -> Option<String> {
+        self.bases.borrow().last().cloned().flatten()
+    }
+
+    fn with_base(&self, base: Option<String>, f: impl FnOnce()) {
+        self.bases.borrow_mut().push(base);
+        f();
+        self.bases.borrow_mut().pop();
+    }
+
+    fn keywords(
+        &self,
+        schema: &Schema,
+        sp: &str,
+        instance: &Any,
+        ip: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let error = |errors: &mut Vec<ValidationError>, keyword: &str, message: String| {
+            errors.push(ValidationError {
+                instance: ip.to_string(),
+                schema: pointer(sp, keyword),
+                message,
+            })
+        };
+
+        if depth > MAX_DEPTH {
+            return;
+        }
+
+        if let Some(r) = &schema.r#ref {
+            let base = self.base();
+            match self.resolver.resolve_schema(base.as_deref(), r) {
+                Some(s) => {
+                    // an anchor is no JSON pointer, so report the reference itself
+                    let target = match r.split_once('#') {
+                        Some((_, f)) if !f.is_empty() && !f.starts_with('/') => r.as_str(),
+                        Some((_, f)) => f,
+                        None => "",
+                    };
+                    self.with_base(ref_base(base.as_deref(), r), || {
+                        self.schema(s, target, instance, ip, depth + 1, errors)
+                    });
+                }
+                None => error(errors, "$ref", format!("Not found reference `{r}`.")),
+            }
+        }
+
+        // nullable until v3.1.0
+        if schema.nullable == Some(true) && *instance == Any::Null {
+            return;
+        }
+
+        self.applicators(schema, sp, instance, ip, depth, errors);
+
+        if let Some(types) = &schema.r#type {
+            let types = match types {
+                SchemaTypes::Unit(t) => vec![t.clone()],
+                SchemaTypes::Array(t) => t.clone(),
+            };
+            if !types.iter().any(|t| is_type(instance, t)) {
+                error(
+                    errors,
+                    "type",
+                    format!(
+                        "Expected type {}, found {}.",
+                        names(&types),
+                        type_name(instance)
+                    ),
+                );
+            }
+        }
+
+        if let Some(values) = &schema.r#enum {
+            if !values.iter().any(|v| equal(v, instance)) {
+                error(errors, "enum", "Value is not one of enum.".to_string());
+            }
+        }
+
+        if let Some(v) = schema.r#const() {
+            if !equal(v, instance) {
+                error(errors, "const", "Value is not const.".to_string());
+            }
+        }
+
+        match instance {
+            Any::Integer(_) | Any::Number(_) => {
+                let n = number(instance).unwrap();
+                self.numeric(schema, n, &mut |k, m| error(errors, k, m));
+            }
+            Any::String(s) => self.string(schema, s, &mut |k, m| error(errors, k, m)),
+            Any::Array(items) => self.array(schema, sp, items, ip, depth, errors),
+            Any::Object(props) => self.object(schema, sp, props, ip, depth, errors),
+            _ => {}
+        }
+    }
+
+    fn applicators(
+        &self,
+        schema: &Schema,
+        sp: &str,
+        instance: &Any,
+        ip: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let error = |errors: &mut Vec<ValidationError>, keyword: &str, message: String| {
+            errors.push(ValidationError {
+                instance: ip.to_string(),
+                schema: pointer(sp, keyword),
+                message,
+            })
+        };
+
+        if let Some(all_of) = &schema.all_of {
+            let sp = pointer(sp, "allOf");
+            for (i, s) in all_of.iter().enumerate() {
+                self.subschema(
+                    s,
+                    &pointer(&sp, &i.to_string()),
+                    instance,
+                    ip,
+                    depth + 1,
+                    errors,
+                );
+            }
+        }
+
+        if let (Some(dependent), Any::Object(props)) = (schema.dependent_schemas(), instance) {
+            let sp = pointer(sp, "dependentSchemas");
+            let mut names = dependent.keys().collect::<Vec<&String>>();
+            names.sort_unstable();
+            for name in names.into_iter().filter(|n| props.contains_key(*n)) {
+                let s = &dependent[name];
+                self.subschema(s, &pointer(&sp, name), instance, ip, depth + 1, errors);
+            }
+        }
+
+        // validate only the schema selected by the discriminator.
+        let polymorphic = schema.one_of.is_some() || schema.any_of.is_some();
+        if polymorphic && schema.discriminator().is_some() && matches!(instance, Any::Object(_)) {
+            match schema.discriminate(self.resolver, instance) {
+                Ok(selected) => {
+                    let target = selected.reference.split_once('#').map_or("", |(_, f)| f);
+                    self.schema(selected.schema, target, instance, ip, depth + 1, errors);
+                }
+                Err(e) => error(errors, "discriminator", e.to_string()),
+            }
+            return self.conditionals(schema, sp, instance, ip, depth, errors);
+        }
+
+        if let Some(any_of) = &schema.any_of {
+            let sp = pointer(sp, "anyOf");
+            let matched = any_of
+                .iter()
+                .enumerate()
+                .any(|(i, s)| self.passes(s, &pointer(&sp, &i.to_string()), instance, ip, depth));
+            if !matched {
+                error(
+                    errors,
+                    "anyOf",
+                    "Value does not match any of anyOf.".to_string(),
+                );
+            }
+        }
+
+        if let Some(one_of) = &schema.one_of {
+            let sp = pointer(sp, "oneOf");
+            let matched = one_of
+                .iter()
+                .enumerate()
+                .filter(|(i, s)| self.passes(s, &pointer(&sp, &i.to_string()), instance, ip, depth))
+                .count();
+            if matched != 1 {
+                error(
+                    errors,
+                    "oneOf",
+                    format!("Value matches {matched} schemas of oneOf, expected 1."),
+                );
+            }
+        }
+
+        self.conditionals(schema, sp, instance, ip, depth, errors);
+    }
+
+    fn conditionals(
+        &self,
+        schema: &Schema,
+        sp: &str,
+        instance: &Any,
+        ip: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let error = |errors: &mut Vec<ValidationError>, keyword: &str, message: String| {
+            errors.push(ValidationError {
+                instance: ip.to_string(),
+                schema: pointer(sp, keyword),
+                message,
+            })
+        };
+
+        if let Some(not) = schema.not() {
+            if self.passes(not, &pointer(sp, "not"), instance, ip, depth) {
+                error(errors, "not", "Value matches not.".to_string());
+            }
+        }
+
+        if let Some(cond) = schema.r#if() {
+            if self.passes(cond, &pointer(sp, "if"), instance, ip, depth) {
+                if let Some(then) = schema.then() {
+                    self.subschema(then, &pointer(sp, "then"), instance, ip, depth + 1, errors);
+                }
+            } else if let Some(els) = schema.r#else() {
+                self.subschema(els, &pointer(sp, "else"), instance, ip, depth + 1, errors);
+            }
+        }
+    }
+
+    fn passes(
+        &self,
+        schema: &SchemaOrBool,
+        sp: &str,
+        instance: &Any,
+        ip: &str,
+        depth: usize,
+    ) -> bool {
+        let mut errors = vec![];
+        self.subschema(schema, sp, instance, ip, depth + 1, &mut errors);
+        errors.is_empty()
+    }
+
+    // `true` accepts any value and `false` rejects all values.
+    fn subschema(
+        &self,
+        schema: &SchemaOrBool,
+        sp: &str,
+        instance: &Any,
+        ip: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        match schema {
+            BooleanOr::Value(s) => self.schema(s, sp, instance, ip, depth, errors),
+            BooleanOr::Boolean(true) => {}
+            BooleanOr::Boolean(false) => errors.push(ValidationError {
+                instance: ip.to_string(),
+                schema: sp.to_string(),
+                message: "Schema `false` rejects all values.".to_string(),
+            }),
+        }
+    }
+
+    fn numeric(&self, schema: &Schema, n: f64, error: &mut dyn FnMut(&str, String)) {
+        if let Some(m) = schema.multiple_of() {
+            if m != 0 && (n / m as f64).fract() != 0.0 {
+                error("multipleOf", format!("{n} is not a multiple of {m}."));
+            }
+        }
+
+        if let Some(m) = schema.maximum() {
+            if n > m as f64 {
+                error("maximum", format!("{n} is greater than {m}."));
+            }
+        }
+
+        if let Some(m) = schema.exclusive_maximum() {
+            if n >= m as f64 {
+                error(
+                    "exclusiveMaximum",
+                    format!("{n} is greater than or equal to {m}."),
+                );
+            }
+        }
+
+        if let Some(m) = schema.minimum() {
+            if n < m as f64 {
+                error("minimum", format!("{n} is less than {m}."));
+            }
+        }
+
+        if let Some(m) = schema.exclusive_minimum() {
+            if n <= m as f64 {
+                error(
+                    "exclusiveMinimum",
+                    format!("{n} is less than or equal to {m}."),
+                );
+            }
+        }
+    }
+
+    fn string(&self, schema: &Schema, s: &str, error: &mut dyn FnMut(&str, String)) {
+        let len = s.chars().count();
+
+        if let Some(m) = schema.max_length() {
+            if len > m as usize {
+                error("maxLength", format!("Length {len} is greater than {m}."));
+            }
+        }
+
+        if let Some(m) = schema.min_length() {
+            if len < m as usize {
+                error("minLength", format!("Length {len} is less than {m}."));
+            }
+        }
+
+        if let Some(pattern) = schema.pattern() {
+            let mut regexes = self.regexes.borrow_mut();
+            let regex = regexes
+                .entry(pattern.to_string())
+                .or_insert_with(|| Regex::new(pattern).ok());
+            if let Some(regex) = regex {
+                if !regex.is_match(s) {
+                    error("pattern", format!("`{s}` does not match `{pattern}`."));
+                }
+            }
+        }
+
+        if let Some(format) = &schema.format {
+            if !is_format(format, s) {
+                error("format", format!("`{s}` is not a valid {format}."));
+            }
+        }
+    }
+
+    fn array(
+        &self,
+        schema: &Schema,
+        sp: &str,
+        items: &[Any],
+        ip: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let error = |errors: &mut Vec<ValidationError>, keyword: &str, message: String| {
+            errors.push(ValidationError {
+                instance: ip.to_string(),
+                schema: pointer(sp, keyword),
+                message,
+            })
+        };
+
+        if let Some(m) = schema.max_items() {
+            if items.len() > m as usize {
+                error(
+                    errors,
+                    "maxItems",
+                    format!("{} items exceed {m}.", items.len()),
+                );
+            }
+        }
+
+        if let Some(m) = schema.min_items() {
+            if items.len() < m as usize {
+                error(
+                    errors,
+                    "minItems",
+                    format!("{} items are less than {m}.", items.len()),
+                );
+            }
+        }
+
+        if schema.unique_items() == Some(true) {
+            let duplicated = items
+                .iter()
+                .enumerate()
+                .any(|(i, a)| items[i + 1..].iter().any(|b| equal(a, b)));
+            if duplicated {
+                error(errors, "uniqueItems", "Items are not unique.".to_string());
+            }
+        }
+
+        let mut offset = 0;
+        if let Some(prefix) = schema.prefix_items() {
+            let sp = pointer(sp, "prefixItems");
+            for (i, (s, item)) in prefix.iter().zip(items).enumerate() {
+                let i = i.to_string();
+                self.subschema(
+                    s,
+                    &pointer(&sp, &i),
+                    item,
+                    &pointer(ip, &i),
+                    depth + 1,
+                    errors,
+                );
+            }
+            offset = prefix.len();
+        }
+
+        if let Some(s) = &schema.items {
+            let sp = pointer(sp, "items");
+            for (i, item) in items.iter().enumerate().skip(offset) {
+                self.subschema(
+                    s,
+                    &sp,
+                    item,
+                    &pointer(ip, &i.to_string()),
+                    depth + 1,
+                    errors,
+                );
+            }
+        }
+
+        if let Some(contains) = schema.contains() {
+            let sp = pointer(sp, "contains");
+            let count = items
+                .iter()
+                .enumerate()
+                .filter(|(i, item)| {
+                    self.passes(contains, &sp, item, &pointer(ip, &i.to_string()), depth)
+                })
+                .count();
+            let min = schema.min_contains().unwrap_or(1) as usize;
+            if count < min {
+                error(
+                    errors,
+                    "contains",
+                    format!("{count} items match contains, expected at least {min}."),
+                );
+            }
+            if let Some(max) = schema.max_contains() {
+                if count > max as usize {
+                    error(
+                        errors,
+                        "maxContains",
+                        format!("{count} items match contains, expected at most {max}."),
+                    );
+                }
+            }
+        }
+    }
+
+    fn object(
+        &self,
+        schema: &Schema,
+        sp: &str,
+        props: &HashMap<String, Any>,
+        ip: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let error = |errors: &mut Vec<ValidationError>, keyword: &str, message: String| {
+            errors.push(ValidationError {
+                instance: ip.to_string(),
+                schema: pointer(sp, keyword),
+                message,
+            })
+        };
+
+        if let Some(m) = schema.max_properties() {
+            if props.len() > m as usize {
+                error(
+                    errors,
+                    "maxProperties",
+                    format!("{} properties exceed {m}.", props.len()),
+                );
+            }
+        }
+
+        if let Some(m) = schema.min_properties() {
+            if props.len() < m as usize {
+                error(
+                    errors,
+                    "minProperties",
+                    format!("{} properties are less than {m}.", props.len()),
+                );
+            }
+        }
+
+        if let Some(required) = &schema.required {
+            let mut missing = required
+                .iter()
+                .filter(|r| !props.contains_key(*r))
+                .map(|r| r.as_str())
+                .collect::<Vec<&str>>();
+            missing.sort_unstable();
+            if !missing.is_empty() {
+                error(
+                    errors,
+                    "required",
+                    format!("Missing required properties: {}.", missing.join(", ")),
+                );
+            }
+        }
+
+        if let Some(dependent) = schema.dependent_required() {
+            let mut names = dependent.keys().collect::<Vec<&String>>();
+            names.sort_unstable();
+            for name in names.into_iter().filter(|n| props.contains_key(*n)) {
+                for r in &dependent[name] {
+                    if !props.contains_key(r) {
+                        error(
+                            errors,
+                            "dependentRequired",
+                            format!("`{r}` is required when `{name}` is present."),
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut names = props.keys().collect::<Vec<&String>>();
+        names.sort_unstable();
+
+        for name in names {
+            let value = &props[name];
+            let ip = pointer(ip, name);
+            let mut evaluated = false;
+
+            if let Some(s) = schema.properties.as_ref().and_then(|p| p.get(name)) {
+                let sp = pointer(&pointer(sp, "properties"), name);
+                self.subschema(s, &sp, value, &ip, depth + 1, errors);
+                evaluated = true;
+            }
+
+            if let Some(patterns) = schema.pattern_properties() {
+                for (pattern, s) in patterns {
+                    let matched = self
+                        .regexes
+                        .borrow_mut()
+                        .entry(pattern.to_string())
+                        .or_insert_with(|| Regex::new(pattern).ok())
+                        .as_ref()
+                        .is_some_and(|r| r.is_match(name));
+                    if matched {
+                        let sp = pointer(&pointer(sp, "patternProperties"), pattern);
+                        self.subschema(s, &sp, value, &ip, depth + 1, errors);
+                        evaluated = true;
+                    }
+                }
+            }
+
+            if !evaluated {
+                match schema.additional_properties.as_deref() {
+                    Some(BooleanOr::Boolean(false)) => errors.push(ValidationError {
+                        instance: ip.clone(),
+                        schema: pointer(sp, "additionalProperties"),
+                        message: format!("Additional property `{name}` is not allowed."),
+                    }),
+                    Some(s) => {
+                        let sp = pointer(sp, "additionalProperties");
+                        self.subschema(s, &sp, value, &ip, depth + 1, errors);
+                    }
+                    None => {}
+                }
+            }
+
+            if let Some(s) = schema.property_names() {
+                let sp = pointer(sp, "propertyNames");
+                let name = Any::String(name.clone());
+                self.subschema(s, &sp, &name, &ip, depth + 1, errors);
+            }
+        }
+    }
+}
+
+pub(crate) fn number(value: &Any) -> Option<f64> {
+    match value {
+        Any::Integer(i) => Some(*i as f64),
+        Any::Number(n) => Some(*n as f64),
+        _ => None,
+    }
+}
+
+/// Compare JSON values, treating `1` and `1.0` as equal.
+pub fn equal(a: &Any, b: &Any) -> bool {
+    match (a, b) {
+        (Any::Array(a), Any::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equal(a, b))
+        }
+        (Any::Object(a), Any::Object(b)) => {
+            a.len() == b.len() && a.iter().all(|(k, v)| b.get(k).is_some_and(|w| equal(v, w)))
+        }
+        _ => match (number(a), number(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => a == b,
+        },
+    }
+}
+
+pub(crate) fn is_type(value: &Any, ty: &SchemaType) -> bool {
+    match (ty, value) {
+        (SchemaType::Null, Any::Null) => true,
+        (SchemaType::Boolean, Any::Boolean(_)) => true,
+        (SchemaType::Object, Any::Object(_)) => true,
+        (SchemaType::Array, Any::Array(_)) => true,
+        (SchemaType::Number, Any::Integer(_) | Any::Number(_)) => true,
+        (SchemaType::String, Any::String(_)) => true,
+        (SchemaType::Integer, Any::Integer(_)) => true,
+        (SchemaType::Integer, Any::Number(n)) => n.fract() == 0.0,
+        _ => false,
+    }
+}
+
+pub(crate) fn type_name(value: &Any) -> &'static str {
+    match value {
+        Any::Null => "null",
+        Any::Boolean(_) => "boolean",
+        Any::Integer(_) => "integer",
+        Any::Number(_) => "number",
+        Any::String(_) => "string",
+        Any::Array(_) => "array",
+        Any::Object(_) => "object",
+    }
+}
+
+fn names(types: &[SchemaType]) -> String {
+    types
+        .iter()
+        .map(|t| match t {
+            SchemaType::Null => "null",
+            SchemaType::Boolean => "boolean",
+            SchemaType::Object => "object",
+            SchemaType::Array => "array",
+            SchemaType::Number => "number",
+            SchemaType::String => "string",
+            SchemaType::Integer => "integer",
+        })
+        .collect::<Vec<&str>>()
+        .join(" or ")
+}
+
+fn is_format(format: &str, s: &str) -> bool {
+    let digits = |s: &str, n: usize| s.len() == n && s.bytes().all(|b| b.is_ascii_digit());
+    let date = |s: &str| {
+        let p = s.split('-').collect::<Vec<&str>>();
+        p.len() == 3 && digits(p[0], 4) && digits(p[1], 2) && digits(p[2], 2)
+    };
+    let time = |s: &str| {
+        let s = s
+            .trim_end_matches(['Z', 'z'])
+            .split(['+', '-'])
+            .next()
+            .unwrap_or("");
+        let s = s.split('.').next().unwrap_or("");
+        let p = s.split(':').collect::<Vec<&str>>();
+        p.len() == 3 && p.iter().all(|p| digits(p, 2))
+    };
+
+    match format {
+        "date-time" => s
+            .split_once(['T', 't'])
+            .is_some_and(|(d, t)| date(d) && time(t)),
+        "date" => date(s),
+        "time" => time(s),
+        "email" | "idn-email" => s
+            .split_once('@')
+            .is_some_and(|(l, d)| !l.is_empty() && !d.is_empty() && !d.contains('@')),
+        "ipv4" => s.parse::<Ipv4Addr>().is_ok(),
+        "ipv6" => s.parse::<Ipv6Addr>().is_ok(),
+        "uuid" => {
+            let p = s.split('-').map(|p| p.len()).collect::<Vec<usize>>();
+            p == [8, 4, 4, 4, 12] && s.chars().all(|c| c == '-' || c.is_ascii_hexdigit())
+        }
+        "uri" | "iri" => s.split_once(':').is_some_and(|(scheme, _)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }),
+        "hostname" => {
+            !s.is_empty()
+                && s.len() <= 253
+                && s.split('.').all(|l| {
+                    !l.is_empty()
+                        && l.len() <= 63
+                        && !l.starts_with('-')
+                        && !l.ends_with('-')
+                        && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenApi;
+    use crate::resolve::SchemaIndex;
+
+    fn schema(s: &str) -> Schema {
+        serde_json::from_str::<Schema>(s).unwrap()
+    }
+
+    fn any(s: &str) -> Any {
+        serde_json::from_str::<Any>(s).unwrap()
+    }
+
+    fn errors(schema_str: &str, instance: &str) -> Vec<(String, String)> {
+        Validator::new(&())
+            .validate(&schema(schema_str), &any(instance))
+            .into_iter()
+            .map(|e| (e.instance, e.schema))
+            .collect()
+    }
+
+    fn err(i: &str, s: &str) -> (String, String) {
+        (i.to_string(), s.to_string())
+    }
+
+    #[test]
+    fn validate_type() {
+        assert!(errors(r#"{"type":"integer"}"#, "1").is_empty());
+        assert!(errors(r#"{"type":"integer"}"#, "1.0").is_empty());
+        assert!(errors(r#"{"type":"number"}"#, "1").is_empty());
+        assert!(errors(r#"{"type":["string","null"]}"#, "null").is_empty());
+        assert!(errors(r#"{"type":"string","nullable":true}"#, "null").is_empty());
+        assert_eq!(
+            vec![err("", "/type")],
+            errors(r#"{"type":"integer"}"#, "1.5")
+        );
+        assert_eq!(
+            vec![err("", "/type")],
+            errors(r#"{"type":"string"}"#, "null")
+        );
+    }
+
+    #[test]
+    fn validate_enum_const() {
+        assert!(errors(r#"{"enum":[1,"a"]}"#, "1.0").is_empty());
+        assert_eq!(
+            vec![err("", "/enum")],
+            errors(r#"{"enum":[1,"a"]}"#, "\"b\"")
+        );
+        assert_eq!(
+            vec![err("", "/const")],
+            errors(r#"{"const":{"a":1}}"#, "{}")
+        );
+    }
+
+    #[test]
+    fn validate_numeric() {
+        let s = r#"{"minimum":1,"exclusiveMaximum":5,"multipleOf":2}"#;
+        assert!(errors(s, "4").is_empty());
+        assert_eq!(vec![err("", "/minimum")], errors(s, "0"));
+        assert_eq!(
+            vec![err("", "/multipleOf"), err("", "/exclusiveMaximum")],
+            errors(s, "5")
+        );
+    }
+
+    #[test]
+    fn validate_string() {
+        let s = r#"{"minLength":2,"maxLength":3,"pattern":"^[a-z]+$"}"#;
+        assert!(errors(s, "\"ab\"").is_empty());
+        assert_eq!(vec![err("", "/minLength")], errors(s, "\"a\""));
+        assert_eq!(vec![err("", "/pattern")], errors(s, "\"A1\""));
+        assert!(errors(r#"{"format":"date-time"}"#, "\"2024-01-01T00:00:00Z\"").is_empty());
+        assert!(
+            errors(
+                r#"{"format":"uuid"}"#,
+                "\"00000000-0000-4000-8000-000000000000\""
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            vec![err("", "/format")],
+            errors(r#"{"format":"ipv4"}"#, "\"1.2.3\"")
+        );
+        assert!(errors(r#"{"format":"unknown"}"#, "\"\"").is_empty());
+    }
+
+    #[test]
+    fn validate_array() {
+        let s = r#"{"prefixItems":[{"type":"string"}],"items":{"type":"integer"},"minItems":2,"uniqueItems":true}"#;
+        assert!(errors(s, r#"["a",1,2]"#).is_empty());
+        assert_eq!(vec![err("/2", "/items/type")], errors(s, r#"["a",1,"b"]"#));
+        assert_eq!(vec![err("", "/uniqueItems")], errors(s, r#"["a",1,1]"#));
+        assert_eq!(vec![err("", "/minItems")], errors(s, r#"["a"]"#));
+        assert_eq!(
+            vec![err("", "/contains")],
+            errors(r#"{"contains":{"const":1}}"#, "[2]")
+        );
+    }
+
+    #[test]
+    fn validate_object() {
+        let s = r#"{
+            "required": ["a"],
+            "properties": {"a": {"type": "integer"}},
+            "patternProperties": {"^x-": {"type": "string"}},
+            "additionalProperties": false
+        }"#;
+        assert!(errors(s, r#"{"a":1,"x-b":"c"}"#).is_empty());
+        assert_eq!(vec![err("", "/required")], errors(s, r#"{}"#));
+        assert_eq!(
+            vec![
+                err("/a", "/properties/a/type"),
+                err("/b", "/additionalProperties")
+            ],
+            errors(s, r#"{"a":"1","b":1}"#)
+        );
+        assert_eq!(
+            vec![err("/x-b", "/patternProperties/^x-/type")],
+            errors(s, r#"{"a":1,"x-b":1}"#)
+        );
+    }
+
+    #[test]
+    fn validate_composition() {
+        let s = r#"{"oneOf":[{"type":"integer"},{"type":"number"}]}"#;
+        assert!(errors(s, "1.5").is_empty());
+        assert_eq!(vec![err("", "/oneOf")], errors(s, "1"));
+        let s = r#"{"anyOf":[{"type":"integer"},{"type":"string"}]}"#;
+        assert_eq!(vec![err("", "/anyOf")], errors(s, "null"));
+        let s = r#"{"allOf":[{"required":["a"]},{"required":["b"]}]}"#;
+        assert_eq!(vec![err("", "/allOf/1/required")], errors(s, r#"{"a":1}"#));
+        assert_eq!(
+            vec![err("", "/not")],
+            errors(r#"{"not":{"type":"null"}}"#, "null")
+        );
+        let s = r#"{"if":{"type":"string"},"then":{"minLength":1},"else":{"type":"null"}}"#;
+        assert_eq!(vec![err("", "/then/minLength")], errors(s, "\"\""));
+        assert_eq!(vec![err("", "/else/type")], errors(s, "1"));
+    }
+
+    #[test]
+    fn validate_boolean_schema() {
+        assert!(errors(r#"{"items":true}"#, "[1]").is_empty());
+        assert!(errors(r#"{"items":false}"#, "[]").is_empty());
+        assert!(errors(r#"{"anyOf":[false,true]}"#, "1").is_empty());
+        assert_eq!(
+            vec![err("/0", "/items")],
+            errors(r#"{"items":false}"#, "[1]")
+        );
+        assert_eq!(
+            vec![err("/a", "/properties/a")],
+            errors(r#"{"properties":{"a":false,"b":true}}"#, r#"{"a":1,"b":2}"#)
+        );
+        assert_eq!(vec![err("", "/not")], errors(r#"{"not":true}"#, "1"));
+    }
+
+    #[test]
+    fn validate_reference() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+                "openapi": "3.1.0",
+                "info": {"title": "", "version": ""},
+                "components": {
+                    "schemas": {
+                        "A": {"type": "object", "properties": {"b": {"type": "string"}}}
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+        let v = Validator::new(&doc);
+        let r = v.validate_at(
+            &schema(r##"{"$ref":"#/components/schemas/A"}"##),
+            "/paths/~1a/get",
+            &any(r#"{"b":1}"#),
+        );
+        assert_eq!(1, r.len());
+        assert_eq!("/b", r[0].instance);
+        assert_eq!("/components/schemas/A/properties/b/type", r[0].schema);
+
+        let r = v.validate(
+            &schema(r##"{"$ref":"#/components/schemas/B"}"##),
+            &Any::Null,
+        );
+        assert_eq!("/$ref", r[0].schema);
+    }
+
+    #[test]
+    fn validate_reference_under_id() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+                "openapi": "3.1.0",
+                "info": {"title": "", "version": ""},
+                "components": {
+                    "schemas": {
+                        "A": {
+                            "$id": "https://example.com/schemas/a",
+                            "properties": {"b": {"$ref": "#bee"}, "c": {"$ref": "c.json"}},
+                            "$defs": {"b": {"$anchor": "bee", "type": "string"}}
+                        },
+                        "C": {
+                            "$id": "https://example.com/schemas/c.json",
+                            "properties": {"d": {"$ref": "a#bee"}}
+                        }
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+        let index = SchemaIndex::new(doc.components.as_ref());
+        let v = Validator::new(&index);
+        let a = schema(r##"{"$ref":"#/components/schemas/A"}"##);
+        assert!(v.is_valid(&a, &any(r#"{"b":"1","c":{"d":"2"}}"#)));
+
+        let r = v.validate(&a, &any(r#"{"b":1,"c":{"d":2}}"#));
+        assert_eq!(
+            vec![("/b", "#bee/type"), ("/c/d", "a#bee/type")],
+            r.iter()
+                .map(|e| (e.instance.as_str(), e.schema.as_str()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn validate_discriminator() {
+        let doc = serde_json::from_str::<OpenApi>(
+            r##"{
+                "openapi": "3.1.0",
+                "info": {"title": "", "version": ""},
+                "components": {
+                    "schemas": {
+                        "Pet": {
+                            "oneOf": [
+                                {"$ref": "#/components/schemas/Cat"},
+                                {"$ref": "#/components/schemas/Dog"}
+                            ],
+                            "discriminator": {"propertyName": "type"}
+                        },
+                        "Cat": {"properties": {"type": {"type": "string"}, "a": {"type": "string"}}},
+                        "Dog": {"properties": {"type": {"type": "string"}, "a": {"type": "integer"}}}
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+        let v = Validator::new(&doc);
+        let pet = schema(r##"{"$ref":"#/components/schemas/Pet"}"##);
+
+        // both match without the discriminator.
+        assert!(v.is_valid(&pet, &any(r#"{"type":"Dog","a":1}"#)));
+
+        let r = v.validate(&pet, &any(r#"{"type":"Dog","a":"1"}"#));
+        assert_eq!(1, r.len());
+        assert_eq!("/components/schemas/Dog/properties/a/type", r[0].schema);
+
+        let r = v.validate(&pet, &any(r#"{"type":"Bird"}"#));
+        assert_eq!(1, r.len());
+        assert_eq!("/components/schemas/Pet/discriminator", r[0].schema);
+    }
+}
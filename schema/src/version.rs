@@ -0,0 +1,302 @@
+use super::visit::{Visitor, pointer, walk};
+use super::{MediaType, OpenApi, Parameter, ParameterIn, PathItem, SecurityScheme, Server, Tag};
+use std::fmt;
+
+/// A minor version of the specification.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SpecVersion {
+    V3_0,
+    V3_1,
+    V3_2,
+}
+
+impl SpecVersion {
+    /// The minor version of a version like `3.1.0`.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        match (parts.next()?, parts.next()?) {
+            ("3", "0") => Some(SpecVersion::V3_0),
+            ("3", "1") => Some(SpecVersion::V3_1),
+            ("3", "2") => Some(SpecVersion::V3_2),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SpecVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpecVersion::V3_0 => write!(f, "v3.0"),
+            SpecVersion::V3_1 => write!(f, "v3.1"),
+            SpecVersion::V3_2 => write!(f, "v3.2"),
+        }
+    }
+}
+
+impl OpenApi {
+    pub fn spec_version(&self) -> Option<SpecVersion> {
+        SpecVersion::parse(&self.openapi)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewerFeature {
+    // JSON pointer of the field.
+    pub pointer: String,
+    // the key of the field, or the value like `querystring`.
+    pub name: String,
+    pub since: SpecVersion,
+}
+
+impl fmt::Display for NewerFeature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}` at {} is supported since {}",
+            self.name, self.pointer, self.since
+        )
+    }
+}
+
+/// Collect the features of `doc` which its version does not support.
+///
+/// The model reads fields of any version, so a document of an earlier version
+/// may have them. Documents of an unknown version have none.
+pub fn newer_features(doc: &OpenApi) -> Vec<NewerFeature> {
+    let version = match doc.spec_version() {
+        Some(version) => version,
+        None => return vec![],
+    };
+
+    let mut features = Features {
+        version,
+        features: vec![],
+    };
+    walk(doc, &mut features);
+    features.features.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+    features.features
+}
+
+struct Features {
+    version: SpecVersion,
+    features: Vec<NewerFeature>,
+}
+
+impl Features {
+    fn check(&mut self, ptr: &str, name: &str, used: bool, since: SpecVersion) {
+        if used && self.version < since {
+            self.features.push(NewerFeature {
+                pointer: pointer(ptr, name),
+                name: name.to_string(),
+                since,
+            });
+        }
+    }
+}
+
+impl Visitor for Features {
+    fn visit_openapi(&mut self, ptr: &str, value: &OpenApi) {
+        self.check(ptr, "$self", value.self_uri.is_some(), SpecVersion::V3_2);
+    }
+
+    fn visit_server(&mut self, ptr: &str, value: &Server) {
+        self.check(ptr, "name", value.name.is_some(), SpecVersion::V3_2);
+    }
+
+    fn visit_tag(&mut self, ptr: &str, value: &Tag) {
+        self.check(ptr, "summary", value.summary.is_some(), SpecVersion::V3_2);
+        self.check(ptr, "parent", value.parent.is_some(), SpecVersion::V3_2);
+        self.check(ptr, "kind", value.kind.is_some(), SpecVersion::V3_2);
+    }
+
+    fn visit_path_item(&mut self, ptr: &str, _path: &str, value: &PathItem) {
+        self.check(ptr, "query", value.query.is_some(), SpecVersion::V3_2);
+        self.check(
+            ptr,
+            "additionalOperations",
+            value.additional_operations.is_some(),
+            SpecVersion::V3_2,
+        );
+    }
+
+    fn visit_parameter(&mut self, ptr: &str, value: &Parameter) {
+        if value.r#in == ParameterIn::Querystring && self.version < SpecVersion::V3_2 {
+            self.features.push(NewerFeature {
+                pointer: pointer(ptr, "in"),
+                name: "querystring".to_string(),
+                since: SpecVersion::V3_2,
+            });
+        }
+    }
+
+    fn visit_media_type(&mut self, ptr: &str, _media_type: &str, value: &MediaType) {
+        let used = value.item_schema.is_some();
+        self.check(ptr, "itemSchema", used, SpecVersion::V3_2);
+    }
+
+    fn visit_security_scheme(&mut self, ptr: &str, _name: &str, value: &SecurityScheme) {
+        let deprecated = match value {
+            SecurityScheme::ApiKey(s) => s.deprecated,
+            SecurityScheme::Http(s) => s.deprecated,
+            SecurityScheme::Oauth2(s) => s.deprecated,
+            SecurityScheme::OpenIdConnect(s) => s.deprecated,
+            SecurityScheme::MutualTls(s) => s.deprecated,
+        };
+        self.check(ptr, "deprecated", deprecated.is_some(), SpecVersion::V3_2);
+
+        if let SecurityScheme::Oauth2(s) = value {
+            let used = s.flows.device_authorization.is_some();
+            let ptr = pointer(ptr, "flows");
+            self.check(&ptr, "deviceAuthorization", used, SpecVersion::V3_2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r##"{
+        "openapi": "3.2.0",
+        "$self": "https://example.com/openapi.json",
+        "info": {"title": "", "version": ""},
+        "servers": [{"url": "https://example.com", "name": "production"}],
+        "tags": [{"name": "b", "summary": "B", "parent": "a", "kind": "nav"}],
+        "paths": {
+            "/a": {
+                "query": {
+                    "parameters": [
+                        {"name": "q", "in": "querystring", "content": {"text/plain": {}}}
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "",
+                            "content": {
+                                "text/event-stream": {"itemSchema": {"type": "object"}}
+                            }
+                        }
+                    }
+                },
+                "additionalOperations": {"COPY": {}}
+            }
+        },
+        "components": {
+            "securitySchemes": {
+                "a": {
+                    "type": "oauth2",
+                    "deprecated": true,
+                    "flows": {
+                        "deviceAuthorization": {
+                            "deviceAuthorizationUrl": "https://example.com/device",
+                            "tokenUrl": "https://example.com/token",
+                            "scopes": {}
+                        }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn version_parse() {
+        assert_eq!(Some(SpecVersion::V3_0), SpecVersion::parse("3.0.3"));
+        assert_eq!(Some(SpecVersion::V3_1), SpecVersion::parse("3.1.0"));
+        assert_eq!(Some(SpecVersion::V3_2), SpecVersion::parse("3.2.0"));
+        assert_eq!(None, SpecVersion::parse("2.0"));
+        assert_eq!(None, SpecVersion::parse(""));
+        assert!(SpecVersion::V3_1 < SpecVersion::V3_2);
+    }
+
+    #[test]
+    fn version_newer_features() {
+        let mut doc = serde_json::from_str::<OpenApi>(DOC).unwrap();
+        assert_eq!(Some(SpecVersion::V3_2), doc.spec_version());
+        assert!(newer_features(&doc).is_empty());
+
+        doc.openapi = "3.1.0".to_string();
+        let features = newer_features(&doc);
+        assert_eq!(
+            vec![
+                "/$self",
+                "/components/securitySchemes/a/deprecated",
+                "/components/securitySchemes/a/flows/deviceAuthorization",
+                "/paths/~1a/additionalOperations",
+                "/paths/~1a/query",
+                "/paths/~1a/query/parameters/0/in",
+                "/paths/~1a/query/responses/200/content/text~1event-stream/itemSchema",
+                "/servers/0/name",
+                "/tags/0/kind",
+                "/tags/0/parent",
+                "/tags/0/summary",
+            ],
+            features
+                .iter()
+                .map(|f| f.pointer.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "`querystring` at /paths/~1a/query/parameters/0/in is supported since v3.2",
+            features[5].to_string()
+        );
+
+        doc.openapi = "2.0".to_string();
+        assert!(newer_features(&doc).is_empty());
+    }
+
+    #[test]
+    fn version_examples() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/oas");
+        for (version, expected) in [("v3.0", SpecVersion::V3_0), ("v3.1", SpecVersion::V3_1)] {
+            for entry in std::fs::read_dir(dir.join(version)).unwrap() {
+                let doc = crate::load::from_path::<OpenApi>(&entry.unwrap().path()).unwrap();
+                assert_eq!(Some(expected), doc.spec_version());
+                assert!(newer_features(&doc).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn version_earlier_document() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/version/v3.1-newer-fields.yaml");
+        let doc = crate::load::from_path::<OpenApi>(&path).unwrap();
+        assert_eq!(Some(SpecVersion::V3_1), doc.spec_version());
+
+        // the fields are read, and reported.
+        assert!(doc.self_uri.is_some());
+        assert_eq!(
+            Some("production"),
+            doc.servers.as_ref().unwrap()[0].name.as_deref()
+        );
+        assert_eq!(
+            vec![
+                "/$self",
+                "/paths/~1events/get/responses/200/content/text~1event-stream/itemSchema",
+                "/servers/0/name",
+                "/tags/0/kind",
+            ],
+            newer_features(&doc)
+                .iter()
+                .map(|f| f.pointer.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let s = std::fs::read_to_string(&path).unwrap();
+        let (strict, fields) = crate::strict::from_yaml_strict(&s).unwrap();
+        assert_eq!(doc, strict);
+        assert_eq!(4, fields.len());
+    }
+
+    #[test]
+    fn version_round_trip() {
+        let doc = serde_json::from_str::<OpenApi>(DOC).unwrap();
+        let value = serde_json::to_value(&doc).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(DOC).unwrap(),
+            value
+        );
+
+        let borrowed = serde_json::from_str::<crate::borrowed::OpenApi>(DOC).unwrap();
+        assert_eq!(doc, borrowed.to_owned_model().unwrap());
+    }
+}
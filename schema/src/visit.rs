@@ -0,0 +1,587 @@
+use super::model::Any;
+use super::{
+    BooleanOr, Callback, Components, Encoding, Example, Examples, Header, Link, MediaType, OpenApi,
+    Operation, Parameter, ParameterPattern, PathItem, ReferenceOr, RequestBody, Response,
+    Responses, Schema, SecurityScheme, Server, Tag,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Callbacks invoked by [`walk`] for each object of a document.
+///
+/// Every method receives the JSON pointer of the visited object.
+pub trait Visitor {
+    fn visit_openapi(&mut self, _ptr: &str, _value: &OpenApi) {}
+
+    fn visit_server(&mut self, _ptr: &str, _value: &Server) {}
+
+    fn visit_tag(&mut self, _ptr: &str, _value: &Tag) {}
+
+    fn visit_path_item(&mut self, _ptr: &str, _path: &str, _value: &PathItem) {}
+
+    fn visit_operation(&mut self, _ptr: &str, _path: &str, _method: &str, _value: &Operation) {}
+
+    fn visit_parameter(&mut self, _ptr: &str, _value: &Parameter) {}
+
+    fn visit_request_body(&mut self, _ptr: &str, _value: &RequestBody) {}
+
+    fn visit_media_type(&mut self, _ptr: &str, _media_type: &str, _value: &MediaType) {}
+
+    fn visit_encoding(&mut self, _ptr: &str, _name: &str, _value: &Encoding) {}
+
+    fn visit_responses(&mut self, _ptr: &str, _value: &Responses) {}
+
+    fn visit_response(&mut self, _ptr: &str, _value: &Response) {}
+
+    fn visit_header(&mut self, _ptr: &str, _name: &str, _value: &Header) {}
+
+    fn visit_example(&mut self, _ptr: &str, _value: &Example) {}
+
+    fn visit_link(&mut self, _ptr: &str, _value: &Link) {}
+
+    fn visit_schema(&mut self, _ptr: &str, _value: &Schema) {}
+
+    fn visit_security_scheme(&mut self, _ptr: &str, _name: &str, _value: &SecurityScheme) {}
+
+    fn visit_reference(&mut self, _ptr: &str, _reference: &str) {}
+
+    fn visit_extensions(&mut self, _ptr: &str, _values: &HashMap<String, Any>) {}
+}
+
+/// Append `token` to JSON pointer `base`.
+pub fn pointer(base: &str, token: &str) -> String {
+    format!("{base}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+pub fn walk<V: Visitor + ?Sized>(doc: &OpenApi, visitor: &mut V) {
+    let ptr = "";
+    visitor.visit_openapi(ptr, doc);
+    visitor.visit_extensions(ptr, &doc.extensions.values);
+    visitor.visit_extensions(&pointer(ptr, "info"), &doc.info.extensions.values);
+
+    if let Some(servers) = &doc.servers {
+        walk_servers(&pointer(ptr, "servers"), servers, visitor);
+    }
+
+    if let Some(paths) = &doc.paths {
+        let ptr = pointer(ptr, "paths");
+        visitor.visit_extensions(&ptr, &paths.extensions);
+        for (path, item) in &paths.values {
+            walk_path_item(&pointer(&ptr, path), path, item, visitor);
+        }
+    }
+
+    if let Some(webhooks) = &doc.webhooks {
+        let ptr = pointer(ptr, "webhooks");
+        for (name, item) in webhooks {
+            walk_reference_or(&pointer(&ptr, name), item, visitor, |p, v, w| {
+                walk_path_item(p, name, v, w)
+            });
+        }
+    }
+
+    if let Some(components) = &doc.components {
+        walk_components(&pointer(ptr, "components"), components, visitor);
+    }
+
+    if let Some(tags) = &doc.tags {
+        let ptr = pointer(ptr, "tags");
+        for (i, tag) in tags.iter().enumerate() {
+            let ptr = pointer(&ptr, &i.to_string());
+            visitor.visit_tag(&ptr, tag);
+            visitor.visit_extensions(&ptr, &tag.extensions.values);
+        }
+    }
+}
+
+fn walk_reference_or<T, V, F>(ptr: &str, value: &ReferenceOr<T>, visitor: &mut V, f: F)
+where
+    V: Visitor + ?Sized,
+    F: FnOnce(&str, &T, &mut V),
+{
+    match value {
+        ReferenceOr::Value(v) => f(ptr, v, visitor),
+        ReferenceOr::Ref(r) => visitor.visit_reference(ptr, &r.r#ref),
+    }
+}
+
+fn walk_servers<V: Visitor + ?Sized>(ptr: &str, servers: &[Server], visitor: &mut V) {
+    for (i, server) in servers.iter().enumerate() {
+        walk_server(&pointer(ptr, &i.to_string()), server, visitor);
+    }
+}
+
+fn walk_server<V: Visitor + ?Sized>(ptr: &str, server: &Server, visitor: &mut V) {
+    visitor.visit_server(ptr, server);
+    visitor.visit_extensions(ptr, &server.extensions.values);
+}
+
+fn walk_components<V: Visitor + ?Sized>(ptr: &str, components: &Components, visitor: &mut V) {
+    visitor.visit_extensions(ptr, &components.extensions.values);
+
+    if let Some(schemas) = &components.schemas {
+        let ptr = pointer(ptr, "schemas");
+        for (name, schema) in schemas {
+            walk_schema(&pointer(&ptr, name), schema, visitor);
+        }
+    }
+
+    if let Some(responses) = &components.responses {
+        let ptr = pointer(ptr, "responses");
+        for (name, response) in responses {
+            walk_reference_or(&pointer(&ptr, name), response, visitor, walk_response);
+        }
+    }
+
+    if let Some(parameters) = &components.parameters {
+        let ptr = pointer(ptr, "parameters");
+        for (name, parameter) in parameters {
+            walk_reference_or(&pointer(&ptr, name), parameter, visitor, walk_parameter);
+        }
+    }
+
+    if let Some(examples) = &components.examples {
+        let ptr = pointer(ptr, "examples");
+        for (name, example) in examples {
+            walk_reference_or(&pointer(&ptr, name), example, visitor, walk_example);
+        }
+    }
+
+    if let Some(request_bodies) = &components.request_bodies {
+        let ptr = pointer(ptr, "requestBodies");
+        for (name, body) in request_bodies {
+            walk_reference_or(&pointer(&ptr, name), body, visitor, walk_request_body);
+        }
+    }
+
+    if let Some(headers) = &components.headers {
+        let ptr = pointer(ptr, "headers");
+        for (name, header) in headers {
+            walk_reference_or(&pointer(&ptr, name), header, visitor, |p, v, w| {
+                walk_header(p, name, v, w)
+            });
+        }
+    }
+
+    if let Some(schemes) = &components.security_schemes {
+        let ptr = pointer(ptr, "securitySchemes");
+        for (name, scheme) in schemes {
+            walk_reference_or(&pointer(&ptr, name), scheme, visitor, |p, v, w| {
+                w.visit_security_scheme(p, name, v)
+            });
+        }
+    }
+
+    if let Some(links) = &components.links {
+        let ptr = pointer(ptr, "links");
+        for (name, link) in links {
+            walk_reference_or(&pointer(&ptr, name), link, visitor, walk_link);
+        }
+    }
+
+    if let Some(callbacks) = &components.callbacks {
+        let ptr = pointer(ptr, "callbacks");
+        for (name, callback) in callbacks {
+            walk_reference_or(&pointer(&ptr, name), callback, visitor, walk_callback);
+        }
+    }
+
+    if let Some(path_items) = &components.path_items {
+        let ptr = pointer(ptr, "pathItems");
+        for (name, item) in path_items {
+            walk_reference_or(&pointer(&ptr, name), item, visitor, |p, v, w| {
+                walk_path_item(p, name, v, w)
+            });
+        }
+    }
+}
+
+fn walk_path_item<V: Visitor + ?Sized>(ptr: &str, path: &str, item: &PathItem, visitor: &mut V) {
+    visitor.visit_path_item(ptr, path, item);
+    visitor.visit_extensions(ptr, &item.extensions.values);
+
+    if let Some(r) = &item.r#ref {
+        visitor.visit_reference(ptr, r);
+    }
+
+    if let Some(servers) = &item.servers {
+        walk_servers(&pointer(ptr, "servers"), servers, visitor);
+    }
+
+    if let Some(parameters) = &item.parameters {
+        walk_parameters(&pointer(ptr, "parameters"), parameters, visitor);
+    }
+
+    for (method, op) in item.operations() {
+        walk_operation(&pointer(ptr, method), path, method, op, visitor);
+    }
+
+    if let Some(operations) = &item.additional_operations {
+        let ptr = pointer(ptr, "additionalOperations");
+        for (method, op) in operations {
+            walk_operation(&pointer(&ptr, method), path, method, op, visitor);
+        }
+    }
+}
+
+fn walk_operation<V: Visitor + ?Sized>(
+    ptr: &str,
+    path: &str,
+    method: &str,
+    op: &Operation,
+    visitor: &mut V,
+) {
+    visitor.visit_operation(ptr, path, method, op);
+    visitor.visit_extensions(ptr, &op.extensions.values);
+
+    if let Some(parameters) = &op.parameters {
+        walk_parameters(&pointer(ptr, "parameters"), parameters, visitor);
+    }
+
+    if let Some(body) = &op.request_body {
+        walk_reference_or(
+            &pointer(ptr, "requestBody"),
+            body,
+            visitor,
+            walk_request_body,
+        );
+    }
+
+    if let Some(responses) = &op.responses {
+        walk_responses(&pointer(ptr, "responses"), responses, visitor);
+    }
+
+    if let Some(callbacks) = &op.callbacks {
+        let ptr = pointer(ptr, "callbacks");
+        for (name, callback) in callbacks {
+            walk_reference_or(&pointer(&ptr, name), callback, visitor, walk_callback);
+        }
+    }
+
+    if let Some(servers) = &op.servers {
+        walk_servers(&pointer(ptr, "servers"), servers, visitor);
+    }
+}
+
+fn walk_callback<V: Visitor + ?Sized>(ptr: &str, callback: &Callback, visitor: &mut V) {
+    visitor.visit_extensions(ptr, &callback.extensions);
+    for (expr, item) in &callback.values {
+        walk_reference_or(&pointer(ptr, expr), item, visitor, |p, v, w| {
+            walk_path_item(p, expr, v, w)
+        });
+    }
+}
+
+fn walk_parameters<V: Visitor + ?Sized>(
+    ptr: &str,
+    parameters: &[ReferenceOr<Parameter>],
+    visitor: &mut V,
+) {
+    for (i, parameter) in parameters.iter().enumerate() {
+        walk_reference_or(
+            &pointer(ptr, &i.to_string()),
+            parameter,
+            visitor,
+            walk_parameter,
+        );
+    }
+}
+
+fn walk_parameter<V: Visitor + ?Sized>(ptr: &str, parameter: &Parameter, visitor: &mut V) {
+    visitor.visit_parameter(ptr, parameter);
+    visitor.visit_extensions(ptr, &parameter.extensions.values);
+    walk_parameter_pattern(ptr, &parameter.pattern, visitor);
+}
+
+fn walk_parameter_pattern<V: Visitor + ?Sized>(
+    ptr: &str,
+    pattern: &ParameterPattern,
+    visitor: &mut V,
+) {
+    match pattern {
+        ParameterPattern::Content(c) => walk_content(&pointer(ptr, "content"), &c.content, visitor),
+        ParameterPattern::Style(s) => {
+            if let Some(BooleanOr::Value(schema)) = &s.schema {
+                walk_schema(&pointer(ptr, "schema"), schema, visitor);
+            }
+            walk_examples(ptr, &s.example, visitor);
+        }
+    }
+}
+
+fn walk_examples<V: Visitor + ?Sized>(ptr: &str, examples: &Examples, visitor: &mut V) {
+    if let Examples::Values { examples } = examples {
+        let ptr = pointer(ptr, "examples");
+        for (name, example) in examples {
+            walk_reference_or(&pointer(&ptr, name), example, visitor, walk_example);
+        }
+    }
+}
+
+fn walk_example<V: Visitor + ?Sized>(ptr: &str, example: &Example, visitor: &mut V) {
+    visitor.visit_example(ptr, example);
+    visitor.visit_extensions(ptr, &example.extensions.values);
+}
+
+fn walk_request_body<V: Visitor + ?Sized>(ptr: &str, body: &RequestBody, visitor: &mut V) {
+    visitor.visit_request_body(ptr, body);
+    visitor.visit_extensions(ptr, &body.extensions.values);
+    walk_content(&pointer(ptr, "content"), &body.content, visitor);
+}
+
+fn walk_content<V: Visitor + ?Sized>(
+    ptr: &str,
+    content: &HashMap<String, MediaType>,
+    visitor: &mut V,
+) {
+    for (media_type, value) in content {
+        walk_media_type(&pointer(ptr, media_type), media_type, value, visitor);
+    }
+}
+
+fn walk_media_type<V: Visitor + ?Sized>(
+    ptr: &str,
+    media_type: &str,
+    value: &MediaType,
+    visitor: &mut V,
+) {
+    visitor.visit_media_type(ptr, media_type, value);
+    visitor.visit_extensions(ptr, &value.extensions.values);
+
+    if let Some(BooleanOr::Value(schema)) = &value.schema {
+        walk_schema(&pointer(ptr, "schema"), schema, visitor);
+    }
+
+    if let Some(BooleanOr::Value(schema)) = &value.item_schema {
+        walk_schema(&pointer(ptr, "itemSchema"), schema, visitor);
+    }
+
+    walk_examples(ptr, &value.example, visitor);
+
+    if let Some(encoding) = &value.encoding {
+        let ptr = pointer(ptr, "encoding");
+        for (name, enc) in encoding {
+            let ptr = pointer(&ptr, name);
+            visitor.visit_encoding(&ptr, name, enc);
+            visitor.visit_extensions(&ptr, &enc.extensions.values);
+            if let Some(headers) = &enc.headers {
+                walk_headers(&pointer(&ptr, "headers"), headers, visitor);
+            }
+        }
+    }
+}
+
+fn walk_responses<V: Visitor + ?Sized>(ptr: &str, responses: &Responses, visitor: &mut V) {
+    visitor.visit_responses(ptr, responses);
+    visitor.visit_extensions(ptr, &responses.extensions.values);
+
+    if let Some(default) = &responses.r#default {
+        walk_reference_or(&pointer(ptr, "default"), default, visitor, walk_response);
+    }
+
+    for (status, response) in &responses.statuses.values {
+        walk_reference_or(
+            &pointer(ptr, &status.to_string()),
+            response,
+            visitor,
+            walk_response,
+        );
+    }
+}
+
+fn walk_response<V: Visitor + ?Sized>(ptr: &str, response: &Response, visitor: &mut V) {
+    visitor.visit_response(ptr, response);
+    visitor.visit_extensions(ptr, &response.extensions.values);
+
+    if let Some(headers) = &response.headers {
+        walk_headers(&pointer(ptr, "headers"), headers, visitor);
+    }
+
+    if let Some(content) = &response.content {
+        walk_content(&pointer(ptr, "content"), content, visitor);
+    }
+
+    if let Some(links) = &response.links {
+        let ptr = pointer(ptr, "links");
+        for (name, link) in links {
+            walk_reference_or(&pointer(&ptr, name), link, visitor, walk_link);
+        }
+    }
+}
+
+fn walk_headers<V: Visitor + ?Sized>(
+    ptr: &str,
+    headers: &HashMap<String, ReferenceOr<Header>>,
+    visitor: &mut V,
+) {
+    for (name, header) in headers {
+        walk_reference_or(&pointer(ptr, name), header, visitor, |p, v, w| {
+            walk_header(p, name, v, w)
+        });
+    }
+}
+
+fn walk_header<V: Visitor + ?Sized>(ptr: &str, name: &str, header: &Header, visitor: &mut V) {
+    visitor.visit_header(ptr, name, header);
+    visitor.visit_extensions(ptr, &header.extensions.values);
+    walk_parameter_pattern(ptr, &header.pattern, visitor);
+}
+
+fn walk_link<V: Visitor + ?Sized>(ptr: &str, link: &Link, visitor: &mut V) {
+    visitor.visit_link(ptr, link);
+    visitor.visit_extensions(ptr, &link.extensions.values);
+
+    if let Some(server) = &link.server {
+        walk_server(&pointer(ptr, "server"), server, visitor);
+    }
+}
+
+pub fn walk_schema<V: Visitor + ?Sized>(ptr: &str, schema: &Schema, visitor: &mut V) {
+    visitor.visit_schema(ptr, schema);
+    visitor.visit_extensions(ptr, &schema.extensions.extensions);
+
+    if let Some(r) = &schema.r#ref {
+        visitor.visit_reference(ptr, r);
+    }
+
+    for (key, schemas) in [
+        ("allOf", schema.all_of.as_ref()),
+        ("anyOf", schema.any_of.as_ref()),
+        ("oneOf", schema.one_of.as_ref()),
+        ("prefixItems", schema.prefix_items()),
+    ] {
+        if let Some(schemas) = schemas {
+            let ptr = pointer(ptr, key);
+            for (i, s) in schemas.iter().enumerate() {
+                if let BooleanOr::Value(s) = s.as_ref() {
+                    walk_schema(&pointer(&ptr, &i.to_string()), s, visitor);
+                }
+            }
+        }
+    }
+
+    for (key, s) in [
+        ("not", schema.not()),
+        ("if", schema.r#if()),
+        ("then", schema.then()),
+        ("else", schema.r#else()),
+        ("items", schema.items.as_ref()),
+        ("contains", schema.contains()),
+        ("propertyNames", schema.property_names()),
+        ("contentSchema", schema.content_schema()),
+    ] {
+        if let Some(BooleanOr::Value(s)) = s.map(Arc::as_ref) {
+            walk_schema(&pointer(ptr, key), s, visitor);
+        }
+    }
+
+    for (key, schemas) in [
+        ("properties", schema.properties.as_ref()),
+        ("patternProperties", schema.pattern_properties()),
+        ("dependentSchemas", schema.dependent_schemas()),
+        ("$defs", schema.defs()),
+    ] {
+        if let Some(schemas) = schemas {
+            let ptr = pointer(ptr, key);
+            for (name, s) in schemas {
+                if let BooleanOr::Value(s) = s.as_ref() {
+                    walk_schema(&pointer(&ptr, name), s, visitor);
+                }
+            }
+        }
+    }
+
+    for (key, s) in [
+        (
+            "additionalProperties",
+            schema.additional_properties.as_ref(),
+        ),
+        ("unevaluatedItems", schema.unevaluated_items()),
+        ("unevaluatedProperties", schema.unevaluated_properties()),
+    ] {
+        if let Some(BooleanOr::Value(s)) = s.map(Arc::as_ref) {
+            walk_schema(&pointer(ptr, key), s, visitor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Collect {
+        operations: Vec<String>,
+        references: Vec<String>,
+        schemas: Vec<String>,
+    }
+
+    impl Visitor for Collect {
+        fn visit_operation(&mut self, ptr: &str, _path: &str, _method: &str, _value: &Operation) {
+            self.operations.push(ptr.to_string());
+        }
+
+        fn visit_reference(&mut self, ptr: &str, reference: &str) {
+            self.references.push(format!("{ptr} {reference}"));
+        }
+
+        fn visit_schema(&mut self, ptr: &str, _value: &Schema) {
+            self.schemas.push(ptr.to_string());
+        }
+    }
+
+    #[test]
+    fn pointer_escape() {
+        assert_eq!("/paths/~1a~1{b}", pointer("/paths", "/a/{b}"));
+        assert_eq!("/a~0b", pointer("", "a~b"));
+    }
+
+    #[test]
+    fn walk_document() {
+        let s = r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "paths": {
+                "/a": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/A"}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "A": {"properties": {"b": {"type": "string"}}}
+                }
+            }
+        }"##;
+        let doc = serde_json::from_str::<OpenApi>(s).unwrap();
+        let mut v = Collect::default();
+        walk(&doc, &mut v);
+
+        assert_eq!(vec!["/paths/~1a/get"], v.operations);
+        assert_eq!(
+            vec![
+                "/paths/~1a/get/responses/200/content/application~1json/schema #/components/schemas/A"
+            ],
+            v.references
+        );
+        v.schemas.sort();
+        assert_eq!(
+            vec![
+                "/components/schemas/A",
+                "/components/schemas/A/properties/b",
+                "/paths/~1a/get/responses/200/content/application~1json/schema",
+            ],
+            v.schemas
+        );
+    }
+}
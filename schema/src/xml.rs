@@ -0,0 +1,957 @@
+use super::merge::MergeError;
+use super::model::Any;
+use super::resolve::{Resolver, component_name};
+use super::validate::Validator;
+use super::visit::pointer;
+use super::{BooleanOr, Schema, SchemaOrBool, SchemaType, SchemaTypes, Xml};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum XmlError {
+    // malformed document, at the byte offset.
+    Syntax { position: usize, message: String },
+    // text which can not be read as the type of the schema.
+    InvalidValue { pointer: String, value: String },
+    // an array or object placed in an attribute.
+    InvalidAttribute(String),
+    NotFoundReference(String),
+    Merge(MergeError),
+    // nested too deeply, or a cycle of `$ref`.
+    TooDeep,
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            XmlError::Syntax { position, message } => {
+                write!(f, "Invalid XML at {position}: {message}.")
+            }
+            XmlError::InvalidValue { pointer, value } => {
+                let ptr = if pointer.is_empty() { "/" } else { pointer };
+                write!(f, "Invalid value `{value}` at {ptr}.")
+            }
+            XmlError::InvalidAttribute(pointer) => {
+                write!(f, "Value at {pointer} can not be an attribute.")
+            }
+            XmlError::NotFoundReference(r) => write!(f, "Not found reference `{r}`."),
+            XmlError::Merge(e) => e.fmt(f),
+            XmlError::TooDeep => write!(f, "XML is nested too deeply."),
+        }
+    }
+}
+
+impl std::error::Error for XmlError {}
+
+impl From<MergeError> for XmlError {
+    fn from(e: MergeError) -> Self {
+        match e {
+            MergeError::NotFoundReference(r) => XmlError::NotFoundReference(r),
+            e => XmlError::Merge(e),
+        }
+    }
+}
+
+/// Converts between XML documents and values, following `xml` of schemas.
+///
+/// The root element is named by `xml.name`, or the component name of `$ref`.
+/// Object properties are written in the order of their names.
+pub struct XmlCodec<'a, R: Resolver + ?Sized> {
+    resolver: &'a R,
+    validator: Validator<'a, R>,
+}
+
+impl<'a, R: Resolver + ?Sized> XmlCodec<'a, R> {
+    pub fn new(resolver: &'a R) -> Self {
+        XmlCodec {
+            resolver,
+            validator: Validator::new(resolver),
+        }
+    }
+
+    pub fn encode(&self, schema: &Schema, value: &Any) -> Result<String, XmlError> {
+        let name = schema
+            .r#ref
+            .as_deref()
+            .and_then(|r| component_name(r, "schemas"))
+            .unwrap_or("root".to_string());
+        let element = self.element(&name, schema, value, "", &[], 0)?;
+        let mut out = String::new();
+        element.write(&mut out);
+        Ok(out)
+    }
+
+    pub fn decode(&self, schema: &Schema, xml: &str) -> Result<Any, XmlError> {
+        let root = Parser::new(xml).document()?;
+        self.read(&root, schema, "", 0)
+    }
+
+    fn element(
+        &self,
+        name: &str,
+        schema: &Schema,
+        value: &Any,
+        ptr: &str,
+        scope: &[(String, String)],
+        depth: usize,
+    ) -> Result<Element, XmlError> {
+        if depth > MAX_DEPTH {
+            return Err(XmlError::TooDeep);
+        }
+        let schema = self.select(schema, value)?;
+        let xml = schema.xml().cloned().unwrap_or_default();
+        let mut element = Element {
+            prefix: xml.prefix.clone(),
+            name: xml.name.clone().unwrap_or(name.to_string()),
+            ..Default::default()
+        };
+        let mut scope = scope.to_vec();
+        element.declare(&xml, &mut scope);
+
+        match value {
+            Any::Object(values) => {
+                let mut keys = values.keys().collect::<Vec<_>>();
+                keys.sort();
+                for key in keys {
+                    let ptr = pointer(ptr, key);
+                    let v = &values[key];
+                    if *v == Any::Null {
+                        continue;
+                    }
+                    let property = property(&schema, key);
+                    let p = self.select(property.schema(), v)?;
+                    let p_xml = p.xml().cloned().unwrap_or_default();
+
+                    if p_xml.attribute == Some(true) {
+                        let value = text(v).ok_or(XmlError::InvalidAttribute(ptr))?;
+                        if p_xml.prefix.is_some() {
+                            element.declare(&p_xml, &mut scope);
+                        }
+                        element.attributes.push(Attribute {
+                            prefix: p_xml.prefix,
+                            name: p_xml.name.unwrap_or(key.clone()),
+                            value,
+                            ..Default::default()
+                        });
+                        continue;
+                    }
+
+                    let items = match v {
+                        Any::Array(items) => items,
+                        v => {
+                            let child = self.element(key, &p, v, &ptr, &scope, depth + 1)?;
+                            element.children.push(Node::Element(child));
+                            continue;
+                        }
+                    };
+                    let item_schema = p.items.as_deref().unwrap_or(&BooleanOr::Boolean(true));
+                    let mut children = vec![];
+                    for (i, item) in items.iter().enumerate() {
+                        let ptr = pointer(&ptr, &i.to_string());
+                        let child =
+                            self.element(key, item_schema.schema(), item, &ptr, &scope, depth + 1)?;
+                        children.push(Node::Element(child));
+                    }
+                    if p_xml.wrapped == Some(true) {
+                        let mut wrapper = Element {
+                            prefix: p_xml.prefix.clone(),
+                            name: p_xml.name.clone().unwrap_or(key.clone()),
+                            children,
+                            ..Default::default()
+                        };
+                        wrapper.declare(&p_xml, &mut scope.clone());
+                        element.children.push(Node::Element(wrapper));
+                    } else {
+                        element.children.extend(children);
+                    }
+                }
+            }
+            // an array at the root, or in an array, is always wrapped.
+            Any::Array(items) => {
+                let item_schema = schema.items.as_deref().unwrap_or(&BooleanOr::Boolean(true));
+                for (i, item) in items.iter().enumerate() {
+                    let ptr = pointer(ptr, &i.to_string());
+                    let child =
+                        self.element(name, item_schema.schema(), item, &ptr, &scope, depth + 1)?;
+                    element.children.push(Node::Element(child));
+                }
+            }
+            Any::Null => {}
+            v => element
+                .children
+                .push(Node::Text(text(v).unwrap_or_default())),
+        }
+        Ok(element)
+    }
+
+    fn read(
+        &self,
+        element: &Element,
+        schema: &Schema,
+        ptr: &str,
+        depth: usize,
+    ) -> Result<Any, XmlError> {
+        if depth > MAX_DEPTH {
+            return Err(XmlError::TooDeep);
+        }
+        let schema = self.normalize(schema)?;
+        let branches = match schema.one_of.as_ref().or(schema.any_of.as_ref()) {
+            Some(branches) => branches,
+            None => return self.read_as(element, &schema, ptr, depth),
+        };
+
+        // the first branch which reads the element to a valid value.
+        let mut first = None;
+        for branch in branches.iter().filter(|b| !b.is_false()) {
+            let s = self.with_branch(&schema, branch)?;
+            match self.read_as(element, &s, ptr, depth) {
+                Ok(v) if self.validator.is_valid(&s, &v) => return Ok(v),
+                v => {
+                    first.get_or_insert(v);
+                }
+            }
+        }
+        first.unwrap_or_else(|| self.read_as(element, &Schema::default(), ptr, depth))
+    }
+
+    fn read_as(
+        &self,
+        element: &Element,
+        schema: &Schema,
+        ptr: &str,
+        depth: usize,
+    ) -> Result<Any, XmlError> {
+        let types = types(schema);
+        let is = |t: SchemaType| types.contains(&t);
+        if is(SchemaType::Object) || (types.is_empty() && schema.properties.is_some()) {
+            return self.object(element, schema, ptr, depth);
+        }
+        if is(SchemaType::Array) || (types.is_empty() && schema.items.is_some()) {
+            let items = schema.items.as_deref().unwrap_or(&BooleanOr::Boolean(true));
+            let mut values = vec![];
+            for (i, child) in element.elements().enumerate() {
+                let ptr = pointer(ptr, &i.to_string());
+                values.push(self.read(child, items.schema(), &ptr, depth + 1)?);
+            }
+            return Ok(Any::Array(values));
+        }
+        if types.is_empty() && element.elements().next().is_some() {
+            return self.object(element, schema, ptr, depth);
+        }
+        scalar(&element.text(), &types, ptr)
+    }
+
+    fn object(
+        &self,
+        element: &Element,
+        schema: &Schema,
+        ptr: &str,
+        depth: usize,
+    ) -> Result<Any, XmlError> {
+        let mut values = HashMap::new();
+        let children = element.elements().collect::<Vec<_>>();
+        let mut used = vec![false; children.len()];
+
+        let mut properties = schema.properties.iter().flatten().collect::<Vec<_>>();
+        properties.sort_by_key(|(key, _)| *key);
+        for (key, property) in properties {
+            if property.is_false() {
+                continue;
+            }
+            let ptr = pointer(ptr, key);
+            let p = self.normalize(property.schema())?;
+            let p_xml = p.xml().cloned().unwrap_or_default();
+            let name = p_xml.name.clone().unwrap_or(key.clone());
+
+            if p_xml.attribute == Some(true) {
+                let attribute = element.attributes.iter().find(|a| {
+                    a.name == name && (a.prefix.is_none() || a.namespace == p_xml.namespace)
+                });
+                if let Some(a) = attribute {
+                    values.insert(key.clone(), scalar(&a.value, &types(&p), &ptr)?);
+                }
+                continue;
+            }
+
+            if !types(&p).contains(&SchemaType::Array) {
+                if let Some(i) = find(&children, &used, &name, &p_xml) {
+                    used[i] = true;
+                    values.insert(key.clone(), self.read(children[i], &p, &ptr, depth + 1)?);
+                }
+                continue;
+            }
+
+            let item_schema = p.items.as_deref().unwrap_or(&BooleanOr::Boolean(true));
+            let items = self.normalize(item_schema.schema())?;
+            let item_xml = items.xml().cloned().unwrap_or_default();
+            let item_name = item_xml.name.clone().unwrap_or(key.clone());
+            let mut found = vec![];
+            if p_xml.wrapped == Some(true) {
+                if let Some(i) = find(&children, &used, &name, &p_xml) {
+                    used[i] = true;
+                    found.extend(
+                        children[i]
+                            .elements()
+                            .filter(|c| c.is(&item_name, &item_xml)),
+                    );
+                    values.insert(key.clone(), Any::Array(vec![]));
+                }
+            } else {
+                for (i, child) in children.iter().enumerate() {
+                    if !used[i] && child.is(&item_name, &item_xml) {
+                        used[i] = true;
+                        found.push(child);
+                    }
+                }
+            }
+            if found.is_empty() {
+                continue;
+            }
+            let mut array = vec![];
+            for (i, child) in found.into_iter().enumerate() {
+                let ptr = pointer(&ptr, &i.to_string());
+                array.push(self.read(child, &items, &ptr, depth + 1)?);
+            }
+            values.insert(key.clone(), Any::Array(array));
+        }
+
+        // elements which are not declared, with `additionalProperties`.
+        let additional = schema
+            .additional_properties
+            .as_deref()
+            .unwrap_or(&BooleanOr::Boolean(true));
+        if !additional.is_false() {
+            for (i, child) in children.iter().enumerate() {
+                if used[i] || values.contains_key(&child.name) {
+                    continue;
+                }
+                let ptr = pointer(ptr, &child.name);
+                let v = self.read(child, additional.schema(), &ptr, depth + 1)?;
+                match values.get_mut(&child.name) {
+                    Some(Any::Array(array)) => array.push(v),
+                    Some(other) => *other = Any::Array(vec![other.clone(), v]),
+                    None => {
+                        values.insert(child.name.clone(), v);
+                    }
+                }
+            }
+        }
+        Ok(Any::Object(values))
+    }
+
+    // the schema with `$ref` resolved and `allOf` merged, and the branch which `value` satisfies.
+    fn select(&self, schema: &Schema, value: &Any) -> Result<Schema, XmlError> {
+        let schema = self.normalize(schema)?;
+        let branches = match schema.one_of.as_ref().or(schema.any_of.as_ref()) {
+            Some(branches) => branches,
+            None => return Ok(schema),
+        };
+        match branches
+            .iter()
+            .find(|b| self.validator.is_valid(b.schema(), value))
+        {
+            Some(branch) => self.with_branch(&schema, branch),
+            None => Ok(schema),
+        }
+    }
+
+    fn with_branch(&self, schema: &Schema, branch: &Arc<SchemaOrBool>) -> Result<Schema, XmlError> {
+        let base = Schema {
+            one_of: None,
+            any_of: None,
+            ..schema.clone()
+        };
+        let merged = Schema {
+            all_of: Some(vec![base.into(), branch.clone()]),
+            ..Default::default()
+        };
+        self.normalize(&merged)
+    }
+
+    fn normalize(&self, schema: &Schema) -> Result<Schema, XmlError> {
+        if schema.r#ref.is_none() && schema.all_of.is_none() {
+            return Ok(schema.clone());
+        }
+        let wrapped = Schema {
+            all_of: Some(vec![schema.clone().into()]),
+            ..Default::default()
+        };
+        Ok(wrapped.merge_all_of(self.resolver)?)
+    }
+}
+
+// the schema of the property `key`, with `additionalProperties` for undeclared ones.
+fn property<'s>(schema: &'s Schema, key: &str) -> &'s SchemaOrBool {
+    static ANY: SchemaOrBool = BooleanOr::Boolean(true);
+    match schema.properties.as_ref().and_then(|p| p.get(key)) {
+        Some(p) => p,
+        None => schema.additional_properties.as_deref().unwrap_or(&ANY),
+    }
+}
+
+fn types(schema: &Schema) -> Vec<SchemaType> {
+    let mut types = match &schema.r#type {
+        Some(SchemaTypes::Unit(t)) => vec![t.clone()],
+        Some(SchemaTypes::Array(v)) => v.clone(),
+        None => vec![],
+    };
+    if schema.nullable == Some(true) && !types.is_empty() {
+        types.push(SchemaType::Null);
+    }
+    types
+}
+
+fn find(children: &[&Element], used: &[bool], name: &str, xml: &Xml) -> Option<usize> {
+    (0..children.len()).find(|i| !used[*i] && children[*i].is(name, xml))
+}
+
+fn text(value: &Any) -> Option<String> {
+    match value {
+        Any::Boolean(b) => Some(b.to_string()),
+        Any::Integer(i) => Some(i.to_string()),
+        Any::Number(n) => Some(n.to_string()),
+        Any::String(s) => Some(s.clone()),
+        Any::Null => Some(String::new()),
+        Any::Array(_) | Any::Object(_) => None,
+    }
+}
+
+// text read as the first of `types` which accepts it, or a string without types.
+fn scalar(s: &str, types: &[SchemaType], ptr: &str) -> Result<Any, XmlError> {
+    if types.is_empty() {
+        return Ok(Any::String(s.to_string()));
+    }
+    if s.is_empty() && types.contains(&SchemaType::Null) {
+        return Ok(Any::Null);
+    }
+    let trimmed = s.trim();
+    for t in types {
+        let v = match t {
+            SchemaType::String => Some(Any::String(s.to_string())),
+            SchemaType::Integer => trimmed.parse().ok().map(Any::Integer),
+            SchemaType::Number => match trimmed.parse() {
+                Ok(i) => Some(Any::Integer(i)),
+                Err(_) => trimmed.parse().ok().map(Any::Number),
+            },
+            SchemaType::Boolean => match trimmed {
+                "true" | "1" => Some(Any::Boolean(true)),
+                "false" | "0" => Some(Any::Boolean(false)),
+                _ => None,
+            },
+            SchemaType::Null | SchemaType::Object | SchemaType::Array => None,
+        };
+        if let Some(v) = v {
+            return Ok(v);
+        }
+    }
+    Err(XmlError::InvalidValue {
+        pointer: ptr.to_string(),
+        value: s.to_string(),
+    })
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Element {
+    prefix: Option<String>,
+    name: String,
+    // the namespace URI of the prefix, only for parsed elements.
+    namespace: Option<String>,
+    attributes: Vec<Attribute>,
+    children: Vec<Node>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Attribute {
+    prefix: Option<String>,
+    name: String,
+    namespace: Option<String>,
+    value: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|n| match n {
+            Node::Element(e) => Some(e),
+            Node::Text(_) => None,
+        })
+    }
+
+    fn text(&self) -> String {
+        let mut s = String::new();
+        for n in &self.children {
+            if let Node::Text(t) = n {
+                s.push_str(t);
+            }
+        }
+        s
+    }
+
+    // the namespace is compared only if the schema declares it.
+    fn is(&self, name: &str, xml: &Xml) -> bool {
+        self.name == name && (xml.namespace.is_none() || self.namespace == xml.namespace)
+    }
+
+    // declare `namespace` of `xml` unless it is already in `scope`.
+    fn declare(&mut self, xml: &Xml, scope: &mut Vec<(String, String)>) {
+        let namespace = match &xml.namespace {
+            Some(namespace) => namespace,
+            None => return,
+        };
+        let prefix = xml.prefix.clone().unwrap_or_default();
+        let declared = scope.iter().rev().find(|(p, _)| *p == prefix);
+        if declared.is_some_and(|(_, n)| n == namespace) {
+            return;
+        }
+        let name = match prefix.is_empty() {
+            true => "xmlns".to_string(),
+            false => format!("xmlns:{prefix}"),
+        };
+        self.attributes.push(Attribute {
+            name,
+            value: namespace.clone(),
+            ..Default::default()
+        });
+        scope.push((prefix, namespace.clone()));
+    }
+
+    fn write(&self, out: &mut String) {
+        let name = qualified(&self.prefix, &self.name);
+        out.push('<');
+        out.push_str(&name);
+        for a in &self.attributes {
+            out.push(' ');
+            out.push_str(&qualified(&a.prefix, &a.name));
+            out.push_str("=\"");
+            out.push_str(&escape(&a.value, true));
+            out.push('"');
+        }
+        if self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+        out.push('>');
+        for n in &self.children {
+            match n {
+                Node::Element(e) => e.write(out),
+                Node::Text(t) => out.push_str(&escape(t, false)),
+            }
+        }
+        out.push_str("</");
+        out.push_str(&name);
+        out.push('>');
+    }
+}
+
+fn qualified(prefix: &Option<String>, name: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{prefix}:{name}"),
+        None => name.to_string(),
+    }
+}
+
+fn escape(s: &str, attribute: bool) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn split_name(name: &str) -> (Option<String>, String) {
+    match name.split_once(':') {
+        Some((prefix, name)) => (Some(prefix.to_string()), name.to_string()),
+        None => (None, name.to_string()),
+    }
+}
+
+// a parser of elements, attributes, text, CDATA and namespaces, without DTD.
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+    // declared namespaces, where the default namespace has the empty prefix.
+    scope: Vec<(String, String)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser {
+            s,
+            pos: 0,
+            scope: vec![(
+                "xml".to_string(),
+                "http://www.w3.org/XML/1998/namespace".to_string(),
+            )],
+        }
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, XmlError> {
+        Err(XmlError::Syntax {
+            position: self.pos,
+            message: message.to_string(),
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    // skip to the end of `end`.
+    fn skip_to(&mut self, end: &str) -> Result<&'a str, XmlError> {
+        match self.rest().find(end) {
+            Some(i) => {
+                let skipped = &self.rest()[..i];
+                self.pos += i + end.len();
+                Ok(skipped)
+            }
+            None => self.error(&format!("missing `{end}`")),
+        }
+    }
+
+    // comments, processing instructions and the document type.
+    fn skip_misc(&mut self) -> Result<(), XmlError> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.skip_to("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_to("-->")?;
+            } else if rest.starts_with("<!DOCTYPE") {
+                if rest
+                    .find('[')
+                    .is_some_and(|i| i < rest.find('>').unwrap_or(0))
+                {
+                    self.skip_to("]")?;
+                }
+                self.skip_to(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn document(&mut self) -> Result<Element, XmlError> {
+        self.skip_misc()?;
+        let root = self.element()?;
+        self.skip_misc()?;
+        if !self.rest().is_empty() {
+            return self.error("content after the root element");
+        }
+        Ok(root)
+    }
+
+    fn name(&mut self) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || "/>=<\"'".contains(c))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return self.error("missing name");
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn element(&mut self) -> Result<Element, XmlError> {
+        if !self.rest().starts_with('<') {
+            return self.error("missing element");
+        }
+        self.pos += 1;
+        let tag = self.name()?;
+
+        let mut attributes = vec![];
+        let scope = self.scope.len();
+        let empty = loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                break true;
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                break false;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return self.error("missing `=`");
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return self.error("missing quote"),
+            };
+            self.pos += 1;
+            let raw = self.skip_to(&quote.to_string())?;
+            let value = self.unescape(raw)?;
+
+            match split_name(name) {
+                (None, n) if n == "xmlns" => self.scope.push((String::new(), value)),
+                (Some(p), n) if p == "xmlns" => self.scope.push((n, value)),
+                (prefix, name) => attributes.push(Attribute {
+                    prefix,
+                    name,
+                    namespace: None,
+                    value,
+                }),
+            }
+        };
+
+        let (prefix, name) = split_name(tag);
+        for a in &mut attributes {
+            a.namespace = a.prefix.as_deref().and_then(|p| self.namespace(p));
+        }
+        let mut element = Element {
+            namespace: self.namespace(prefix.as_deref().unwrap_or_default()),
+            prefix,
+            name,
+            attributes,
+            children: vec![],
+        };
+
+        if !empty {
+            self.content(&mut element)?;
+            let end = self.name()?;
+            if end != tag {
+                return self.error(&format!("`</{end}>` does not close `<{tag}>`"));
+            }
+            self.skip_whitespace();
+            if !self.rest().starts_with('>') {
+                return self.error("missing `>`");
+            }
+            self.pos += 1;
+        }
+        self.scope.truncate(scope);
+        Ok(element)
+    }
+
+    // children until the end tag, which is consumed up to its name.
+    fn content(&mut self, element: &mut Element) -> Result<(), XmlError> {
+        let mut text = String::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return self.error("missing end tag");
+            }
+            if rest.starts_with("</") {
+                self.pos += 2;
+                break;
+            }
+            if rest.starts_with("<!--") {
+                self.skip_to("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                text.push_str(self.skip_to("]]>")?);
+            } else if rest.starts_with("<?") {
+                self.skip_to("?>")?;
+            } else if rest.starts_with('<') {
+                if !text.is_empty() {
+                    element.children.push(Node::Text(std::mem::take(&mut text)));
+                }
+                let child = self.element()?;
+                element.children.push(Node::Element(child));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                text.push_str(&self.unescape(&rest[..len])?);
+                self.pos += len;
+            }
+        }
+        if !text.is_empty() {
+            element.children.push(Node::Text(text));
+        }
+        // whitespace between elements is not content.
+        if element.elements().next().is_some() {
+            element
+                .children
+                .retain(|n| !matches!(n, Node::Text(t) if t.trim().is_empty()));
+        }
+        Ok(())
+    }
+
+    fn namespace(&self, prefix: &str) -> Option<String> {
+        self.scope
+            .iter()
+            .rev()
+            .find(|(p, _)| p == prefix)
+            .map(|(_, n)| n.clone())
+            .filter(|n| !n.is_empty())
+    }
+
+    fn unescape(&self, s: &str) -> Result<String, XmlError> {
+        let mut unescaped = String::new();
+        let mut rest = s;
+        while let Some(i) = rest.find('&') {
+            unescaped.push_str(&rest[..i]);
+            let end = match rest[i..].find(';') {
+                Some(end) => i + end,
+                None => return self.error("missing `;` of the entity"),
+            };
+            let c = match &rest[i + 1..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                e => match e.strip_prefix("#x").or(e.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                    None => e
+                        .strip_prefix('#')
+                        .and_then(|d| d.parse().ok())
+                        .and_then(char::from_u32),
+                },
+            };
+            match c {
+                Some(c) => unescaped.push(c),
+                None => return self.error(&format!("unknown entity `{}`", &rest[i..=end])),
+            }
+            rest = &rest[end + 1..];
+        }
+        unescaped.push_str(rest);
+        Ok(unescaped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenApi;
+
+    fn doc() -> OpenApi {
+        serde_json::from_str::<OpenApi>(
+            r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "", "version": ""},
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "xml": {"namespace": "https://example.com/schema", "prefix": "ex"},
+                        "properties": {
+                            "id": {"type": "integer", "xml": {"attribute": true}},
+                            "name": {"type": "string", "xml": {"name": "petName"}},
+                            "tags": {
+                                "type": "array",
+                                "xml": {"wrapped": true},
+                                "items": {"type": "string", "xml": {"name": "tag"}}
+                            },
+                            "photos": {"type": "array", "items": {"type": "string"}},
+                            "owner": {"$ref": "#/components/schemas/Owner"}
+                        }
+                    },
+                    "Owner": {
+                        "type": "object",
+                        "xml": {"name": "person"},
+                        "properties": {"verified": {"type": "boolean"}}
+                    }
+                }
+            }
+        }"##,
+        )
+        .unwrap()
+    }
+
+    fn any(s: &str) -> Any {
+        serde_json::from_str(s).unwrap()
+    }
+
+    fn pet() -> Schema {
+        serde_json::from_str(r##"{"$ref": "#/components/schemas/Pet"}"##).unwrap()
+    }
+
+    #[test]
+    fn xml_encode() {
+        let doc = doc();
+        let value = any(
+            r#"{"id": 1, "name": "a<b", "tags": ["x", "y"], "photos": ["p"], "owner": {"verified": true}}"#,
+        );
+        assert_eq!(
+            concat!(
+                r#"<ex:Pet xmlns:ex="https://example.com/schema" id="1">"#,
+                "<petName>a&lt;b</petName>",
+                "<person><verified>true</verified></person>",
+                "<photos>p</photos>",
+                "<tags><tag>x</tag><tag>y</tag></tags>",
+                "</ex:Pet>"
+            ),
+            XmlCodec::new(&doc).encode(&pet(), &value).unwrap()
+        );
+
+        let value = any(r#"{"id": [1]}"#);
+        assert_eq!(
+            Err(XmlError::InvalidAttribute("/id".to_string())),
+            XmlCodec::new(&doc).encode(&pet(), &value)
+        );
+    }
+
+    #[test]
+    fn xml_decode() {
+        let doc = doc();
+        let xml = r#"<?xml version="1.0"?>
+            <!-- pet -->
+            <p:Pet xmlns:p="https://example.com/schema" id=" 2">
+                <petName><![CDATA[a<b]]> &amp; c</petName>
+                <tags><tag>x</tag><tag>y</tag></tags>
+                <photos>p1</photos>
+                <photos>p2</photos>
+                <person><verified>1</verified></person>
+                <extra><a>b</a></extra>
+            </p:Pet>"#;
+        let v = XmlCodec::new(&doc).decode(&pet(), xml).unwrap();
+        assert_eq!(
+            any(
+                r#"{"id": 2, "name": "a<b & c", "tags": ["x", "y"], "photos": ["p1", "p2"],
+                    "owner": {"verified": true}, "extra": {"a": "b"}}"#
+            ),
+            v
+        );
+
+        assert!(matches!(
+            XmlCodec::new(&doc).decode(&pet(), r#"<Pet id="x"/>"#),
+            Err(XmlError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            XmlCodec::new(&doc).decode(&pet(), "<Pet></Dog>"),
+            Err(XmlError::Syntax { .. })
+        ));
+    }
+
+    #[test]
+    fn xml_round_trip() {
+        let doc = doc();
+        let codec = XmlCodec::new(&doc);
+        let schema = serde_json::from_str::<Schema>(
+            r#"{
+                "type": "array",
+                "xml": {"name": "numbers"},
+                "items": {"oneOf": [{"type": "integer"}, {"type": "boolean"}], "xml": {"name": "n"}}
+            }"#,
+        )
+        .unwrap();
+        let value = any("[1, true, -3]");
+        let xml = codec.encode(&schema, &value).unwrap();
+        assert_eq!("<numbers><n>1</n><n>true</n><n>-3</n></numbers>", xml);
+        assert_eq!(value, codec.decode(&schema, &xml).unwrap());
+    }
+}